@@ -0,0 +1,127 @@
+// Animated GIF images, looping forever, with all of their frames in a palette of evenly spaced colors.
+
+const LEVELS: u16 = 6; // of each of red, green and blue in the palette
+const MIN_CODE_SIZE: u8 = 8; // bits of a palette index
+const MAX_CODE_SIZE: u8 = 12;
+const CLEAR_CODE: u16 = 1 << MIN_CODE_SIZE;
+const END_CODE: u16 = CLEAR_CODE + 1;
+
+// of the closest color in the palette
+pub fn palette_index(red: u8, green: u8, blue: u8) -> u8 {
+    let level = |channel: u8| (u16::from(channel) * (LEVELS - 1) + 127) / 255;
+    (level(red) * LEVELS * LEVELS + level(green) * LEVELS + level(blue)) as u8
+}
+
+fn palette() -> Vec<u8> {
+    let value = |level: u16| (level * 255 / (LEVELS - 1)) as u8;
+    let mut palette: Vec<u8> = (0..LEVELS.pow(3))
+        .flat_map(|index| vec![value(index / (LEVELS * LEVELS)), value(index / LEVELS % LEVELS), value(index % LEVELS)])
+        .collect();
+    palette.resize(3 << MIN_CODE_SIZE, 0);
+    palette
+}
+
+pub struct Animation {
+    width: u16,
+    height: u16,
+    delay: u16, // between frames, in hundredths of a second
+    frames: Vec<Vec<u8>>, // palette indices, row by row
+}
+
+impl Animation {
+    pub fn new(width: u16, height: u16, delay: u16) -> Self {
+        Self { width, height, delay, frames: Vec::new() }
+    }
+    pub fn push(&mut self, frame: Vec<u8>) {
+        assert_eq!(frame.len(), usize::from(self.width) * usize::from(self.height), "frame of another size");
+        self.frames.push(frame);
+    }
+    pub fn frames(&self) -> usize {
+        self.frames.len()
+    }
+    pub fn encoded(&self) -> Vec<u8> {
+        let mut bytes = b"GIF89a".to_vec();
+        bytes.extend_from_slice(&self.width.to_le_bytes());
+        bytes.extend_from_slice(&self.height.to_le_bytes());
+        bytes.extend_from_slice(&[0x80 | (MIN_CODE_SIZE - 1) << 4 | (MIN_CODE_SIZE - 1), 0, 0]); // global palette
+        bytes.extend_from_slice(&palette());
+        bytes.extend_from_slice(&[0x21, 0xff, 0x0b]);
+        bytes.extend_from_slice(b"NETSCAPE2.0");
+        bytes.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]); // looping forever
+
+        for frame in &self.frames {
+            bytes.extend_from_slice(&[0x21, 0xf9, 0x04, 0x00]);
+            bytes.extend_from_slice(&self.delay.to_le_bytes());
+            bytes.extend_from_slice(&[0x00, 0x00]);
+            bytes.extend_from_slice(&[0x2c, 0, 0, 0, 0]);
+            bytes.extend_from_slice(&self.width.to_le_bytes());
+            bytes.extend_from_slice(&self.height.to_le_bytes());
+            bytes.extend_from_slice(&[0x00, MIN_CODE_SIZE]);
+            for block in compressed(frame).chunks(255) {
+                bytes.push(block.len() as u8);
+                bytes.extend_from_slice(block);
+            }
+            bytes.push(0x00);
+        }
+        bytes.push(0x3b);
+        bytes
+    }
+}
+
+// Codes of variable size, packed from the least significant bit on.
+struct BitWriter {
+    bytes: Vec<u8>,
+    pending: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.pending |= u32::from(code) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.pending as u8);
+            self.pending >>= 8;
+            self.bits -= 8;
+        }
+    }
+    fn finished(mut self) -> Vec<u8> {
+        if self.bits > 0 { self.bytes.push(self.pending as u8); }
+        self.bytes
+    }
+}
+
+// LZW, as the decoder expects it: it learns every code one step later than it is made, so it grows the code size
+// one step later too, and the table is started over once it is full.
+pub fn compressed(indices: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter { bytes: Vec::new(), pending: 0, bits: 0 };
+    let mut size = MIN_CODE_SIZE + 1;
+    writer.write(CLEAR_CODE, size);
+    let (first, rest) = match indices.split_first() { Some(split) => split, None => { writer.write(END_CODE, size); return writer.finished(); } };
+
+    let mut table = std::collections::HashMap::new();
+    let mut next_code = END_CODE + 1;
+    let mut prefix = u16::from(*first);
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix, size);
+        if next_code < 1 << MAX_CODE_SIZE {
+            table.insert((prefix, index), next_code);
+            next_code += 1;
+            if next_code == (1 << size) + 1 && size < MAX_CODE_SIZE { size += 1; }
+        } else {
+            writer.write(CLEAR_CODE, size);
+            table.clear();
+            next_code = END_CODE + 1;
+            size = MIN_CODE_SIZE + 1;
+        }
+        prefix = u16::from(index);
+    }
+    writer.write(prefix, size);
+    if next_code == 1 << size && size < MAX_CODE_SIZE { size += 1; }
+    writer.write(END_CODE, size);
+    writer.finished()
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::gif::{compressed, palette_index, Animation};
+
+    // the other way around, as a GIF decoder would do it
+    fn decompressed(bytes: &[u8]) -> Vec<u8> {
+        let (mut position, mut size) = (0, 9);
+        let mut read = |size: usize| {
+            let code = (0..size).fold(0, |code, bit| code | usize::from(bytes[(position + bit) / 8] >> ((position + bit) % 8) & 1) << bit);
+            position += size;
+            code
+        };
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let mut indices = Vec::new();
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            let code = read(size);
+            match code {
+                256 => { table = (0..=255).map(|index| vec![index]).chain(vec![vec![], vec![]]).collect(); size = 9; previous = None; continue; }
+                257 => return indices,
+                _ => {}
+            }
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => [previous.clone(), vec![previous[0]]].concat(),
+                (None, None) => panic!("unknown code {}", code),
+            };
+            if let Some(previous) = previous {
+                if table.len() < 4096 { table.push([previous, vec![entry[0]]].concat()); }
+                if table.len() == 1 << size && size < 12 { size += 1; }
+            }
+            indices.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn colors_map_to_the_closest_in_the_palette() {
+        assert_eq!(palette_index(0, 0, 0), 0);
+        assert_eq!(palette_index(255, 255, 255), 215);
+        assert_eq!(palette_index(0, 0, 255), 5);
+        assert_eq!(palette_index(250, 10, 0), 180);
+    }
+
+    #[test]
+    fn frames_survive_compression_past_a_full_table() {
+        let mut seed = 1u32;
+        let indices: Vec<u8> = (0..50_000).map(|_| { seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345); (seed >> 16) as u8 % 7 }).collect();
+        assert_eq!(decompressed(&compressed(&indices)), indices);
+        assert_eq!(decompressed(&compressed(&[3; 1000])), vec![3; 1000]);
+        assert_eq!(decompressed(&compressed(&[])), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn animation_loops_with_one_image_per_frame() {
+        let mut animation = Animation::new(2, 2, 4);
+        animation.push(vec![0, 1, 2, 3]);
+        animation.push(vec![3, 2, 1, 0]);
+        let bytes = animation.encoded();
+        assert_eq!(&bytes[..10], b"GIF89a\x02\x00\x02\x00");
+        assert!(bytes.windows(11).any(|window| window == b"NETSCAPE2.0"));
+        assert_eq!(bytes.windows(6).filter(|&window| window == [0x21, 0xf9, 0x04, 0x00, 0x04, 0x00]).count(), 2);
+        assert_eq!(bytes.last(), Some(&0x3b));
+    }
+}
@@ -0,0 +1,44 @@
+use std::collections::VecDeque;
+
+// Ring buffer of the most recent states, which can be stepped through back and forth;
+// recording a new state while stepped back discards the ones that followed.
+pub struct History<T> {
+    states: VecDeque<T>,
+    capacity: usize,
+    current: usize, // index of the state stepped back to, if not the last one
+}
+
+impl<T> History<T> {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { states: VecDeque::with_capacity(capacity), capacity, current: 0 }
+    }
+
+    pub fn record(&mut self, state: T) {
+        if !self.states.is_empty() { self.states.truncate(self.current + 1); }
+        if self.states.len() >= self.capacity { self.states.pop_front(); }
+        self.states.push_back(state);
+        self.current = self.states.len() - 1;
+    }
+
+    pub fn back(&mut self) -> Option<&T> {
+        if self.current == 0 { return None; }
+        self.current -= 1;
+        self.states.get(self.current)
+    }
+
+    pub fn forward(&mut self) -> Option<&T> {
+        if self.current + 1 >= self.states.len() { return None; }
+        self.current += 1;
+        self.states.get(self.current)
+    }
+
+    pub fn clear(&mut self) {
+        self.states.clear();
+        self.current = 0;
+    }
+
+    // how many states back from the most recent one
+    pub fn rewound_by(&self) -> usize {
+        self.states.len().saturating_sub(self.current + 1)
+    }
+}
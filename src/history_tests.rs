@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use crate::history::History;
+
+    fn history_of(states: &[u32], capacity: usize) -> History<u32> {
+        let mut history = History::with_capacity(capacity);
+        for &state in states { history.record(state); }
+        history
+    }
+
+    #[test]
+    fn steps_back_and_forward_through_recorded_states() {
+        let mut history = history_of(&[1, 2, 3], 10);
+        assert_eq!(history.back(), Some(&2));
+        assert_eq!(history.back(), Some(&1));
+        assert_eq!(history.back(), None);
+        assert_eq!(history.rewound_by(), 2);
+        assert_eq!(history.forward(), Some(&2));
+        assert_eq!(history.forward(), Some(&3));
+        assert_eq!(history.forward(), None);
+    }
+
+    #[test]
+    fn oldest_states_are_forgotten_beyond_capacity() {
+        let mut history = history_of(&[1, 2, 3, 4], 2);
+        assert_eq!(history.back(), Some(&3));
+        assert_eq!(history.back(), None);
+    }
+
+    #[test]
+    fn recording_after_stepping_back_discards_later_states() {
+        let mut history = history_of(&[1, 2, 3], 10);
+        history.back();
+        history.back();
+        history.record(4);
+        assert_eq!(history.rewound_by(), 0);
+        assert_eq!(history.forward(), None);
+        assert_eq!(history.back(), Some(&1));
+    }
+
+    #[test]
+    fn cleared_history_cannot_be_stepped_through() {
+        let mut history = history_of(&[1, 2, 3], 10);
+        history.clear();
+        assert_eq!(history.back(), None);
+        history.record(4);
+        assert_eq!(history.back(), None);
+    }
+}
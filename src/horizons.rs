@@ -0,0 +1,75 @@
+use crate::maths::{Coordinate, EuclideanVector, Real};
+use crate::physics::Body;
+
+const START_OF_TABLE: &str = "$$SOE";
+const END_OF_TABLE: &str = "$$EOE";
+const TARGET_NAME: &str = "Target body name:";
+const METERS_PER_KILOMETER: Real = 1000.;
+
+// A single row of a vector table exported from JPL Horizons in CSV format, with the default km and km/s units,
+// converted to meters and meters per second, with the ecliptic for the plane of the simulation.
+#[derive(Copy, Clone)]
+pub struct StateVector {
+    pub epoch: Real, // Julian day number
+    pub position: Coordinate,
+    pub velocity: EuclideanVector,
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    MissingTable,
+    MalformedRow(String),
+    NoStateAt(Real),
+}
+
+impl std::fmt::Display for ImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::MissingTable => write!(f, "no {}...{} table of state vectors", START_OF_TABLE, END_OF_TABLE),
+            Self::MalformedRow(row) => write!(f, "malformed row: {}", row),
+            Self::NoStateAt(epoch) => write!(f, "no state vectors around JD {}", epoch),
+        }
+    }
+}
+
+pub fn target_name(export: &str) -> Option<&str> {
+    let line = export.lines().find_map(|line| line.trim().strip_prefix(TARGET_NAME))?;
+    line.split('(').next().map(str::trim).filter(|name| !name.is_empty())
+}
+
+pub fn state_vectors(export: &str) -> Result<Vec<StateVector>, ImportError> {
+    let table = export.split(START_OF_TABLE).nth(1).and_then(|rest| rest.split(END_OF_TABLE).next()).ok_or(ImportError::MissingTable)?;
+    table.lines().map(str::trim).filter(|row| !row.is_empty()).map(parse_row).collect()
+}
+
+// columns: JDTDB, calendar date, X, Y, Z, VX, VY, VZ, and possibly more
+fn parse_row(row: &str) -> Result<StateVector, ImportError> {
+    let columns: Vec<&str> = row.split(',').map(str::trim).collect();
+    let number = |index: usize| columns.get(index).and_then(|column| column.parse::<Real>().ok()).ok_or_else(|| ImportError::MalformedRow(row.to_string()));
+
+    Ok(StateVector {
+        epoch: number(0)?,
+        position: Coordinate { x: number(2)? * METERS_PER_KILOMETER, y: number(3)? * METERS_PER_KILOMETER, z: number(4)? * METERS_PER_KILOMETER },
+        velocity: EuclideanVector { dx: number(5)? * METERS_PER_KILOMETER, dy: number(6)? * METERS_PER_KILOMETER, dz: number(7)? * METERS_PER_KILOMETER },
+    })
+}
+
+// the state closest to the given epoch, which has to lie within the exported time span
+pub fn state_at(states: &[StateVector], epoch: Real) -> Option<StateVector> {
+    let first = states.iter().map(|state| state.epoch).fold(Real::INFINITY, Real::min);
+    let last = states.iter().map(|state| state.epoch).fold(Real::NEG_INFINITY, Real::max);
+    if epoch < first || epoch > last { return None; }
+
+    states.iter().copied().min_by(|a, b| {
+        (a.epoch - epoch).abs().partial_cmp(&(b.epoch - epoch).abs()).unwrap_or(std::cmp::Ordering::Equal)
+    })
+}
+
+// masses are not part of the export, so they have to be given; the epoch defaults to the first exported one
+pub fn body_from(export: &str, mass: Real, epoch: Option<Real>) -> Result<Body, ImportError> {
+    let states = state_vectors(export)?;
+    let epoch = epoch.or_else(|| states.first().map(|state| state.epoch)).ok_or(ImportError::MissingTable)?;
+    let state = state_at(&states, epoch).ok_or(ImportError::NoStateAt(epoch))?;
+
+    Ok(Body::new().named(target_name(export).unwrap_or("unnamed")).with_mass(mass).at(state.position).moving(state.velocity))
+}
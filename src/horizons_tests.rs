@@ -0,0 +1,63 @@
+#[cfg(test)]
+mod tests {
+    use crate::horizons::{body_from, state_at, state_vectors, target_name, ImportError};
+
+    const EXPORT: &str = "\
+*******************************************************************************
+ Revised: July 31, 2013             Earth                              399
+Target body name: Earth (399)                     {source: DE441}
+Center body name: Sun (10)                        {source: DE441}
+*******************************************************************************
+            JDTDB,            Calendar Date (TDB),                      X,                      Y,                      Z,                     VX,                     VY,                     VZ,
+**************************************************************************************************************************************************************************************************
+$$SOE
+2451545.000000000, A.D. 2000-Jan-01 12:00:00.0000, -2.649903367743050E+07,  1.327574173547367E+08,  5.755671847212622E+04, -2.979426007043741E+01, -5.018052308799903E+00,  3.480917883880235E-04,
+2451546.000000000, A.D. 2000-Jan-02 12:00:00.0000, -2.907131054663360E+07,  1.323055322198360E+08,  5.758456567339599E+04, -2.974929493877078E+01, -5.441568324574990E+00,  3.254393262488937E-04,
+$$EOE
+**************************************************************************************************************************************************************************************************
+";
+
+    #[test]
+    fn target_name_is_read_from_the_header() {
+        assert_eq!(target_name(EXPORT), Some("Earth"));
+        assert_eq!(target_name("nothing here"), None);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", allow(clippy::excessive_precision))]
+    fn state_vectors_are_converted_to_meters() {
+        let states = state_vectors(EXPORT).unwrap();
+        assert_eq!(states.len(), 2);
+        assert_eq!(states[0].epoch, 2451545.);
+        assert!((states[0].position.y - 1.327574173547367E+11).abs() < 1.);
+        assert!((states[0].velocity.dx + 2.979426007043741E+04).abs() < 1e-6);
+    }
+
+    #[test]
+    fn closest_state_to_the_epoch_is_chosen() {
+        let states = state_vectors(EXPORT).unwrap();
+        assert_eq!(state_at(&states, 2451545.8).unwrap().epoch, 2451546.);
+        assert!(state_at(&states, 2451550.).is_none());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", allow(clippy::excessive_precision))]
+    fn body_is_created_at_the_chosen_epoch() {
+        let body = body_from(EXPORT, 5.972e24, Some(2451546.)).unwrap();
+        assert_eq!(body.name, "Earth");
+        assert_eq!(body.mass, 5.972e24);
+        assert!((body.position.x + 2.907_131_054_663_36e10).abs() < 1.);
+    }
+
+    #[test]
+    fn missing_table_is_reported() {
+        assert!(matches!(state_vectors("no table"), Err(ImportError::MissingTable)));
+        assert!(matches!(body_from(EXPORT, 1., Some(0.)), Err(ImportError::NoStateAt(_))));
+    }
+
+    #[test]
+    fn malformed_row_is_reported() {
+        let export = "$$SOE\n2451545.0, date, 1.0, oops, 0.0, 1.0, 1.0, 0.0,\n$$EOE";
+        assert!(matches!(state_vectors(export), Err(ImportError::MalformedRow(_))));
+    }
+}
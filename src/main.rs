@@ -1,100 +1,889 @@
+#[macro_use]
+mod serialization;
+mod gif;
+mod gif_tests;
+mod history;
+mod history_tests;
+mod horizons;
+mod horizons_tests;
 mod maths;
 mod maths_tests;
 mod physics;
 mod physics_tests;
+mod scenarios;
+mod scenarios_tests;
+mod tle;
+mod tle_tests;
 
 use chrono::prelude::*;
 use gdk::{keys, ScrollDirection};
 use gio::prelude::*;
 use gtk::prelude::*;
-use maths::{Coordinate, EuclideanVector};
-use physics::Body;
+use history::History;
+use maths::{widened, Aabb, Coordinate, EuclideanVector, Random, Real, Transform};
+use physics::{Body, BodyId, Constants, DirectSummation, ForceModel, ForceSolver};
+use physics::collision::{CollisionMode, Collisions};
+#[cfg(feature = "gpu")]
+use physics::compute::Compute;
+use physics::conservation::{potential_at, Totals};
+use physics::engine::Engine;
+use physics::flyby::{is_approaching, Flyby};
+use physics::fmm::FastMultipole;
+use physics::integration::{self, Integrator};
+use physics::kernel::Vectorized;
+use physics::lagrange::lagrange_points;
+use physics::maneuver::Maneuver;
+use physics::orbit::{self, Elements};
+use physics::quantities::{Length, Mass};
+use physics::swarm::{self, Particle, Ring};
+use physics::tidal::{self, TidalDisruption, TidalLocking};
+use physics::transfer::Hohmann;
+use physics::tree::BarnesHut;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env::args;
 use std::f64::consts::PI;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
 const VECTOR_MAGNIFICATION: f64 = 25.;
 const REFRESH_RATE: u32 = 50; // per second
 const UPDATE_RATE: u32 = 50; // per second
-const TRAIL_HISTORY: u32 = 2000;
+const RATE_TOLERANCE: f64 = 0.9; // of the intended update or refresh rate, below which falling behind is warned of
+const TRAIL_LENGTHS: [u32; 7] = [0, 250, 500, 1000, 2000, 4000, 8000]; // updates trails reach back, 0 for no trails
+const TRAIL_SPACINGS: [u64; 5] = [1, 2, 5, 10, 25]; // updates between points of a trail
+const TIME_SCALES: [Real; 13] = [0.1, 0.2, 0.5, 1., 2., 5., 10., 20., 50., 100., 200., 500., 1000.]; // of simulated time per update
+const TRAIL_SHADES: u64 = 16; // stretches of a trail stroked at once, each fainter than the one after
 const SCROLL_STEP: f64 = 25.;
+const OPENING_ANGLE: Real = 0.5; // of Barnes-Hut, unless given on the command line
+const OPENING_ANGLE_STEP: Real = 0.1;
+const MAX_OPENING_ANGLE: Real = 1.5; // beyond which whole octants of the tree are taken as single bodies
+// of colors, evenly spaced from the lowest value to the highest
+type Ramp = &'static [(f64, f64, f64)];
+const RAMPS: [(&str, Ramp); 3] = [
+    ("viridis", &[(0.27, 0., 0.33), (0.23, 0.32, 0.55), (0.13, 0.57, 0.55), (0.37, 0.79, 0.38), (0.99, 0.91, 0.14)]),
+    ("heat", &[(0.3, 0., 0.), (0.8, 0.1, 0.), (1., 0.6, 0.), (1., 1., 0.6)]),
+    ("gray", &[(0.3, 0.3, 0.3), (1., 1., 1.)]),
+];
+#[cfg(not(feature = "gpu"))]
+const SOLVERS: [&str; 4] = ["direct", "vectorized", "barnes-hut", "fmm"]; // as ordered in Situation::solvers
+#[cfg(feature = "gpu")]
+const SOLVERS: [&str; 5] = ["direct", "vectorized", "barnes-hut", "fmm", "compute"];
+const RESTITUTION_STEP: Real = 0.1;
+const SOFTENING_STEP: Real = 0.5;
+const SPEED_OF_LIGHT: Real = 30.;
+const ENGINE_ROTATION_STEP: Real = maths::consts::PI / 36.;
+const THROTTLE_STEP: Real = 0.1;
+const PREDICTION_STEPS: usize = 500;
+const REWIND_HISTORY: usize = 30 * UPDATE_RATE as usize; // updates
+const MIN_APSIDES_ECCENTRICITY: Real = 0.001; // below that apsides are too ill-defined to be worth showing
+const FLIGHT_DATA_WIDTH: f64 = 280.; // of the panel in the top right corner
+const MANEUVER_LEAD: u64 = PREDICTION_STEPS as u64 / 4; // updates ahead of which maneuver nodes are placed
+const MANEUVER_SHIFT: u64 = 10; // updates by which maneuver nodes are moved along the trajectory
+const MANEUVER_BURN_STEP: Real = 0.01; // fraction of the body's orbital speed by which burns are adjusted
+const RING_SEED: u64 = 0x5269_6e67; // of the rings given on the command line
+const MINIMAP_SIZE: f64 = 160.; // in pixels, across the minimap
+const MINIMAP_MARGIN: f64 = 10.; // in pixels, between the minimap and the corner of the window
+const HEATMAP_CELL: f64 = 16.; // in pixels, across each cell of the potential heatmap
+const GRAPH_LENGTH: u64 = 3000; // updates the graphs reach back
+const GRAPH_WIDTH: f64 = 300.; // in pixels
+const GRAPH_HEIGHT: f64 = 40.; // in pixels, of each of the graphs
+const SCALE_BAR_MAX_LENGTH: f64 = 150.; // in pixels
+const GRID_MIN_SPACING: f64 = 40.; // in pixels, between the lines of the grid at least
+const STARFIELD_SEED: u64 = 0x5374_6172;
+const STARFIELD_TILE: f64 = 512.; // in pixels, across which the starfield repeats
+const STARFIELD_STARS: usize = 120; // in a tile
+const STARFIELD_SHADES: usize = 4; // of gray the stars are in, from dim to bright
+const TILT_STEP: Real = maths::consts::PI / 36.;
+const MAX_TILT: Real = maths::consts::PI * 4. / 9.; // beyond that the plane gets too thin to make anything out
+const FRAMED_FILL: Real = 0.9; // of the window, taken by the bounding box of all the bodies when framing them
+const CAMERA_GLIDE: u32 = UPDATE_RATE / 2; // updates it takes the camera to move over to what it is to look at next
+const CAMERA_EASING: Real = 0.2; // fraction of the remaining tilt the camera turns by on every update
+const CLICK_TOLERANCE: f64 = 5.; // pixels by which a click may miss a body and still pick it
+const CAMERA_MENU_BODIES: usize = 8; // heaviest ones offered to follow the midpoint of along with the tracked body
+const BODY_LIST_REFRESH: u32 = 500; // milliseconds between updates of the body list
+const BODY_LIST_WIDTH: i32 = 260; // in pixels, to begin with
+const DUPLICATE_OFFSET: Real = 3.; // radii away from the original its duplicate is put at
+const PLACED_MASS_FRACTION: Real = 1e-3; // of the heaviest body, which bodies are placed with to begin with
+const PLACED_MASS_STEP: Real = 10.; // factor by which the mass of the body being placed is adjusted
+const SCREENSHOT_WIDTH: i32 = 1920; // in pixels
+const SCREENSHOT_HEIGHT: i32 = 1080; // in pixels
+const FIGURE_WIDTH: f64 = 960.; // in points
+const FIGURE_HEIGHT: f64 = 540.; // in points
+const RECORDING_WIDTH: i32 = 1280; // in pixels
+const RECORDING_HEIGHT: i32 = 720; // in pixels
+const ANIMATION_WIDTH: u16 = 480; // in pixels
+const ANIMATION_HEIGHT: u16 = 270; // in pixels
+const ANIMATION_EVERY: u64 = 5; // frame shown that is kept in an animation
+const ANIMATION_FRAMES: usize = 250; // at most, after which the animation is saved
+const TRACKED_RING_GAP: f64 = 4.; // between the tracked body and the ring around it, in pixels
+const LABEL_GAP: f64 = 7.; // between a body and its label, in pixels
+const LABEL_HEIGHT: f64 = 10.; // in pixels, taken up by a label along with the space between the lines
+const TRANSFER_TARGET_STEP: Real = 1.1; // factor by which the target radius of a Hohmann transfer is adjusted
 
-struct Mark {
+// where a body has been, outliving it for as long as it reaches back
+struct Trail {
+    points: VecDeque<(Coordinate, u64)>, // along with the update each was passed on, oldest first
+    color: (f64, f64, f64),
+}
+
+impl Trail {
+    const fn new(color: (f64, f64, f64)) -> Self {
+        Self { points: VecDeque::new(), color }
+    }
+    fn forget_before(&mut self, update: u64) {
+        while self.points.front().is_some_and(|&(_, passed)| passed < update) { self.points.pop_front(); }
+    }
+}
+
+// position on the screen, or within the window, in pixels
+#[derive(Copy, Clone)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+// Maps world coordinates, in whatever units the scenario uses, onto the screen before zooming, with the center of
+// the camera at the origin. The plane of the simulation may be tilted away from the camera around the horizontal
+// axis, foreshortening it vertically and lifting whatever lies in front of it up the screen.
+#[derive(Clone)]
+struct View {
+    transform: Transform,
+    time_step: Real, // vectors show how far they would move a body in VECTOR_MAGNIFICATION updates
+    velocities: bool, // whether to show the velocity vectors of bodies
+    forces: bool, // whether to show the vectors of forces acting on bodies
+    colors: HashMap<BodyId, (f64, f64, f64)>, // of bodies colored by a property rather than their own color
+    tracked: Option<BodyId>, // ringed
+    quality: Quality,
+}
+
+impl View {
+    fn at(&self, position: Coordinate) -> Point {
+        let point = self.transform.world_to_screen(position);
+        Point { x: widened(point.x), y: widened(point.y) }
+    }
+    // of vectors already in screen units
+    fn projected(&self, vector: EuclideanVector) -> EuclideanVector {
+        self.transform.tilted(vector)
+    }
+    fn length(&self, length: Real) -> Real {
+        length * self.transform.scale
+    }
+}
+
+// what is needed to resume the simulation from a past update
+#[derive(Clone)]
+struct Moment {
+    bodies: Vec<Body>,
+    updates: u64,
+}
+
+// of a body, as edited by the user
+#[derive(Clone, Debug, PartialEq)]
+struct Properties {
+    name: String,
+    mass: Real,
     position: Coordinate,
-    age: u32,
+    velocity: EuclideanVector,
+}
+
+impl Properties {
+    fn of(body: &Body) -> Self {
+        Self { name: body.name.clone(), mass: body.mass, position: body.position, velocity: body.velocity }
+    }
+}
+
+// of something happening, as achieved over the last second rather than as intended
+struct Rate {
+    since: Instant, // when counting started
+    times: VecDeque<Instant>, // of the last second
+}
+
+impl Rate {
+    fn new(since: Instant) -> Self {
+        Self { since, times: VecDeque::new() }
+    }
+    fn tick(&mut self, now: Instant) {
+        self.times.push_back(now);
+        while self.times.front().is_some_and(|&time| now.duration_since(time) >= Duration::from_secs(1)) { self.times.pop_front(); }
+    }
+    // none until counted for a whole second
+    fn per_second(&self, now: Instant) -> Option<usize> {
+        if now.duration_since(self.since) < Duration::from_secs(1) { return None; }
+        Some(self.times.iter().filter(|&&time| now.duration_since(time) < Duration::from_secs(1)).count())
+    }
+}
+
+// of the gravitational potential, over cells covering the window, as of when it was last worth computing
+struct Heatmap {
+    transform: Transform, // of the view it was computed for
+    half_size: Point, // of the window it covers
+    positions: Vec<Coordinate>, // of the bodies it was computed for
+    columns: usize,
+    potentials: Vec<Real>, // of the middle of each cell, row by row
+}
+
+impl Heatmap {
+    // once the view, or any of the bodies, has moved by half a cell or more
+    fn is_stale(&self, bodies: &[Body], transform: &Transform, half_size: Point) -> bool {
+        let moved = |from: Coordinate, to: Coordinate| widened((to - from).magnitude() * transform.scale) >= HEATMAP_CELL / 2.;
+        let origin = Coordinate { x: 0., y: 0., z: 0. };
+        self.half_size.x != half_size.x || self.half_size.y != half_size.y
+            || self.transform.scale != transform.scale || self.transform.tilt != transform.tilt
+            || moved(self.transform.screen_to_world(origin), transform.screen_to_world(origin))
+            || self.positions.len() != bodies.len()
+            || self.positions.iter().zip(bodies).any(|(&position, body)| moved(position, body.position))
+    }
+}
+
+// of the frames shown, writing some of them out as numbered images, to be made into a video
+struct Recorder {
+    directory: String,
+    every: u64, // frame shown that is written out
+    frames: u64, // shown since the recording started
+}
+
+// of the frames shown, keeping some of them to be saved as one looping image once stopped
+struct Animator {
+    path: String,
+    animation: gif::Animation,
+    frames: u64, // shown since the animation started
+}
+
+// a moment along with the camera looking at it, to repeat an experiment from
+#[derive(Clone)]
+struct Snapshot {
+    moment: Moment,
+    zoom_exponent: f64,
+    translation: EuclideanVector,
+    tracked_body: Option<usize>,
+    following: Option<Following>,
+    particles: Vec<Particle>,
+}
+
+// what the camera may follow other than a single body
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Following {
+    Barycenter, // of all the bodies
+    Midpoint(usize, usize), // of the two bodies
+}
+
+// the property of bodies they are colored by, if any
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Coloring {
+    Plain,
+    Mass,
+    Speed,
+    Acceleration,
+}
+
+impl Coloring {
+    fn next(self) -> Self {
+        match self {
+            Self::Plain => Self::Mass,
+            Self::Mass => Self::Speed,
+            Self::Speed => Self::Acceleration,
+            Self::Acceleration => Self::Plain,
+        }
+    }
+    fn value_of(self, body: &Body) -> Option<f64> {
+        match self {
+            Self::Plain => None,
+            Self::Mass => Some(widened(body.mass)),
+            Self::Speed => Some(widened(body.velocity.magnitude())),
+            Self::Acceleration if body.mass > 0. => {
+                let net_force = body.forces.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, &force| sum + force);
+                Some(widened(net_force.magnitude() / body.mass))
+            }
+            Self::Acceleration => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Coloring {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Plain => write!(f, "plain"),
+            Self::Mass => write!(f, "mass"),
+            Self::Speed => write!(f, "speed"),
+            Self::Acceleration => write!(f, "acceleration"),
+        }
+    }
+}
+
+// of rendering, from quick enough for weak machines to fine enough for screenshots
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Quality {
+    Low,
+    Medium,
+    High,
 }
 
-impl Mark {
-    const fn new(at: Coordinate) -> Self {
-        Self { position: at, age: 0 }
+impl Quality {
+    fn next(self) -> Self {
+        match self {
+            Self::Low => Self::Medium,
+            Self::Medium => Self::High,
+            Self::High => Self::Low,
+        }
+    }
+    fn named(name: &str) -> Option<Self> {
+        IntoIterator::into_iter([Self::Low, Self::Medium, Self::High]).find(|quality| quality.to_string() == name)
+    }
+    fn antialias(self) -> cairo::Antialias {
+        match self {
+            Self::Low => cairo::Antialias::None,
+            Self::Medium => cairo::Antialias::Default,
+            Self::High => cairo::Antialias::Best,
+        }
     }
-    fn update(&mut self) {
-        self.age += 1;
+    fn text_antialias(self) -> cairo::Antialias {
+        match self {
+            Self::Low => cairo::Antialias::None,
+            Self::Medium => cairo::Antialias::Default,
+            Self::High => cairo::Antialias::Gray,
+        }
+    }
+    // of trails, in pixels at the default zoom like all of the sizes below, which grow with the quality
+    fn trail_width(self) -> f64 {
+        match self {
+            Self::Low => 1.,
+            Self::Medium => 2.,
+            Self::High => 2.5,
+        }
+    }
+    // of particles, and of bodies in performance mode
+    fn dot_size(self) -> f64 {
+        match self {
+            Self::Low | Self::Medium => 1.,
+            Self::High => 1.5,
+        }
+    }
+    // of velocity and force vectors
+    fn vector_width(self) -> f64 {
+        match self {
+            Self::Low => 1.,
+            Self::Medium => 2.,
+            Self::High => 2.5,
+        }
+    }
+}
+
+impl std::fmt::Display for Quality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Medium => write!(f, "medium"),
+            Self::High => write!(f, "high"),
+        }
     }
 }
 
+// of the given value within the given range, from 0 to 1, on a logarithmic scale unless there are values of 0 or less
+fn ramp_position(value: f64, lowest: f64, highest: f64) -> f64 {
+    let position = if lowest > 0. { (value / lowest).ln() / (highest / lowest).ln() } else { (value - lowest) / (highest - lowest) };
+    if position.is_finite() { position.clamp(0., 1.) } else { 0.5 }
+}
+
+fn ramp_color(ramp: &[(f64, f64, f64)], position: f64) -> (f64, f64, f64) {
+    let scaled = position.clamp(0., 1.) * (ramp.len() - 1) as f64;
+    let below = (scaled.floor() as usize).min(ramp.len() - 2);
+    let (fraction, (lower, upper)) = (scaled - below as f64, (ramp[below], ramp[below + 1]));
+    let mix = |lower: f64, upper: f64| lower + (upper - lower) * fraction;
+    (mix(lower.0, upper.0), mix(lower.1, upper.1), mix(lower.2, upper.2))
+}
+
 struct Situation {
     bodies: Vec<Body>,
-    marks: Vec<Mark>,
+    particles: Vec<Particle>, // not rewound along with the bodies, to keep the history light
+    trails: HashMap<BodyId, Trail>,
+    trail_length: u32, // updates trails reach back, with none left at all if 0
+    trail_spacing: u64, // updates between points of a trail
     updates: u64,
     zoom_exponent: f64,
     fullscreen: bool,
     paused: bool,
+    editing: Option<bool>, // whether it was paused before the body editor was opened, while that is open
     translation: EuclideanVector,
-    drag_start: Coordinate,
+    drag_start: Point,
     tracked_body: Option<usize>,
+    integrator: Integrator,
+    substeps: u32,
+    solvers: Vec<Box<dyn ForceSolver>>,
+    solver: usize,
+    opening_angle: Real, // of the Barnes-Hut solver
+    force_models: Vec<Box<dyn ForceModel>>, // acting besides the gravity of the solver
+    collisions: Collisions,
+    tidal_disruption: TidalDisruption,
+    tidal_locking: TidalLocking,
+    constants: Constants,
+    pixels_per_unit: Real,
+    time_step: Real, // simulated time per update
+    time_scale: Real, // faster by as many whole updates at a time, slower by shorter ones
+    tilt: Real, // of the camera, away from looking straight down at the plane of the simulation
+    tilt_target: Real, // the camera eases towards
+    glide: Option<(EuclideanVector, u32)>, // center the camera glides away from, and updates it has left to get there
+    reversed: bool, // whether time runs backwards
+    history: History<Moment>,
+    snapshot: Option<Snapshot>,
+    ship: Option<usize>, // the body piloted by the user, if any
+    prediction: Vec<Vec<Coordinate>>, // predicted path of each body
+    always_predict: bool, // otherwise only when paused
+    show_lagrange_points: bool,
+    show_velocities: bool,
+    show_forces: bool,
+    show_hud: bool, // the text in the top left corner
+    show_labels: bool,
+    show_grid: bool,
+    show_minimap: bool,
+    coloring: Coloring,
+    ramp: usize, // index into RAMPS
+    quality: Quality,
+    frame_rate: Rate,
+    update_rate: Rate,
+    hidden_trails: HashSet<BodyId>, // of bodies which leave none
+    hidden_labels: HashSet<BodyId>, // of bodies which are not labelled
+    placing: Option<Body>, // being put in by the user, with its velocity dragged out from it
+    record_to: Option<String>, // directory to record frames to, instead of one named after the time recording starts
+    record_every: u64, // frame shown that is recorded
+    recorder: Option<Recorder>,
+    animator: Option<Animator>,
+    clean: bool, // showing nothing but the bodies and their trails, as for screenshots
+    following: Option<Following>,
+    show_hill_spheres: bool,
+    transfer_target: Option<Length>, // radius of the orbit to plan a Hohmann transfer of the tracked body to, if any
+    maneuver: Option<Maneuver>,
+    maneuver_prediction: Option<(Coordinate, Vec<Coordinate>)>, // where the maneuver happens, and the path after it
+    maneuver_predicted_for: Option<(Maneuver, u64, u64)>, // the maneuver, update and checksum of the bodies it was made for
+    log_flybys: bool, // to the standard output, as they happen
+    performance: bool, // for thousands of bodies: no substeps, trails, rewind history, totals or predictions, and bodies as dots
+    totals: Option<Totals>,
+    initial_totals: Option<Totals>, // taken on the first update, to measure the drift against
+    graphed: VecDeque<(u64, Totals, usize)>, // totals and the number of bodies of each update the graphs reach back, oldest first
+    show_graphs: bool,
+    heatmap: Option<Heatmap>, // only while shown
+    show_heatmap: bool,
+}
+
+// What is simulated and how, down to the trails, leaving out the view, the history and the force models, which are
+// the standard ones anyway. The solvers are made anew by `loaded`, with the opening angle loaded.
+serializable!(Situation {
+    updates, pixels_per_unit, time_step, time_scale, reversed, integrator, solver, opening_angle, trail_length, trail_spacing,
+    constants, collisions, tidal_disruption, tidal_locking, bodies,
+} or Situation::new());
+
+// as ordered in SOLVERS
+#[cfg(not(feature = "gpu"))]
+fn solvers(opening_angle: Real) -> Vec<Box<dyn ForceSolver>> {
+    vec![Box::new(DirectSummation), Box::new(Vectorized), Box::new(BarnesHut::with_opening_angle(opening_angle)), Box::new(FastMultipole)]
+}
+#[cfg(feature = "gpu")]
+fn solvers(opening_angle: Real) -> Vec<Box<dyn ForceSolver>> {
+    vec![Box::new(DirectSummation), Box::new(Vectorized), Box::new(BarnesHut::with_opening_angle(opening_angle)), Box::new(FastMultipole), Box::new(Compute::new())]
 }
 
 impl Situation {
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             bodies: Vec::<Body>::new(),
-            marks: Vec::<Mark>::new(),
+            particles: Vec::new(),
+            trails: HashMap::new(),
+            trail_length: 2000,
+            trail_spacing: u64::from(REFRESH_RATE) / 10,
             updates: 0,
             zoom_exponent: 0.,
             fullscreen: false,
             paused: false,
-            translation: EuclideanVector { dx: 0., dy: 0. },
-            drag_start: Coordinate { x: 0., y: 0. },
+            editing: None,
+            translation: EuclideanVector { dx: 0., dy: 0., dz: 0. },
+            drag_start: Point { x: 0., y: 0. },
             tracked_body: None,
+            integrator: Integrator::Euler,
+            substeps: 1,
+            solvers: solvers(OPENING_ANGLE),
+            solver: 0,
+            opening_angle: OPENING_ANGLE,
+            force_models: physics::standard_models(),
+            collisions: Collisions::new(),
+            tidal_disruption: TidalDisruption::new(),
+            tidal_locking: TidalLocking::new(),
+            constants: Constants::new(),
+            pixels_per_unit: 1.,
+            time_step: integration::NOMINAL_TIME_STEP,
+            time_scale: 1.,
+            tilt: 0.,
+            tilt_target: 0.,
+            glide: None,
+            reversed: false,
+            history: History::with_capacity(REWIND_HISTORY),
+            snapshot: None,
+            ship: None,
+            prediction: Vec::new(),
+            always_predict: false,
+            show_lagrange_points: false,
+            show_velocities: true,
+            show_forces: true,
+            show_hud: true,
+            show_labels: true,
+            show_grid: false,
+            show_minimap: true,
+            coloring: Coloring::Plain,
+            ramp: 0,
+            quality: Quality::Medium,
+            frame_rate: Rate::new(Instant::now()),
+            update_rate: Rate::new(Instant::now()),
+            hidden_trails: HashSet::new(),
+            hidden_labels: HashSet::new(),
+            placing: None,
+            record_to: None,
+            record_every: 1,
+            recorder: None,
+            animator: None,
+            clean: false,
+            following: None,
+            show_hill_spheres: false,
+            transfer_target: None,
+            maneuver: None,
+            maneuver_prediction: None,
+            maneuver_predicted_for: None,
+            log_flybys: false,
+            performance: false,
+            totals: None,
+            initial_totals: None,
+            graphed: VecDeque::new(),
+            show_graphs: false,
+            heatmap: None,
+            show_heatmap: false,
         }
     }
+    pub fn with_constants(mut self, constants: Constants) -> Self {
+        self.constants = constants;
+        self
+    }
+    pub fn with_scale(mut self, pixels_per_unit: Real, time_step: Real) -> Self {
+        self.pixels_per_unit = pixels_per_unit;
+        self.time_step = time_step;
+        self
+    }
+    pub fn with_trails(mut self, length: u32, spacing: u64) -> Self {
+        self.trail_length = length;
+        self.trail_spacing = spacing;
+        self
+    }
     pub fn with(mut self, body: Body) -> Self {
         self.add(body);
         self
     }
-    pub fn add(&mut self, body: Body) {
+    // bodies of no density of their own are given the one of the constants
+    pub fn add(&mut self, mut body: Body) {
+        if body.density.is_none() { body.radius = self.constants.radius_of(Mass(body.mass)).0; }
         self.bodies.push(body);
     }
+    pub fn add_particle(&mut self, particle: Particle) {
+        self.particles.push(particle);
+    }
 
     pub fn update(&mut self) {
+        self.update_rate.tick(Instant::now());
+        self.move_camera();
+        self.refresh_prediction();
+        self.refresh_maneuver_prediction();
         if self.paused { return; }
+        let from = self.updates;
+        for _ in 0..(self.time_scale.round() as u32).max(1) { self.advance(); }
+        self.record(from);
+    }
+
+    // by a single update, leaving it to `record` to keep track of it
+    fn advance(&mut self) {
+        if let Some(maneuver) = self.maneuver.filter(|maneuver| maneuver.at == self.updates) {
+            maneuver.execute(&mut self.bodies);
+            self.maneuver = None;
+        }
 
-        for i in 0..self.bodies.len() {
-            let (head, tail) = self.bodies.split_at_mut(i);
-            let (body, tail) = tail.split_at_mut(1);
-            let body = &mut body[0];
+        let flying_by: Vec<Option<usize>> = if self.log_flybys { self.flybys_underway() } else { Vec::new() };
 
-            body.update();
-            body.forces.clear();
+        self.substeps = if self.performance { 1 } else { self.integrator.substeps_needed(&self.bodies, &self.force_models, &self.constants, self.time_step) };
+        let time_step = self.signed_time_step() * self.time_scale.min(1.) / self.substeps as Real;
+        for _ in 0..self.substeps {
+            self.integrator.advance(&mut self.bodies, self.solvers[self.solver].as_ref(), &self.force_models, &self.constants, time_step);
+            swarm::advance(&mut self.particles, &self.bodies, &self.constants, time_step);
 
-            for other_body in head.iter_mut().chain(tail) {
-                body.add_pull_from(other_body);
+            for (survivor, removed) in self.collisions.resolve(&mut self.bodies, time_step) {
+                self.body_merged(survivor, removed);
+            }
+            for removed in self.tidal_disruption.disrupt(&mut self.bodies) {
+                self.body_removed(removed);
             }
+            self.tidal_locking.despin(&mut self.bodies, time_step);
+        }
+
+        if flying_by.len() == self.bodies.len() { self.log_passed_flybys(&flying_by); }
 
-            if self.updates % (u64::from(REFRESH_RATE) / 10) == 0 {
-                self.marks.push(Mark::new(body.position));
+        if self.ship.is_some_and(|ship| self.bodies[ship].engine.is_none()) {
+            self.ship = None; // crashed into something bigger
+        }
+
+        self.updates += 1;
+    }
+
+    // Of where the updates since the given one have brought the bodies: the trails, totals and history are kept once
+    // for all of them, however many there were. Trails are marked if they passed an update the spacing falls on.
+    fn record(&mut self, from: u64) {
+        let spaced = from.div_ceil(self.trail_spacing) * self.trail_spacing < self.updates;
+        for (i, body) in self.bodies.iter_mut().enumerate() {
+            let marked = !self.performance && self.trail_length > 0 && !self.hidden_trails.contains(&body.id);
+            if marked && spaced {
+                let trail = self.trails.entry(body.id).or_insert_with(|| Trail::new(body_color(body)));
+                trail.points.push_back((body.position, self.updates - 1));
             }
 
             body.highlighted = self.tracked_body == Some(i);
         }
 
-        for mark in &mut self.marks {
-            mark.update();
+        self.forget_trails();
+        if self.performance { return; }
+
+        let totals = Totals::of(&self.bodies, &self.constants);
+        self.initial_totals.get_or_insert(totals);
+        self.totals = Some(totals);
+        self.graphed.push_back((self.updates, totals, self.bodies.len()));
+        while self.graphed.front().is_some_and(|&(passed, ..)| passed + GRAPH_LENGTH <= self.updates) { self.graphed.pop_front(); }
+
+        self.history.record(Moment { bodies: self.bodies.clone(), updates: self.updates });
+    }
+
+    fn refresh_prediction(&mut self) {
+        if (!self.paused && !self.always_predict) || self.performance {
+            self.prediction.clear();
+            return;
+        }
+
+        let mut bodies = self.bodies.clone();
+        let mut paths = vec![Vec::with_capacity(PREDICTION_STEPS); bodies.len()];
+        for _ in 0..PREDICTION_STEPS {
+            self.predict_update(&mut bodies);
+            for (path, body) in paths.iter_mut().zip(&bodies) { path.push(body.position); }
         }
-        self.marks.retain(|mark| mark.age < TRAIL_HISTORY);
-        self.updates += 1;
+        self.prediction = paths;
+    }
+
+    // the trajectory of the maneuvering body after the burn, as if it were committed to
+    fn refresh_maneuver_prediction(&mut self) {
+        let maneuver = match self.maneuver.filter(|_| !self.performance) {
+            Some(maneuver) => maneuver,
+            None => { self.maneuver_prediction = None; self.maneuver_predicted_for = None; return; }
+        };
+        let key = (maneuver, self.updates, physics::checksum(&self.bodies));
+        if self.maneuver_predicted_for == Some(key) { return; }
+        self.maneuver_predicted_for = Some(key);
+
+        let mut bodies = self.bodies.clone();
+        for _ in self.updates..maneuver.at { self.predict_update(&mut bodies); }
+        let node = bodies[maneuver.body].position;
+        maneuver.execute(&mut bodies);
+
+        let mut path = Vec::with_capacity(PREDICTION_STEPS);
+        for _ in 0..PREDICTION_STEPS {
+            self.predict_update(&mut bodies);
+            path.push(bodies[maneuver.body].position);
+        }
+        self.maneuver_prediction = Some((node, path));
+    }
+
+    // the planet each body is approaching on a hyperbolic pass, if any
+    fn flybys_underway(&self) -> Vec<Option<usize>> {
+        (0..self.bodies.len())
+            .map(|index| self.flyby_of(index).map(|(planet, _)| planet).filter(|&planet| is_approaching(&self.bodies[index], &self.bodies[planet])))
+            .collect()
+    }
+
+    fn log_passed_flybys(&self, flying_by: &[Option<usize>]) {
+        for (index, planet) in flying_by.iter().enumerate().filter_map(|(index, planet)| planet.map(|planet| (index, planet))) {
+            if is_approaching(&self.bodies[index], &self.bodies[planet]) { continue; }
+            if let Some((_, flyby)) = self.flyby_of(index).filter(|&(passed, _)| passed == planet) {
+                println!("update {}: {} flew by {} at {:.3} (excess speed: {:.3}, turn: {:.1}°, speed change: {:+.3})",
+                    self.updates, self.bodies[index].name, self.bodies[planet].name, flyby.periapsis, flyby.excess_speed, flyby.turn_angle.to_degrees(), flyby.speed_change);
+            }
+        }
+    }
+
+    // a hyperbolic pass of the body by its dominant attractor, itself orbiting another body
+    fn flyby_of(&self, index: usize) -> Option<(usize, Flyby)> {
+        let planet = orbit::dominant_attractor(&self.bodies, index)?;
+        let frame = orbit::dominant_attractor(&self.bodies, planet).filter(|&frame| frame != index)?;
+        Flyby::of(&self.bodies[index], &self.bodies[planet], self.bodies[frame].velocity, &self.constants).map(|flyby| (planet, flyby))
+    }
+
+    // without resolving collisions, as the bodies are not to be mutated by a prediction
+    fn predict_update(&self, bodies: &mut [Body]) {
+        let substeps = self.integrator.substeps_needed(bodies, &self.force_models, &self.constants, self.time_step);
+        let time_step = self.signed_time_step() / substeps as Real;
+        for _ in 0..substeps {
+            self.integrator.advance(bodies, self.solvers[self.solver].as_ref(), &self.force_models, &self.constants, time_step);
+        }
+    }
+
+    fn signed_time_step(&self) -> Real {
+        if self.reversed { -self.time_step } else { self.time_step }
+    }
+
+    // pauses the simulation, so that it resumes from the moment stepped to
+    pub fn rewind(&mut self) {
+        self.paused = true;
+        let moment = self.history.back().map(|moment| (moment.bodies.clone(), moment.updates));
+        if let Some((bodies, updates)) = moment { self.restore(bodies, updates); }
+    }
+    // or, if there is nothing left to replay, steps on by a single update
+    pub fn replay(&mut self) {
+        self.paused = true;
+        let moment = self.history.forward().map(|moment| (moment.bodies.clone(), moment.updates));
+        match moment {
+            Some((bodies, updates)) => self.restore(bodies, updates),
+            None => {
+                let from = self.updates;
+                self.advance();
+                self.record(from);
+            }
+        }
+    }
+
+    pub fn toggle_recording(&mut self) {
+        if let Some(recorder) = self.recorder.take() {
+            println!("update {}: recorded {} frames to {}", self.updates, recorder.frames.div_ceil(recorder.every), recorder.directory);
+            return;
+        }
+        let directory = self.record_to.clone().unwrap_or_else(|| format!("frames-{}", Local::now().format("%Y%m%d-%H%M%S")));
+        match std::fs::create_dir_all(&directory) {
+            Ok(()) => self.recorder = Some(Recorder { directory, every: self.record_every.max(1), frames: 0 }),
+            Err(error) => eprintln!("Failed to create {}: {}", directory, error),
+        }
+    }
+    pub fn toggle_animation(&mut self) {
+        if let Some(animator) = self.animator.take() {
+            self.save_animation(animator);
+            return;
+        }
+        let path = format!("animation-{}.gif", Local::now().format("%Y%m%d-%H%M%S"));
+        let delay = (ANIMATION_EVERY * 100 / u64::from(REFRESH_RATE)) as u16;
+        self.animator = Some(Animator { path, animation: gif::Animation::new(ANIMATION_WIDTH, ANIMATION_HEIGHT, delay), frames: 0 });
+    }
+    fn save_animation(&self, animator: Animator) {
+        match std::fs::write(&animator.path, animator.animation.encoded()) {
+            Ok(()) => println!("update {}: animation of {} frames saved to {}", self.updates, animator.animation.frames(), animator.path),
+            Err(error) => eprintln!("Failed to save {}: {}", animator.path, error),
+        }
+    }
+    // stopping the recording if it cannot be written
+    pub fn record_frame(&mut self) {
+        self.animate_frame();
+        let path = match &mut self.recorder {
+            Some(recorder) => {
+                recorder.frames += 1;
+                if (recorder.frames - 1) % recorder.every != 0 { return; }
+                format!("{}/frame-{:06}.png", recorder.directory, (recorder.frames - 1) / recorder.every)
+            }
+            None => return,
+        };
+        if let Err(error) = render_png(self, RECORDING_WIDTH, RECORDING_HEIGHT, &path) {
+            eprintln!("Failed to record {}: {}", path, error);
+            self.recorder = None;
+        }
+    }
+    // saving the animation once it is as long as it gets
+    fn animate_frame(&mut self) {
+        match &mut self.animator {
+            Some(animator) => {
+                animator.frames += 1;
+                if (animator.frames - 1) % ANIMATION_EVERY != 0 { return; }
+            }
+            None => return,
+        }
+        let frame = render_palette_indices(self, ANIMATION_WIDTH, ANIMATION_HEIGHT);
+        let mut animator = self.animator.take().expect("Animation stopped while rendering");
+        match frame {
+            Ok(frame) => animator.animation.push(frame),
+            Err(error) => {
+                eprintln!("Failed to render a frame of {}: {}", animator.path, error);
+                return;
+            }
+        }
+        if animator.animation.frames() < ANIMATION_FRAMES { self.animator = Some(animator); } else { self.save_animation(animator); }
+    }
+    // as saved, with the solver of the given index if there is one
+    pub fn loaded(text: &str) -> Result<Self, String> {
+        let mut situation: Self = toml::from_str(text).map_err(|error| error.to_string())?;
+        if situation.solver >= SOLVERS.len() { return Err(format!("Unknown solver {}, available are: {}", situation.solver, SOLVERS.join(", "))); }
+        situation.set_opening_angle(situation.opening_angle);
+        Ok(situation)
+    }
+    pub fn save(&self) {
+        let path = format!("situation-{}.toml", Local::now().format("%Y%m%d-%H%M%S"));
+        let saved = toml::to_string(self).map_err(|error| error.to_string())
+            .and_then(|text| std::fs::write(&path, text).map_err(|error| error.to_string()));
+        match saved {
+            Ok(()) => println!("update {}: saved to {}", self.updates, path),
+            Err(error) => eprintln!("Failed to save {}: {}", path, error),
+        }
+    }
+    pub fn take_snapshot(&mut self) {
+        self.snapshot = Some(Snapshot {
+            moment: Moment { bodies: self.bodies.clone(), updates: self.updates },
+            zoom_exponent: self.zoom_exponent,
+            translation: self.translation,
+            tracked_body: self.tracked_body,
+            following: self.following,
+            particles: self.particles.clone(),
+        });
+    }
+    // the rewind history is dropped, as it no longer leads up to the restored moment
+    pub fn restore_snapshot(&mut self) {
+        let snapshot = match self.snapshot.clone() { Some(snapshot) => snapshot, None => return };
+        self.zoom_exponent = snapshot.zoom_exponent;
+        self.translation = snapshot.translation;
+        self.tracked_body = snapshot.tracked_body;
+        self.following = snapshot.following;
+        self.particles = snapshot.particles;
+        self.restore(snapshot.moment.bodies, snapshot.moment.updates);
+        self.trails.clear();
+        self.history.clear();
+    }
+
+    fn restore(&mut self, bodies: Vec<Body>, updates: u64) {
+        self.bodies = bodies;
+        self.updates = updates;
+        self.tracked_body = self.tracked_body.filter(|&tracked| tracked < self.bodies.len());
+        self.maneuver = self.maneuver.filter(|maneuver| maneuver.body < self.bodies.len() && maneuver.at >= updates);
+        self.ship = self.ship.filter(|&ship| self.bodies.get(ship).is_some_and(|body| body.engine.is_some()));
+        self.totals = Some(Totals::of(&self.bodies, &self.constants));
+        self.graphed.retain(|&(passed, ..)| passed <= updates);
+    }
+
+    fn body_merged(&mut self, survivor: usize, removed: usize) {
+        self.remap_bodies(removed, Some(survivor));
+    }
+
+    fn body_removed(&mut self, removed: usize) {
+        self.remap_bodies(removed, None);
+    }
+
+    fn remap_bodies(&mut self, removed: usize, replacement: Option<usize>) {
+        let remapped = |index: Option<usize>| match index {
+            Some(index) if index == removed => replacement,
+            Some(index) if index > removed => Some(index - 1),
+            index => index,
+        };
+        self.tracked_body = remapped(self.tracked_body);
+        self.following = match self.following {
+            Some(Following::Midpoint(lhs, rhs)) => remapped(Some(lhs)).zip(remapped(Some(rhs))).map(|(lhs, rhs)| Following::Midpoint(lhs, rhs)),
+            following => following,
+        };
+        self.ship = remapped(self.ship);
+        self.maneuver = self.maneuver.and_then(|maneuver| match maneuver.body {
+            body if body == removed => None,
+            body if body > removed => Some(Maneuver { body: body - 1, ..maneuver }),
+            _ => Some(maneuver),
+        });
     }
 
     pub fn count_forces(&self) -> usize {
@@ -111,30 +900,440 @@ impl Situation {
     pub fn zoom_reset(&mut self) {
         self.zoom_exponent = 0.;
     }
+    // keeping the point under the cursor in place, unless the camera is following something
+    pub fn zoom_at(&mut self, cursor: Point, zooming_in: bool) {
+        let cursor = Coordinate { x: cursor.x as Real, y: cursor.y as Real, z: 0. };
+        let before = self.transform().screen_to_world(cursor);
+        if zooming_in { self.zoom_in(); } else { self.zoom_out(); }
+        if self.ship.or(self.tracked_body).is_none() && self.following.is_none() {
+            self.translation += self.transform().screen_to_world(cursor) - before;
+        }
+    }
+    pub fn bounds(&self) -> Option<Aabb> {
+        Aabb::of(self.bodies.iter().map(|body| body.position))
+    }
+    // onto the minimap, looking straight down at all the bodies
+    pub fn minimap_transform(&self) -> Option<Transform> {
+        let bounds = self.bounds()?;
+        let extent = Real::max(bounds.size().dx, bounds.size().dy);
+        let scale = if extent > 0. { (MINIMAP_SIZE as Real) * FRAMED_FILL / extent } else { 1. };
+        let center = bounds.center();
+        let translation = EuclideanVector { dx: MINIMAP_SIZE as Real / 2. - center.x * scale, dy: MINIMAP_SIZE as Real / 2. - center.y * scale, dz: 0. };
+        Some(Transform { scale, tilt: 0., translation })
+    }
+    // centers the camera on all the bodies, and zooms in as far as they still fit in the window of the given half-size
+    pub fn frame_all(&mut self, half_size: Point) {
+        let bounds = match self.bounds() { Some(bounds) => bounds, None => return };
+        self.start_glide();
+        self.tracked_body = None;
+        self.following = None;
+        self.translation = -EuclideanVector::towards(bounds.center());
+
+        let size = bounds.size() * self.pixels_per_unit;
+        let zoom = Real::min(2. * half_size.x as Real / size.dx, 2. * half_size.y as Real / (size.dy * self.tilt.cos() + size.dz * self.tilt.sin())) * FRAMED_FILL;
+        if zoom.is_finite() { self.zoom_exponent = (widened(zoom.log2()) * 4.).floor() / 4.; }
+    }
     pub fn zoom(&self) -> f64 {
         2.0_f64.powf(self.zoom_exponent)
     }
+    pub fn barycenter(&self) -> Coordinate {
+        physics::barycenter(&self.bodies)
+    }
     pub fn track_next(&mut self) {
         match self.tracked_body {
-            Some(tracked) => if self.bodies.len() > tracked + 1 { self.tracked_body = Some(tracked + 1); } else { self.tracked_body = None; },
-            None => if !self.bodies.is_empty() { self.tracked_body = Some(0); },
+            Some(tracked) => if self.bodies.len() > tracked + 1 { self.track(Some(tracked + 1)); } else { self.track(None); },
+            None => if !self.bodies.is_empty() { self.track(Some(0)); },
+        }
+    }
+    pub fn track_previous(&mut self) {
+        match self.tracked_body {
+            Some(tracked) => self.track(tracked.checked_sub(1)),
+            None => if !self.bodies.is_empty() { self.track(Some(self.bodies.len() - 1)); },
         }
     }
+    pub fn tracking(&self) -> bool {
+        self.tracked_body.is_some() || self.following.is_some()
+    }
+    pub fn stop_tracking(&mut self) {
+        self.follow(None);
+    }
+    fn track(&mut self, body: Option<usize>) {
+        self.start_glide();
+        self.following = None;
+        self.tracked_body = body;
+    }
+    // instead of the tracked body, or nothing at all, leaving the camera where it is
+    pub fn follow(&mut self, following: Option<Following>) {
+        if following.is_none() { self.translation = self.center_translation(); }
+        self.start_glide();
+        self.tracked_body = None;
+        self.following = following;
+    }
+    // the closest one drawn under the cursor, relative to the center of the window
+    pub fn body_at(&self, cursor: Point) -> Option<usize> {
+        let transform = self.transform();
+        let cursor = Coordinate { x: cursor.x as Real, y: cursor.y as Real, z: 0. };
+        let distances = self.bodies.iter().map(|body| (transform.world_to_screen(body.position) - cursor).magnitude());
+        distances.enumerate()
+            .filter(|&(body, distance)| widened(distance) <= f64::max(widened(self.bodies[body].radius * transform.scale), CLICK_TOLERANCE))
+            .min_by(|(_, lhs), (_, rhs)| lhs.partial_cmp(rhs).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(body, _)| body)
+    }
+    // kept paused while a body is being edited
     pub fn toggle_pause(&mut self) {
+        if self.editing.is_some() { return; }
         self.paused = !self.paused
     }
-    pub fn drag_started(&mut self, window_position: Coordinate) {
-        self.drag_start = window_position;
+    // pausing the simulation until done, unless there is an editor open already
+    pub fn start_editing(&mut self) -> bool {
+        if self.editing.is_some() { return false; }
+        self.editing = Some(self.paused);
+        self.paused = true;
+        true
+    }
+    pub fn finish_editing(&mut self) {
+        if let Some(paused) = self.editing.take() { self.paused = paused; }
+    }
+    pub fn toggle_prediction(&mut self) {
+        self.always_predict = !self.always_predict
+    }
+    pub fn toggle_lagrange_points(&mut self) {
+        self.show_lagrange_points = !self.show_lagrange_points
+    }
+    pub fn toggle_velocities(&mut self) {
+        self.show_velocities = !self.show_velocities
+    }
+    pub fn toggle_forces(&mut self) {
+        self.show_forces = !self.show_forces
+    }
+    // hiding both unless both are already hidden
+    pub fn toggle_vectors(&mut self) {
+        let shown = !(self.show_velocities || self.show_forces);
+        self.show_velocities = shown;
+        self.show_forces = shown;
+    }
+    pub fn toggle_hud(&mut self) {
+        self.show_hud = !self.show_hud
+    }
+    pub fn toggle_labels(&mut self) {
+        self.show_labels = !self.show_labels
+    }
+    pub fn toggle_grid(&mut self) {
+        self.show_grid = !self.show_grid
+    }
+    pub fn toggle_graphs(&mut self) {
+        self.show_graphs = !self.show_graphs
+    }
+    pub fn toggle_heatmap(&mut self) {
+        self.show_heatmap = !self.show_heatmap
+    }
+    // for a window of the given half-size, if it is shown and no longer up to date
+    pub fn refresh_heatmap(&mut self, half_size: Point) {
+        if !self.show_heatmap || self.performance {
+            self.heatmap = None;
+            return;
+        }
+        let transform = self.transform();
+        if self.heatmap.as_ref().is_some_and(|heatmap| !heatmap.is_stale(&self.bodies, &transform, half_size)) { return; }
+
+        let (columns, rows) = ((2. * half_size.x / HEATMAP_CELL).ceil() as usize, (2. * half_size.y / HEATMAP_CELL).ceil() as usize);
+        let potentials = (0..rows * columns).map(|cell| {
+            let x = (cell % columns) as f64 * HEATMAP_CELL + HEATMAP_CELL / 2. - half_size.x;
+            let y = (cell / columns) as f64 * HEATMAP_CELL + HEATMAP_CELL / 2. - half_size.y;
+            potential_at(&self.bodies, &self.constants, transform.screen_to_world(Coordinate { x: x as Real, y: y as Real, z: 0. }))
+        }).collect();
+        let positions = self.bodies.iter().map(|body| body.position).collect();
+        self.heatmap = Some(Heatmap { transform, half_size, positions, columns, potentials });
+    }
+    pub fn toggle_minimap(&mut self) {
+        self.show_minimap = !self.show_minimap
+    }
+    pub fn switch_quality(&mut self) {
+        self.quality = self.quality.next();
+    }
+    pub fn switch_coloring(&mut self) {
+        self.coloring = self.coloring.next();
+    }
+    // of the property bodies are colored by, among all that have it
+    pub fn coloring_range(&self) -> Option<(f64, f64)> {
+        let mut values = self.bodies.iter().filter_map(|body| self.coloring.value_of(body));
+        let first = values.next()?;
+        Some(values.fold((first, first), |(lowest, highest), value| (lowest.min(value), highest.max(value))))
+    }
+    pub fn body_colors(&self) -> HashMap<BodyId, (f64, f64, f64)> {
+        let (lowest, highest) = match self.coloring_range() { Some(range) => range, None => return HashMap::new() };
+        self.bodies.iter()
+            .filter_map(|body| Some((body.id, ramp_color(RAMPS[self.ramp].1, ramp_position(self.coloring.value_of(body)?, lowest, highest)))))
+            .collect()
+    }
+    pub fn toggle_trail_of(&mut self, body: usize) {
+        let id = self.bodies[body].id;
+        if !self.hidden_trails.remove(&id) {
+            self.hidden_trails.insert(id);
+            self.trails.remove(&id);
+        }
+    }
+    pub fn toggle_label_of(&mut self, body: usize) {
+        let id = self.bodies[body].id;
+        if !self.hidden_labels.remove(&id) { self.hidden_labels.insert(id); }
+    }
+    pub fn delete_body(&mut self, body: usize) {
+        self.bodies.remove(body);
+        self.body_removed(body);
+    }
+    // next to the original, and named after it
+    pub fn duplicate_body(&mut self, body: usize) {
+        let original = &self.bodies[body];
+        let offset = Real::max(original.radius * DUPLICATE_OFFSET, CLICK_TOLERANCE as Real / self.transform().scale);
+        let mut duplicate = original.duplicated().at(original.position + EuclideanVector { dx: offset, dy: offset, dz: 0. });
+        duplicate.name = format!("{} (copy)", original.name);
+        self.bodies.push(duplicate);
+    }
+    // the radius following from the new mass as it would for a new body
+    // unless it is gone in the meantime
+    pub fn edit_body(&mut self, body: BodyId, properties: Properties) {
+        let body = match self.bodies.iter().position(|candidate| candidate.id == body) {
+            Some(body) => body,
+            None => return,
+        };
+        let radius = match self.bodies[body].density {
+            Some(density) => physics::radius_at(Mass(properties.mass), density),
+            None => self.constants.radius_of(Mass(properties.mass)),
+        };
+        let body = &mut self.bodies[body];
+        body.name = properties.name;
+        body.mass = properties.mass;
+        body.radius = radius.0;
+        body.position = properties.position;
+        body.velocity = properties.velocity;
+    }
+    // moving the camera over to the body, without tracking it
+    pub fn center_on(&mut self, body: usize) {
+        self.follow(None);
+        self.translation = -EuclideanVector::towards(self.bodies[body].position);
+    }
+    pub fn toggle_clean(&mut self) {
+        self.clean = !self.clean
+    }
+    pub fn toggle_hill_spheres(&mut self) {
+        self.show_hill_spheres = !self.show_hill_spheres
+    }
+    pub fn adjust_trail_length(&mut self, longer: bool) {
+        self.trail_length = stepped(&TRAIL_LENGTHS, self.trail_length, longer);
+        self.forget_trails();
+    }
+    // of the points too old to be shown, and of the bodies which have none left
+    fn forget_trails(&mut self) {
+        let oldest = (self.updates + 1).saturating_sub(u64::from(self.trail_length));
+        for trail in self.trails.values_mut() { trail.forget_before(oldest); }
+        self.trails.retain(|_, trail| !trail.points.is_empty());
+    }
+    pub fn adjust_trail_spacing(&mut self, denser: bool) {
+        self.trail_spacing = stepped(&TRAIL_SPACINGS, self.trail_spacing, !denser);
+    }
+    pub fn log_bodies(&self) {
+        for body in &self.bodies {
+            println!("update {}: {}", self.updates, body);
+        }
+    }
+    // the history and totals are dropped, as they would have a gap in them
+    pub fn toggle_performance(&mut self) {
+        self.performance = !self.performance;
+        self.trails.clear();
+        self.history.clear();
+        self.totals = None;
+        self.initial_totals = None;
+        self.graphed.clear();
+    }
+    pub fn toggle_maneuver(&mut self) {
+        self.maneuver = match (self.maneuver, self.tracked_body) {
+            (None, Some(tracked)) => Some(Maneuver::new(tracked, self.updates + MANEUVER_LEAD)),
+            _ => None,
+        };
+    }
+    pub fn shift_maneuver(&mut self, later: bool) {
+        let updates = self.updates;
+        if let Some(maneuver) = self.maneuver.as_mut() {
+            maneuver.at = if later { maneuver.at + MANEUVER_SHIFT } else { maneuver.at.saturating_sub(MANEUVER_SHIFT).max(updates) };
+            maneuver.at = maneuver.at.min(updates + PREDICTION_STEPS as u64);
+        }
+    }
+    pub fn adjust_maneuver(&mut self, prograde: Real, radial: Real) {
+        let bodies = &self.bodies;
+        let maneuver = match self.maneuver.as_mut() { Some(maneuver) => maneuver, None => return };
+        let body = &bodies[maneuver.body];
+        let speed = orbit::dominant_attractor(bodies, maneuver.body)
+            .map_or(body.velocity, |attractor| body.velocity - bodies[attractor].velocity)
+            .magnitude();
+        maneuver.prograde += prograde * MANEUVER_BURN_STEP * speed;
+        maneuver.radial += radial * MANEUVER_BURN_STEP * speed;
+    }
+    pub fn toggle_transfer_planner(&mut self) {
+        self.transfer_target = match (self.transfer_target, self.tracked_body) {
+            (None, Some(tracked)) => orbit::dominant_attractor(&self.bodies, tracked)
+                .map(|primary| Length(2. * (self.bodies[tracked].position - self.bodies[primary].position).magnitude())),
+            _ => None,
+        };
+    }
+    pub fn adjust_transfer_target(&mut self, factor: Real) {
+        if let Some(target) = self.transfer_target.as_mut() { *target *= factor; }
+    }
+    fn transfer(&self) -> Option<(Hohmann, &Body)> {
+        let (target, tracked) = (self.transfer_target?, self.tracked_body?);
+        let primary = &self.bodies[orbit::dominant_attractor(&self.bodies, tracked)?];
+        Some((Hohmann::plan(&self.bodies[tracked], primary, &self.constants, target), primary))
+    }
+    pub fn toggle_following_barycenter(&mut self) {
+        self.follow(if self.following == Some(Following::Barycenter) { None } else { Some(Following::Barycenter) });
+    }
+    pub fn adjust_time_scale(&mut self, faster: bool) {
+        self.time_scale = stepped(&TIME_SCALES, self.time_scale, faster);
+    }
+    pub fn switch_integrator(&mut self) {
+        self.integrator = self.integrator.next();
+    }
+    // only a time-symmetric integrator brings the bodies back to where they came from
+    pub fn toggle_time_reversal(&mut self) {
+        self.reversed = !self.reversed;
+        if self.reversed && !self.integrator.is_time_symmetric() { self.integrator = Integrator::Leapfrog; }
+    }
+    pub fn switch_solver(&mut self) {
+        self.solver = (self.solver + 1) % self.solvers.len();
+    }
+    pub fn set_opening_angle(&mut self, opening_angle: Real) {
+        self.opening_angle = opening_angle.clamp(0., MAX_OPENING_ANGLE);
+        self.solvers = solvers(self.opening_angle);
+    }
+    pub fn adjust_opening_angle(&mut self, delta: Real) {
+        self.set_opening_angle(self.opening_angle + delta);
+    }
+    pub fn switch_collision_mode(&mut self) {
+        self.collisions.mode = self.collisions.mode.next();
+    }
+    pub fn adjust_restitution(&mut self, delta: Real) {
+        self.collisions.restitution = (self.collisions.restitution + delta).clamp(0., 1.);
+    }
+    pub fn toggle_tidal_disruption(&mut self) {
+        self.tidal_disruption.enabled = !self.tidal_disruption.enabled
+    }
+    pub fn toggle_tidal_locking(&mut self) {
+        self.tidal_locking.enabled = !self.tidal_locking.enabled
+    }
+    pub fn adjust_softening(&mut self, delta: Real) {
+        self.constants.softening = (self.constants.softening + delta).max(0.);
+    }
+    pub fn toggle_relativity(&mut self) {
+        self.constants.speed_of_light = match self.constants.speed_of_light {
+            Some(_) => None,
+            None => Some(SPEED_OF_LIGHT),
+        };
+    }
+    fn engine_of(&mut self, body: Option<usize>) -> Option<&mut Engine> {
+        self.bodies[body?].engine.as_mut()
+    }
+    pub fn rotate_engine(&mut self, angle: Real) {
+        if let Some(engine) = self.engine_of(self.tracked_body) { engine.rotate(angle); }
+    }
+    pub fn throttle_engine(&mut self, throttle: Real) {
+        if let Some(engine) = self.engine_of(self.tracked_body) { engine.throttle = throttle; }
+    }
+    pub fn piloting(&self) -> bool {
+        self.ship.is_some()
+    }
+    pub fn toggle_piloting(&mut self) {
+        self.start_glide();
+        if self.ship.take().is_some() { return; }
+
+        let has_engine = |index: &usize| self.bodies[*index].engine.is_some();
+        self.ship = self.tracked_body.filter(has_engine).or_else(|| (0..self.bodies.len()).find(has_engine));
+        if self.ship.is_some() { self.tracked_body = self.ship; }
+    }
+    pub fn steer_ship(&mut self, angle: Real) {
+        if let Some(engine) = self.engine_of(self.ship) { engine.rotate(angle); }
+    }
+    pub fn throttle_ship(&mut self, delta: Real) {
+        if let Some(engine) = self.engine_of(self.ship) { engine.throttle = (engine.throttle + delta).clamp(0., 1.); }
+    }
+    // tracking the body pressed on, if any, and otherwise getting ready to drag the view
+    pub fn pressed_at(&mut self, cursor: Point) {
+        self.drag_start = cursor;
+        if let Some(body) = self.body_at(cursor) { self.track(Some(body)); }
+    }
+    // at the cursor, at rest until its velocity is dragged out
+    pub fn start_placing(&mut self, cursor: Point) {
+        let position = self.transform().screen_to_world(Coordinate { x: cursor.x as Real, y: cursor.y as Real, z: 0. });
+        let heaviest = self.bodies.iter().map(|body| body.mass).fold(0., Real::max);
+        let mass = if heaviest > 0. { heaviest * PLACED_MASS_FRACTION } else { 1. };
+        let name = format!("Body {}", self.bodies.len() + 1);
+        let mut body = Body::new().with_mass(mass).at(position).named(&name);
+        body.radius = self.constants.radius_of(Mass(mass)).0;
+        self.placing = Some(body);
+    }
+    pub fn adjust_placed_mass(&mut self, factor: Real) {
+        if let Some(body) = &mut self.placing {
+            body.mass *= factor;
+            body.radius = self.constants.radius_of(Mass(body.mass)).0;
+        }
+    }
+    pub fn finish_placing(&mut self) {
+        if let Some(body) = self.placing.take() { self.add(body); }
+    }
+    // moving the view, or setting the velocity of the body being placed, as shown by its vector
+    pub fn dragging_to(&mut self, cursor: Point) {
+        let end = self.transform().screen_to_world(Coordinate { x: cursor.x as Real, y: cursor.y as Real, z: 0. });
+        if let Some(body) = &mut self.placing {
+            body.velocity = (end - body.position) / (self.time_step * VECTOR_MAGNIFICATION as Real);
+            return;
+        }
+        let (dx, dy) = (cursor.x - self.drag_start.x, cursor.y - self.drag_start.y);
+        self.scroll(dx / self.zoom(), dy / self.zoom());
+        self.drag_start = cursor;
+    }
+    // by a distance given in screen pixels
+    pub fn scroll(&mut self, dx: f64, dy: f64) {
+        self.translation += EuclideanVector { dx: dx as Real, dy: dy as Real / self.tilt.cos(), dz: 0. } / self.pixels_per_unit;
+    }
+    pub fn tilt_camera(&mut self, delta: Real) {
+        self.tilt_target = (self.tilt_target + delta).clamp(0., MAX_TILT);
+    }
+    // from wherever the camera is looking now, even if half way through another glide
+    fn start_glide(&mut self) {
+        self.glide = Some((self.center_translation(), CAMERA_GLIDE));
     }
-    pub fn dragging_to(&mut self, window_position: Coordinate) {
-        let delta = (window_position - self.drag_start) / self.zoom();
-        self.translation += delta;
-        self.drag_start = window_position;
+    fn move_camera(&mut self) {
+        self.tilt = maths::lerp_angle(self.tilt, self.tilt_target, CAMERA_EASING);
+        self.glide = self.glide.filter(|&(_, left)| left > 1).map(|(from, left)| (from, left - 1));
+    }
+    pub fn view(&self) -> View {
+        let center = self.center_translation() * self.pixels_per_unit;
+        let transform = Transform { tilt: self.tilt, ..Transform::scaling(self.pixels_per_unit) };
+        let transform = Transform { translation: transform.tilted(center), ..transform };
+        View {
+            transform,
+            time_step: self.time_step,
+            velocities: self.show_velocities && !self.clean,
+            forces: self.show_forces && !self.clean,
+            colors: HashMap::new(),
+            tracked: self.tracked_body.map(|tracked| self.bodies[tracked].id),
+            quality: self.quality,
+        }
+    }
+    // from the world onto the window, relative to its center
+    pub fn transform(&self) -> Transform {
+        self.view().transform.then(&Transform::scaling(self.zoom() as Real))
     }
     pub fn center_translation(&self) -> EuclideanVector {
-        match self.tracked_body {
+        let target = match self.ship.or(self.tracked_body) {
             Some(tracked) => -EuclideanVector::towards(self.bodies[tracked].position),
-            None => self.translation,
+            None => match self.following {
+                Some(Following::Barycenter) => -EuclideanVector::towards(self.barycenter()),
+                Some(Following::Midpoint(lhs, rhs)) => -EuclideanVector::towards(self.bodies[lhs].position.lerp(self.bodies[rhs].position, 0.5)),
+                None => self.translation,
+            },
+        };
+        match self.glide {
+            Some((from, left)) => target.lerp(from, left as Real / CAMERA_GLIDE as Real),
+            None => target,
         }
     }
 }
@@ -142,55 +1341,457 @@ impl Situation {
 // ---
 
 trait CairoPaintable {
-    fn paint_on(&self, context: &cairo::Context);
+    fn paint_on(&self, context: &cairo::Context, view: &View);
 }
 
+// already in screen units
 impl CairoPaintable for EuclideanVector {
-    fn paint_on(&self, context: &cairo::Context) {
+    fn paint_on(&self, context: &cairo::Context, _view: &View) {
         if self.magnitude() == 0. { return; }
 
         context.move_to(0., 0.);
-        context.line_to(VECTOR_MAGNIFICATION * self.dx, VECTOR_MAGNIFICATION * self.dy);
+        context.line_to(VECTOR_MAGNIFICATION * widened(self.dx), VECTOR_MAGNIFICATION * widened(self.dy));
         context.stroke();
     }
 }
 
 impl CairoPaintable for Body {
-    fn paint_on(&self, context: &cairo::Context) {
+    fn paint_on(&self, context: &cairo::Context, view: &View) {
         context.save();
 
-        context.translate(self.position.x, self.position.y);
-        context.set_source_rgb(1., 1., 1.);
-        context.arc(0., 0., self.radius, 0., PI * 2.);
+        let position = view.at(self.position);
+        context.translate(position.x, position.y);
+        if let Some(atmosphere) = &self.atmosphere {
+            context.set_source_rgb(0.2, 0.3, 0.5);
+            context.arc(0., 0., widened(view.length(atmosphere.radius)), 0., PI * 2.);
+            context.stroke();
+        }
+
+        // lit from the top left, fading to a dark edge
+        let (red, green, blue) = view.colors.get(&self.id).copied().unwrap_or_else(|| body_color(self));
+        let radius = widened(view.length(self.radius));
+        let shading = cairo::RadialGradient::new(-0.3 * radius, -0.3 * radius, 0., 0., 0., radius);
+        shading.add_color_stop_rgb(0., (red * 1.3).min(1.), (green * 1.3).min(1.), (blue * 1.3).min(1.));
+        shading.add_color_stop_rgb(1., red * 0.35, green * 0.35, blue * 0.35);
+        context.set_source(&shading);
+        context.arc(0., 0., radius, 0., PI * 2.);
+        context.fill_preserve();
+        context.set_source_rgb(red, green, blue);
         context.stroke();
 
-        context.move_to(7., 10.);
-        if self.highlighted { context.set_source_rgb(1., 1., 0.); }
-        context.show_text(&self.name);
+        if view.tracked == Some(self.id) {
+            context.set_source_rgb(1., 0.8, 0.2);
+            context.arc(0., 0., radius + TRACKED_RING_GAP, 0., PI * 2.);
+            context.stroke();
+        }
+        context.set_source_rgb(1., 1., 1.);
+
+        if self.spin != 0. {
+            let marker = view.projected(EuclideanVector::from_polar(view.length(self.radius), self.rotation));
+            context.move_to(0., 0.);
+            context.line_to(widened(marker.dx), widened(marker.dy));
+            context.stroke();
+        }
+
         context.move_to(0., 0.);
+        context.set_line_width(view.quality.vector_width());
 
-        context.set_source_rgb(0., 0., 1.);
-        self.velocity.paint_on(context);
+        if view.velocities {
+            context.set_source_rgb(0., 0., 1.);
+            view.projected(self.velocity * view.length(view.time_step)).paint_on(context, view);
+        }
 
         context.set_source_rgb(1., 0., 0.);
-        for force in &self.forces { force.paint_on(context); }
+        if view.forces && self.mass > 0. {
+            let displacement_per_force = view.length(view.time_step * view.time_step / self.mass);
+            for force in &self.forces { view.projected(*force * displacement_per_force).paint_on(context, view); }
+        }
+
+        if let Some(engine) = &self.engine { engine.paint_on(context, view); }
 
         context.restore();
     }
 }
 
-impl CairoPaintable for Mark {
-    fn paint_on(&self, context: &cairo::Context) {
+// relative to the body it is mounted on
+impl CairoPaintable for Engine {
+    fn paint_on(&self, context: &cairo::Context, view: &View) {
         context.save();
-        context.translate(self.position.x, self.position.y);
+        context.scale(1., widened(view.transform.tilt.cos()));
+        context.rotate(widened(self.heading));
+
+        context.set_source_rgb(1., 0.5, 0.);
+        context.move_to(0., 0.);
+        context.line_to(-6., 0.);
+        context.stroke();
+
+        if self.is_firing() {
+            context.set_source_rgb(1., 0.9, 0.2);
+            context.move_to(-6., -2.);
+            context.line_to(-6. - 8. * widened(self.throttle), 0.);
+            context.line_to(-6., 2.);
+            context.stroke();
+        }
+
+        context.restore();
+    }
+}
+
+// the closest of the steps above or below the current value, or the extreme one if there are none
+fn stepped<T: Copy + PartialOrd>(steps: &[T], current: T, up: bool) -> T {
+    if up {
+        steps.iter().copied().find(|&step| step > current).unwrap_or(steps[steps.len() - 1])
+    } else {
+        steps.iter().copied().rev().find(|&step| step < current).unwrap_or(steps[0])
+    }
+}
+
+// its own color, or one of a hue following from its name, so that it stays the same from run to run
+fn body_color(body: &Body) -> (f64, f64, f64) {
+    body.color.unwrap_or_else(|| of_hue((physics::fnv(body.name.bytes()) % 360) as f64))
+}
+
+// pale enough for the trails of all hues to be about as visible
+fn of_hue(degrees: f64) -> (f64, f64, f64) {
+    let channel = |offset: f64| {
+        let distance = ((degrees - offset).rem_euclid(360.) - 180.).abs(); // from the opposite hue
+        0.4 + 0.6 * ((distance - 60.) / 60.).clamp(0., 1.)
+    };
+    (channel(0.), channel(120.), channel(240.))
+}
+
+fn paint_apsides(context: &cairo::Context, view: &View, body: &Body, primary: &Body, constants: &Constants) {
+    let elements = Elements::of(body, primary, constants);
+    if elements.eccentricity < MIN_APSIDES_ECCENTRICITY { return; }
+
+    context.set_source_rgb(0.4, 0.8, 1.);
+    paint_apsis(context, view.at(elements.periapsis_point(primary.position)), &format!("Pe {:.1}", elements.periapsis));
+    if let (Some(point), Some(apoapsis)) = (elements.apoapsis_point(primary.position), elements.apoapsis) {
+        paint_apsis(context, view.at(point), &format!("Ap {:.1}", apoapsis));
+    }
+}
+
+fn paint_apsis(context: &cairo::Context, at: Point, label: &str) {
+    context.move_to(at.x, at.y - 4.);
+    context.line_to(at.x + 3., at.y);
+    context.line_to(at.x, at.y + 4.);
+    context.line_to(at.x - 3., at.y);
+    context.close_path();
+    context.fill();
+    print_text(context, at.x + 6., at.y - 4., label);
+}
+
+fn paint_lagrange_points(context: &cairo::Context, view: &View, primary: &Body, secondary: &Body) {
+    context.set_source_rgb(0.8, 0.4, 1.);
+    for (i, point) in lagrange_points(primary, secondary).iter().map(|&point| view.at(point)).enumerate() {
+        context.move_to(point.x - 3., point.y - 3.);
+        context.line_to(point.x + 3., point.y + 3.);
+        context.move_to(point.x - 3., point.y + 3.);
+        context.line_to(point.x + 3., point.y - 3.);
+        context.stroke();
+        print_text(context, point.x + 5., point.y - 5., &format!("L{}", i + 1));
+    }
+}
+
+fn paint_hill_spheres(context: &cairo::Context, view: &View, bodies: &[Body], constants: &Constants) {
+    let primary = match orbit::most_massive(bodies) {
+        Some(primary) => primary,
+        None => return,
+    };
+
+    context.save();
+    context.set_source_rgb(0.4, 0.4, 0.6);
+    context.set_dash(&[2., 6.], 0.);
+    for (i, body) in bodies.iter().enumerate() {
+        if i == primary { continue; }
+        if let Some(radius) = orbit::hill_radius(body, &bodies[primary], constants) {
+            let center = view.at(body.position);
+            context.new_sub_path();
+            context.arc(center.x, center.y, widened(view.length(radius)), 0., PI * 2.);
+            context.stroke();
+        }
+    }
+    context.restore();
+}
+
+fn paint_dots(context: &cairo::Context, view: &View, positions: impl Iterator<Item = Coordinate>) {
+    for point in positions.map(|position| view.at(position)) {
+        context.rectangle(point.x, point.y, view.quality.dot_size(), view.quality.dot_size());
+    }
+    context.fill();
+}
+
+// in a tile, each along with its shade; the same every time
+fn starfield() -> Vec<(Point, usize)> {
+    let mut random = Random::seeded(STARFIELD_SEED);
+    (0..STARFIELD_STARS).map(|_| {
+        let at = Point { x: random.next_f64() * STARFIELD_TILE, y: random.next_f64() * STARFIELD_TILE };
+        (at, (random.next_f64() * random.next_f64() * STARFIELD_SHADES as f64) as usize) // mostly dim
+    }).collect()
+}
 
-        let brightness = 0.7 * f64::max(0.05, f64::from(TRAIL_HISTORY - self.age) / f64::from(TRAIL_HISTORY));
+// tiled in screen space, so it looks the same at any zoom, but shifted along with the world when panning
+fn paint_starfield(context: &cairo::Context, situation: &Situation, viewport_translation: Point) {
+    let origin = situation.transform().world_to_screen(Coordinate { x: 0., y: 0., z: 0. });
+    let offset = Point {
+        x: (viewport_translation.x + widened(origin.x)).rem_euclid(STARFIELD_TILE),
+        y: (viewport_translation.y + widened(origin.y)).rem_euclid(STARFIELD_TILE),
+    };
+    let columns = (2. * viewport_translation.x / STARFIELD_TILE).ceil() as i32 + 1;
+    let rows = (2. * viewport_translation.y / STARFIELD_TILE).ceil() as i32 + 1;
+    let stars = starfield();
+    for shade in 0..STARFIELD_SHADES {
+        let brightness = 0.25 + 0.6 * shade as f64 / (STARFIELD_SHADES - 1) as f64;
         context.set_source_rgb(brightness, brightness, brightness);
-        context.arc(0., 0., 1., 0., PI * 2.);
+        for (at, _) in stars.iter().filter(|(_, star_shade)| *star_shade == shade) {
+            for column in -1..columns {
+                for row in -1..rows {
+                    let (x, y) = (offset.x + at.x + f64::from(column) * STARFIELD_TILE, offset.y + at.y + f64::from(row) * STARFIELD_TILE);
+                    context.rectangle(x, y, 1., 1.);
+                }
+            }
+        }
         context.fill();
+    }
+}
 
-        context.restore();
+// in the bottom right corner, with the bodies as dots and the part of the world in the window as a rectangle
+fn paint_minimap(context: &cairo::Context, situation: &Situation, viewport_translation: Point) {
+    let transform = match situation.minimap_transform() { Some(transform) => transform, None => return };
+    let corner = Point { x: 2. * viewport_translation.x - MINIMAP_SIZE - MINIMAP_MARGIN, y: 2. * viewport_translation.y - MINIMAP_SIZE - MINIMAP_MARGIN };
+    let at = |position: Coordinate| {
+        let at = transform.world_to_screen(position);
+        Point { x: corner.x + widened(at.x), y: corner.y + widened(at.y) }
+    };
+
+    context.save();
+    context.rectangle(corner.x, corner.y, MINIMAP_SIZE, MINIMAP_SIZE);
+    context.set_source_rgb(0.1, 0.1, 0.12);
+    context.fill_preserve();
+    context.set_source_rgb(0.4, 0.4, 0.4);
+    context.set_line_width(1.);
+    context.stroke_preserve();
+    context.clip();
+
+    for (i, body) in situation.bodies.iter().enumerate() {
+        let (red, green, blue) = if situation.tracked_body == Some(i) { (1., 0.8, 0.2) } else { body_color(body) };
+        let at = at(body.position);
+        context.set_source_rgb(red, green, blue);
+        context.rectangle(at.x - 1., at.y - 1., 2., 2.);
+        context.fill();
     }
+
+    let window = situation.transform();
+    let world = |x: f64, y: f64| window.screen_to_world(Coordinate { x: x as Real, y: y as Real, z: 0. });
+    let (top_left, bottom_right) = (at(world(-viewport_translation.x, -viewport_translation.y)), at(world(viewport_translation.x, viewport_translation.y)));
+    context.set_source_rgb(0.8, 0.8, 0.8);
+    context.rectangle(top_left.x, top_left.y, (bottom_right.x - top_left.x).max(1.), (bottom_right.y - top_left.y).max(1.));
+    context.stroke();
+    context.restore();
+}
+
+// round ones, written out in full unless too long that way
+fn format_units(units: f64) -> String {
+    if (1e-3..1e6).contains(&units) { format!("{}", units) } else { format!("{:e}", units) }
+}
+
+// the longest round length, in world units, that fits in a scale bar, along with how long it is in pixels
+fn scale_bar(pixels_per_unit: f64) -> (f64, f64) {
+    let longest = SCALE_BAR_MAX_LENGTH / pixels_per_unit;
+    let power = 10_f64.powf(longest.log10().floor());
+    let units = IntoIterator::into_iter([5., 2., 1.]).map(|step| step * power).find(|&units| units <= longest).unwrap_or(power);
+    (units, units * pixels_per_unit)
+}
+
+// stacked at the bottom in the middle, each scaled to fit what it shows, with the updates they reach back to across
+fn paint_graphs(context: &cairo::Context, situation: &Situation, viewport_translation: Point) {
+    let latest = match situation.graphed.back() { Some(&(latest, ..)) => latest, None => return };
+    type Series = (&'static str, fn(&Totals, usize) -> f64); // named, out of the totals and the number of bodies
+    let series: [Series; 3] = [
+        ("energy", |totals, _| widened(totals.energy)),
+        ("momentum", |totals, _| widened(totals.momentum.magnitude())),
+        ("bodies", |_, bodies| bodies as f64),
+    ];
+    let left = viewport_translation.x - GRAPH_WIDTH / 2.;
+    for (i, (name, value_of)) in series.iter().enumerate() {
+        let top = 2. * viewport_translation.y - (series.len() - i) as f64 * (GRAPH_HEIGHT + 15.);
+        let values: Vec<(u64, f64)> = situation.graphed.iter().map(|&(passed, totals, bodies)| (passed, value_of(&totals, bodies))).collect();
+        let (lowest, highest) = values.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lowest, highest), &(_, value)| (lowest.min(value), highest.max(value)));
+
+        context.set_source_rgb(0.1, 0.1, 0.12);
+        context.rectangle(left, top, GRAPH_WIDTH, GRAPH_HEIGHT);
+        context.fill();
+        context.set_source_rgb(0.4, 0.8, 0.5);
+        context.set_line_width(1.);
+        for &(passed, value) in &values {
+            let x = left + GRAPH_WIDTH * (1. - (latest - passed) as f64 / GRAPH_LENGTH as f64);
+            let height = if highest > lowest { (value - lowest) / (highest - lowest) } else { 0.5 };
+            context.line_to(x, top + GRAPH_HEIGHT * (1. - height));
+        }
+        context.stroke();
+        context.set_source_rgb(0.9, 0.9, 0.9);
+        let (_, current) = values[values.len() - 1];
+        print_text(context, left, top - 3., &format!("{}: {:.4e} (range: {:.2e} to {:.2e})", name, current, lowest, highest));
+    }
+}
+
+// of the colors of bodies, above the scale bar
+fn paint_legend(context: &cairo::Context, situation: &Situation, viewport_translation: Point) {
+    let (lowest, highest) = match situation.coloring_range() { Some(range) => range, None => return };
+    let (x, y) = (10., 2. * viewport_translation.y - 45.);
+    let slices = 50;
+    for slice in 0..slices {
+        let (red, green, blue) = ramp_color(RAMPS[situation.ramp].1, f64::from(slice) / f64::from(slices - 1));
+        context.set_source_rgb(red, green, blue);
+        context.rectangle(x + f64::from(slice) * SCALE_BAR_MAX_LENGTH / f64::from(slices), y, SCALE_BAR_MAX_LENGTH / f64::from(slices) + 0.5, 8.);
+        context.fill();
+    }
+    context.set_source_rgb(0.9, 0.9, 0.9);
+    let scale = if lowest > 0. { "logarithmic" } else { "linear" };
+    print_text(context, x, y - 4., &format!("{} ({})", situation.coloring, scale));
+    print_text(context, x, y + 18., &format!("{:.2e}", lowest));
+    print_text(context, x + SCALE_BAR_MAX_LENGTH - 50., y + 18., &format!("{:.2e}", highest));
+}
+
+// in the bottom left corner, with the length it stands for above it
+fn paint_scale_bar(context: &cairo::Context, situation: &Situation, viewport_translation: Point) {
+    let (units, length) = scale_bar(widened(situation.transform().scale));
+    let (x, y) = (10., 2. * viewport_translation.y - 15.);
+    context.set_source_rgb(0.9, 0.9, 0.9);
+    context.set_line_width(1.);
+    context.move_to(x, y - 4.);
+    context.line_to(x, y);
+    context.line_to(x + length, y);
+    context.line_to(x + length, y - 4.);
+    context.stroke();
+    print_text(context, x, y - 7., &format!("{} units", format_units(units)));
+}
+
+// dimmed, so as to stay in the background, from the shallowest potential to the deepest
+fn paint_heatmap(context: &cairo::Context, heatmap: &Heatmap, ramp: Ramp, viewport_translation: Point) {
+    let depths: Vec<f64> = heatmap.potentials.iter().map(|&potential| -widened(potential)).collect();
+    let (lowest, highest) = depths.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lowest, highest), &depth| (lowest.min(depth), highest.max(depth)));
+    for (cell, &depth) in depths.iter().enumerate() {
+        let (red, green, blue) = ramp_color(ramp, ramp_position(depth, lowest, highest));
+        let x = viewport_translation.x - heatmap.half_size.x + (cell % heatmap.columns) as f64 * HEATMAP_CELL;
+        let y = viewport_translation.y - heatmap.half_size.y + (cell / heatmap.columns) as f64 * HEATMAP_CELL;
+        context.set_source_rgb(red * 0.5, green * 0.5, blue * 0.5);
+        context.rectangle(x, y, HEATMAP_CELL, HEATMAP_CELL);
+        context.fill();
+    }
+}
+
+// in world units, the power of ten that keeps the lines of the grid far enough apart
+fn grid_spacing(pixels_per_unit: f64) -> f64 {
+    10_f64.powf((GRID_MIN_SPACING / pixels_per_unit).log10().ceil())
+}
+
+// in screen space, with thin lines whatever the zoom, and the axes through the origin stressed and labeled
+fn paint_grid(context: &cairo::Context, situation: &Situation, viewport_translation: Point) {
+    let transform = situation.transform();
+    let spacing = grid_spacing(widened(transform.scale));
+    let corner = |x: f64, y: f64| transform.screen_to_world(Coordinate { x: x as Real, y: y as Real, z: 0. });
+    let (top_left, bottom_right) = (corner(-viewport_translation.x, -viewport_translation.y), corner(viewport_translation.x, viewport_translation.y));
+    let lines = |from: Real, to: Real| (widened(from) / spacing).ceil() as i64..=(widened(to) / spacing).floor() as i64;
+    let at = |x: f64, y: f64| {
+        let at = transform.world_to_screen(Coordinate { x: x as Real, y: y as Real, z: 0. });
+        Point { x: viewport_translation.x + widened(at.x), y: viewport_translation.y + widened(at.y) }
+    };
+    let line = |from: Point, to: Point| {
+        context.move_to(from.x, from.y);
+        context.line_to(to.x, to.y);
+    };
+
+    let (left, right, top, bottom) = (widened(top_left.x), widened(bottom_right.x), widened(top_left.y), widened(bottom_right.y));
+    context.set_line_width(1.);
+    context.set_source_rgb(0.15, 0.15, 0.2);
+    for column in lines(top_left.x, bottom_right.x).filter(|&column| column != 0) {
+        line(at(column as f64 * spacing, top), at(column as f64 * spacing, bottom));
+    }
+    for row in lines(top_left.y, bottom_right.y).filter(|&row| row != 0) {
+        line(at(left, row as f64 * spacing), at(right, row as f64 * spacing));
+    }
+    context.stroke();
+
+    let origin = at(0., 0.);
+    let units = format_units(spacing);
+    context.set_source_rgb(0.35, 0.35, 0.45);
+    line(at(left, 0.), at(right, 0.));
+    line(at(0., top), at(0., bottom));
+    context.stroke();
+    print_text(context, 2. * viewport_translation.x - 100., origin.y - 4., &format!("x, grid of {}", units));
+    print_text(context, origin.x + 4., 2. * viewport_translation.y - 10., "y");
+}
+
+fn paint_barycenter(context: &cairo::Context, at: Point) {
+    context.set_source_rgb(0.9, 0.3, 0.3);
+    context.arc(at.x, at.y, 4., 0., PI * 2.);
+    context.move_to(at.x - 6., at.y);
+    context.line_to(at.x + 6., at.y);
+    context.move_to(at.x, at.y - 6.);
+    context.line_to(at.x, at.y + 6.);
+    context.stroke();
+}
+
+fn paint_transfer(context: &cairo::Context, view: &View, transfer: &Hohmann, primary: Coordinate) {
+    context.save();
+    context.set_source_rgb(1., 0.6, 0.2);
+    context.set_dash(&[6., 3.], 0.);
+    paint_path(context, view, &transfer.path(primary));
+    context.restore();
+
+    let arrival = view.at(transfer.arrival_point(primary));
+    context.set_source_rgb(1., 0.6, 0.2);
+    context.arc(arrival.x, arrival.y, 3., 0., PI * 2.);
+    context.fill();
+}
+
+fn paint_maneuver(context: &cairo::Context, view: &View, node: Coordinate, path: &[Coordinate]) {
+    context.save();
+    context.set_source_rgb(0.3, 0.8, 1.);
+    let at = view.at(node);
+    context.arc(at.x, at.y, 5., 0., PI * 2.);
+    context.stroke();
+    context.set_dash(&[2., 3.], 0.);
+    paint_path(context, view, path);
+    context.restore();
+}
+
+// in stretches of the same shade, to keep the strokes few
+fn paint_trail(context: &cairo::Context, view: &View, trail: &Trail, updates: u64, length: u32) {
+    let (red, green, blue) = trail.color;
+    context.set_line_width(view.quality.trail_width());
+    let stroke = |shade: u64| {
+        let brightness = 0.7 * f64::max(0.05, 1. - (shade as f64 + 0.5) / TRAIL_SHADES as f64);
+        context.set_source_rgb(red * brightness, green * brightness, blue * brightness);
+        context.stroke();
+    };
+
+    let mut last: Option<(Point, u64)> = None;
+    for &(position, passed) in &trail.points {
+        let point = view.at(position);
+        let shade = (updates - passed) * TRAIL_SHADES / u64::from(length).max(1);
+        match last {
+            None => context.move_to(point.x, point.y),
+            Some((_, last_shade)) if last_shade == shade => context.line_to(point.x, point.y),
+            Some((start, last_shade)) => {
+                stroke(last_shade);
+                context.move_to(start.x, start.y);
+                context.line_to(point.x, point.y);
+            }
+        }
+        last = Some((point, shade));
+    }
+    if let Some((_, shade)) = last { stroke(shade); }
+}
+
+fn paint_path(context: &cairo::Context, view: &View, path: &[Coordinate]) {
+    if path.is_empty() { return; }
+
+    let start = view.at(path[0]);
+    context.move_to(start.x, start.y);
+    for point in path[1..].iter().map(|&point| view.at(point)) { context.line_to(point.x, point.y); }
+    context.stroke();
 }
 
 fn print_text(context: &cairo::Context, x: f64, y: f64, text: &str) {
@@ -199,44 +1800,333 @@ fn print_text(context: &cairo::Context, x: f64, y: f64, text: &str) {
 }
 
 fn print_debug(context: &cairo::Context, situation: &Situation) {
+    let now = Instant::now();
+    let rates = match (situation.frame_rate.per_second(now), situation.update_rate.per_second(now)) {
+        (Some(frames), Some(updates)) => {
+            let behind = (frames as f64) < f64::from(REFRESH_RATE) * RATE_TOLERANCE || (updates as f64) < f64::from(UPDATE_RATE) * RATE_TOLERANCE;
+            if behind { context.set_source_rgb(1., 0.5, 0.2); } else { context.set_source_rgb(1., 1., 1.); }
+            format!("{} fps, {} updates/s", frames, updates)
+        }
+        _ => {
+            context.set_source_rgb(1., 1., 1.);
+            "measuring rates".to_string()
+        }
+    };
+    print_text(context, 10., 15., &format!("{} ({})", Local::now().format("%Y-%m-%d %H:%M:%S"), rates));
     context.set_source_rgb(1., 1., 1.);
-    print_text(context, 10., 15., &format!("{}", Local::now().format("%Y-%m-%d %H:%M:%S")));
-    print_text(context, 10., 25., &format!("bodies: {}", situation.bodies.len()));
-    print_text(context, 10., 35., &format!("forces: {}", situation.count_forces()));
-    print_text(context, 10., 45., &format!("iteration: {}", situation.updates));
-    print_text(context, 10., 55., &format!("zoom: {}", situation.zoom_exponent));
-    print_text(context, 10., 65., &format!("center: {}", -situation.center_translation()));
-    if situation.fullscreen { print_text(context, 10., 85., "Fullscreen"); }
-    if situation.paused { print_text(context, 10., 95., "Paused"); }
+    let mode = if situation.performance { ", performance mode" } else { "" };
+    print_text(context, 10., 25., &format!("bodies: {} (particles: {}{})", situation.bodies.len(), situation.particles.len(), mode));
+    print_text(context, 10., 35., &format!("forces: {} (quality: {})", situation.count_forces(), situation.quality));
+    print_text(context, 10., 45., &format!("iteration: {} (checksum: {:016x})", situation.updates, physics::checksum(&situation.bodies)));
+    let trails = match situation.trail_length {
+        0 => "off".to_string(),
+        length => format!("{} updates, marked every {}", length, situation.trail_spacing),
+    };
+    let units_per_pixel = 1. / widened(situation.transform().scale);
+    print_text(context, 10., 55., &format!("scale: {:.2e} units/px (tilt: {:.0}°), trails: {}", units_per_pixel, situation.tilt.to_degrees(), trails));
+    let extent = situation.bounds().map_or(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |bounds| bounds.size());
+    print_text(context, 10., 65., &format!("center: {} (extent: {:.1} × {:.1})", -situation.center_translation(), extent.dx, extent.dy));
+    print_text(context, 10., 75., &format!("integrator: {} (substeps: {}, time scale: {}×)", situation.integrator, situation.substeps, situation.time_scale));
+    print_text(context, 10., 85., &format!("solver: {} (softening: {:.1})", situation.solvers[situation.solver], situation.constants.softening));
+    let collisions = &situation.collisions;
+    match collisions.mode {
+        CollisionMode::Bounce => print_text(context, 10., 95., &format!("collisions: {} (restitution: {:.1})", collisions.mode, collisions.restitution)),
+        CollisionMode::Fragment => print_text(context, 10., 95., &format!("collisions: {} (above energy: {})", collisions.mode, collisions.fragmentation_energy)),
+        CollisionMode::Accrete => print_text(context, 10., 95., &format!("collisions: {} (rate: {})", collisions.mode, collisions.accretion_rate)),
+        _ => print_text(context, 10., 95., &format!("collisions: {}", collisions.mode)),
+    }
+    match situation.constants.speed_of_light {
+        Some(speed_of_light) => print_text(context, 10., 105., &format!("relativity: on (c = {})", speed_of_light)),
+        None => print_text(context, 10., 105., "relativity: off"),
+    }
+    let disruption = if situation.tidal_disruption.enabled { format!("on (particles: {})", situation.tidal_disruption.particles) } else { "off".to_string() };
+    let locking = if situation.tidal_locking.enabled { format!("on (Q = {})", situation.tidal_locking.quality) } else { "off".to_string() };
+    print_text(context, 10., 115., &format!("tidal disruption: {}, locking: {}", disruption, locking));
+    if let (Some(totals), Some(initial)) = (situation.totals, situation.initial_totals) {
+        print_totals(context, 10., 125., &totals, &initial);
+    }
+    if let Some(ship) = situation.ship {
+        let throttle = situation.bodies[ship].engine.map_or(0., |engine| engine.throttle);
+        print_text(context, 10., 155., &format!("piloting: {} (throttle: {:.0}%)", situation.bodies[ship].name, throttle * 100.));
+    }
+    if let Some(engine) = situation.ship.or(situation.tracked_body).and_then(|body| situation.bodies[body].engine) {
+        print_text(context, 10., 165., &format!("propellant: {:.2}", engine.propellant));
+    }
+    if let Some(tracked) = situation.tracked_body {
+        if let Some(primary) = orbit::dominant_attractor(&situation.bodies, tracked) {
+            print_orbit(context, 10., 175., &situation.bodies[tracked], &situation.bodies[primary], &situation.constants);
+        }
+    }
+    if situation.fullscreen { print_text(context, 10., 225., "Fullscreen"); }
+    if situation.paused { print_text(context, 10., 235., "Paused"); }
+    if situation.reversed { print_text(context, 10., 245., "Reversed"); }
+    if situation.history.rewound_by() > 0 { print_text(context, 10., 255., &format!("Rewound by {} updates", situation.history.rewound_by())); }
+    if let (Some((transfer, _)), Some(target)) = (situation.transfer(), situation.transfer_target) {
+        print_transfer(context, 10., 270., &transfer, target);
+    }
+    if let Some(maneuver) = situation.maneuver {
+        print_text(context, 10., 305., &format!("maneuver of {} in {} updates (delta-v: {:.3})",
+            situation.bodies[maneuver.body].name, maneuver.at.saturating_sub(situation.updates), maneuver.magnitude()));
+        print_text(context, 10., 315., &format!("prograde: {:+.3}, radial: {:+.3}", maneuver.prograde, maneuver.radial));
+    }
+    if let Some((planet, flyby)) = situation.tracked_body.and_then(|tracked| situation.flyby_of(tracked)) {
+        print_flyby(context, 10., 330., &flyby, &situation.bodies[planet]);
+    }
 }
 
-fn viewport_translation(viewport: &gtk::DrawingArea) -> EuclideanVector {
-    EuclideanVector {
-        dx: f64::from(viewport.get_allocated_width()) / 2.,
-        dy: f64::from(viewport.get_allocated_height()) / 2.,
+fn print_flyby(context: &cairo::Context, x: f64, y: f64, flyby: &Flyby, planet: &Body) {
+    print_text(context, x, y, &format!("flyby of {} (periapsis: {:.1})", planet.name, flyby.periapsis));
+    print_text(context, x, y + 10., &format!("excess speed: {:.3}, turn: {:.1}°", flyby.excess_speed, flyby.turn_angle.to_degrees()));
+    print_text(context, x, y + 20., &format!("speed change: {:+.3}", flyby.speed_change));
+}
+
+fn print_transfer(context: &cairo::Context, x: f64, y: f64, transfer: &Hohmann, target: Length) {
+    print_text(context, x, y, &format!("Hohmann transfer to radius {:.1} (total delta-v: {:.3})", target, transfer.total_burn()));
+    print_text(context, x, y + 10., &format!("departure burn: {:+.3} now", transfer.departure_burn));
+    print_text(context, x, y + 20., &format!("arrival burn: {:+.3} in {:.1}", transfer.arrival_burn, transfer.duration));
+}
+
+// of the tracked body, in the top right corner
+fn print_flight_data(context: &cairo::Context, x: f64, y: f64, situation: &Situation) {
+    let tracked = match situation.tracked_body { Some(tracked) => tracked, None => return };
+    let body = &situation.bodies[tracked];
+
+    print_text(context, x, y, &format!("flight data: {}", body.name));
+    if let Some(primary) = orbit::dominant_attractor(&situation.bodies, tracked).map(|primary| &situation.bodies[primary]) {
+        let elements = Elements::of(body, primary, &situation.constants);
+        let gravitational_parameter = situation.constants.gravitational * (body.mass + primary.mass);
+        let distance = (body.position - primary.position).magnitude();
+        let speed = (body.velocity - primary.velocity).magnitude();
+        print_text(context, x, y + 10., &format!("specific orbital energy: {:.3}", orbit::specific_energy(body, primary, &situation.constants)));
+        print_text(context, x, y + 20., &format!("speed: {:.3} (vis-viva: {:.3})", speed, elements.speed_at(distance, gravitational_parameter)));
+        if let Some(ratio) = tidal::spin_to_orbit_period(body, primary) {
+            print_text(context, x, y + 30., &format!("spin period : orbit period = {:.3}", ratio));
+        }
+    }
+    if let Some(engine) = body.engine {
+        let (spent, remaining) = engine.delta_v(body.mass);
+        print_text(context, x, y + 40., &format!("delta-v spent: {:.3} (remaining: {:.3})", spent, remaining));
+    }
+    print_text(context, x, y + 55., &format!("mass: {:.4e}, radius: {:.4e}", body.mass, body.radius));
+    print_text(context, x, y + 65., &format!("speed: {:.4e}", body.velocity.magnitude()));
+    if let Some(heaviest) = physics::heaviest_first(&situation.bodies).into_iter().find(|&heaviest| heaviest != tracked) {
+        let distance = (situation.bodies[heaviest].position - body.position).magnitude();
+        print_text(context, x, y + 75., &format!("distance to {}: {:.4e}", situation.bodies[heaviest].name, distance));
+    }
+    let net_force = body.forces.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, &force| sum + force);
+    print_text(context, x, y + 85., &format!("net force: {:.4e}", net_force.magnitude()));
+}
+
+fn print_totals(context: &cairo::Context, x: f64, y: f64, totals: &Totals, initial: &Totals) {
+    let drift = totals.drift_from(initial);
+    print_text(context, x, y, &format!("energy: {:.3} (drift: {:+.2e})", totals.energy, drift.energy));
+    print_text(context, x, y + 10., &format!("momentum: {} (drift: {:.2e})", totals.momentum, drift.momentum.magnitude()));
+    print_text(context, x, y + 20., &format!("angular momentum: {:.3} (drift: {:+.2e})", totals.angular_momentum, drift.angular_momentum));
+}
+
+fn print_orbit(context: &cairo::Context, x: f64, y: f64, body: &Body, primary: &Body, constants: &Constants) {
+    let elements = Elements::of(body, primary, constants);
+    let optional = |value: Option<Real>| value.map_or_else(|| "-".to_string(), |value| format!("{:.1}", value));
+
+    print_text(context, x, y, &format!("orbit around: {}", primary.name));
+    print_text(context, x, y + 10., &format!("semi-major axis: {:.1}, eccentricity: {:.3}", elements.semi_major_axis, elements.eccentricity));
+    print_text(context, x, y + 20., &format!("periapsis: {:.1}, apoapsis: {}", elements.periapsis, optional(elements.apoapsis)));
+    print_text(context, x, y + 30., &format!("period: {}", optional(elements.period)));
+    let trajectory = if elements.apoapsis.is_some() { "bound" } else { "hyperbolic" };
+    print_text(context, x, y + 40., &format!("trajectory: {} (speed: {:.2} of escape velocity)", trajectory, orbit::escape_velocity_ratio(body, primary, constants)));
+}
+
+fn viewport_translation(viewport: &gtk::DrawingArea) -> Point {
+    Point {
+        x: f64::from(viewport.get_allocated_width()) / 2.,
+        y: f64::from(viewport.get_allocated_height()) / 2.,
     }
 }
 
-fn paint(drawing_area: &gtk::DrawingArea, context: &cairo::Context, situation: &Situation) -> gtk::Inhibit {
+// relative to the center of the window, from the position within it
+fn cursor_in(viewport: &gtk::DrawingArea, (x, y): (f64, f64)) -> Point {
+    let center = viewport_translation(viewport);
+    Point { x: x - center.x, y: y - center.y }
+}
+
+// onto a viewport, of the given half-size, of whatever surface the context draws on
+fn paint(context: &cairo::Context, situation: &Situation, viewport_translation: Point) {
+    context.set_antialias(situation.quality.antialias());
+    let mut font_options = cairo::FontOptions::new();
+    font_options.set_antialias(situation.quality.text_antialias());
+    context.set_font_options(&font_options);
     context.set_source_rgb(0.05, 0.05, 0.05);
     context.paint();
+    match &situation.heatmap {
+        Some(heatmap) => paint_heatmap(context, heatmap, RAMPS[situation.ramp].1, viewport_translation),
+        None if !situation.performance => paint_starfield(context, situation, viewport_translation),
+        None => {}
+    }
+    if situation.show_grid && !situation.clean { paint_grid(context, situation, viewport_translation); }
     context.save();
 
-    let viewport_translation = viewport_translation(drawing_area);
-    context.translate(viewport_translation.dx, viewport_translation.dy);
+    context.translate(viewport_translation.x, viewport_translation.y);
 
     let scale = situation.zoom();
     context.scale(scale, scale);
 
-    let translation = situation.center_translation();
-    context.translate(translation.dx, translation.dy);
-
-    for body in &situation.bodies { body.paint_on(context); }
-    for mark in &situation.marks { mark.paint_on(context); }
+    let view = View { colors: situation.body_colors(), ..situation.view() };
+    context.save();
+    for trail in situation.trails.values() { paint_trail(context, &view, trail, situation.updates, situation.trail_length); }
+    context.restore();
+    if situation.performance {
+        context.set_source_rgb(1., 1., 1.);
+        paint_dots(context, &view, situation.bodies.iter().map(|body| body.position));
+    } else {
+        for body in &situation.bodies { body.paint_on(context, &view); }
+    }
+    if let Some(body) = &situation.placing { body.paint_on(context, &view); }
+    context.set_source_rgb(0.6, 0.5, 0.4);
+    paint_dots(context, &view, situation.particles.iter().map(|particle| particle.position));
+    if !situation.clean { paint_overlays(context, &view, situation); }
     context.restore();
 
-    print_debug(context, situation);
-    Inhibit(false)
+    if situation.clean { return; }
+    if situation.show_labels && !situation.performance { paint_labels(context, situation, viewport_translation); }
+    if let Some(body) = &situation.placing {
+        let at = situation.transform().world_to_screen(body.position);
+        let (x, y) = (viewport_translation.x + widened(at.x) + LABEL_GAP, viewport_translation.y + widened(at.y) - LABEL_GAP);
+        print_text(context, x, y, &format!("mass: {:.1e} (scroll to change, drag for velocity)", body.mass));
+    }
+    if situation.show_hud { print_debug(context, situation); }
+    if situation.show_minimap { paint_minimap(context, situation, viewport_translation); }
+    paint_scale_bar(context, situation, viewport_translation);
+    if situation.show_graphs { paint_graphs(context, situation, viewport_translation); }
+    paint_legend(context, situation, viewport_translation);
+    print_flight_data(context, 2. * viewport_translation.x - FLIGHT_DATA_WIDTH, 15., situation);
+}
+
+// of the given size, whatever the size of the window
+fn render(situation: &Situation, width: i32, height: i32) -> Result<cairo::ImageSurface, String> {
+    let surface = cairo::ImageSurface::create(cairo::Format::Rgb24, width, height).map_err(|status| format!("{:?}", status))?;
+    paint(&cairo::Context::new(&surface), situation, Point { x: f64::from(width) / 2., y: f64::from(height) / 2. });
+    Ok(surface)
+}
+
+fn render_png(situation: &Situation, width: i32, height: i32, path: &str) -> Result<(), String> {
+    let surface = render(situation, width, height)?;
+    let mut file = std::fs::File::create(path).map_err(|error| error.to_string())?;
+    surface.write_to_png(&mut file).map_err(|error| error.to_string())
+}
+
+// as drawn on the given vector surface, which is finished by then
+fn render_figure(surface: &cairo::Surface, situation: &Situation) {
+    paint(&cairo::Context::new(surface), situation, Point { x: FIGURE_WIDTH / 2., y: FIGURE_HEIGHT / 2. });
+    surface.finish();
+}
+
+// as both SVG and PDF, so that it is sharp at any size
+fn save_figures(situation: &Situation) {
+    let name = format!("figure-{}", Local::now().format("%Y%m%d-%H%M%S"));
+    let (svg, pdf) = (format!("{}.svg", name), format!("{}.pdf", name));
+    match cairo::SvgSurface::new(FIGURE_WIDTH, FIGURE_HEIGHT, Some(&svg)) {
+        Ok(surface) => render_figure(&surface, situation),
+        Err(error) => {
+            eprintln!("Failed to save {}: {:?}", svg, error);
+            return;
+        }
+    }
+    match cairo::PdfSurface::new(FIGURE_WIDTH, FIGURE_HEIGHT, &pdf) {
+        Ok(surface) => render_figure(&surface, situation),
+        Err(error) => {
+            eprintln!("Failed to save {}: {:?}", pdf, error);
+            return;
+        }
+    }
+    println!("update {}: figures saved to {} and {}", situation.updates, svg, pdf);
+}
+
+// row by row, in the palette of animations
+fn render_palette_indices(situation: &Situation, width: u16, height: u16) -> Result<Vec<u8>, String> {
+    let surface = render(situation, i32::from(width), i32::from(height))?;
+    let stride = surface.get_stride() as usize;
+    let mut indices = Vec::with_capacity(usize::from(width) * usize::from(height));
+    surface.with_data(|data| {
+        for row in 0..usize::from(height) {
+            for pixel in data[row * stride..].chunks(4).take(usize::from(width)) {
+                indices.push(gif::palette_index(pixel[2], pixel[1], pixel[0])); // blue, green and red, on little endian machines
+            }
+        }
+    }).map_err(|error| format!("{:?}", error))?;
+    Ok(indices)
+}
+
+fn save_screenshot(situation: &Situation) {
+    let path = format!("screenshot-{}.png", Local::now().format("%Y%m%d-%H%M%S"));
+    match render_png(situation, SCREENSHOT_WIDTH, SCREENSHOT_HEIGHT, &path) {
+        Ok(()) => println!("update {}: screenshot saved to {}", situation.updates, path),
+        Err(error) => eprintln!("Failed to save {}: {}", path, error),
+    }
+}
+
+// of the tracked body and then of the heaviest ones, in screen space so that they keep their size when zooming
+fn paint_labels(context: &cairo::Context, situation: &Situation, viewport_translation: Point) {
+    let mut order = physics::heaviest_first(&situation.bodies);
+    order.retain(|&body| !situation.hidden_labels.contains(&situation.bodies[body].id));
+    order.sort_by_key(|&body| situation.tracked_body != Some(body));
+
+    let transform = situation.transform();
+    let labels: Vec<(Point, f64)> = order.iter().map(|&body| {
+        let at = transform.world_to_screen(situation.bodies[body].position);
+        let anchor = Point { x: viewport_translation.x + widened(at.x), y: viewport_translation.y + widened(at.y) };
+        (anchor, context.text_extents(&situation.bodies[body].name).x_advance)
+    }).collect();
+
+    for (&body, corner) in order.iter().zip(place_labels(&labels)) {
+        let corner = match corner { Some(corner) => corner, None => continue };
+        if situation.bodies[body].highlighted { context.set_source_rgb(1., 1., 0.); } else { context.set_source_rgb(1., 1., 1.); }
+        print_text(context, corner.x, corner.y + LABEL_HEIGHT - 2., &situation.bodies[body].name);
+    }
+}
+
+// Top left corners of labels of the given widths, next to the given points on either side of them, each placed so as
+// not to overlap the ones before it, or left out if there is no such place.
+fn place_labels(labels: &[(Point, f64)]) -> Vec<Option<Point>> {
+    let mut placed: Vec<(Point, f64)> = Vec::new();
+    labels.iter().map(|&(anchor, width)| {
+        let candidates = [
+            Point { x: anchor.x + LABEL_GAP, y: anchor.y },
+            Point { x: anchor.x + LABEL_GAP, y: anchor.y - LABEL_HEIGHT },
+            Point { x: anchor.x - LABEL_GAP - width, y: anchor.y },
+            Point { x: anchor.x - LABEL_GAP - width, y: anchor.y - LABEL_HEIGHT },
+        ];
+        let free = IntoIterator::into_iter(candidates).find(|corner| placed.iter().all(|&(other, other_width)| {
+            corner.x >= other.x + other_width || other.x >= corner.x + width || (corner.y - other.y).abs() >= LABEL_HEIGHT
+        }));
+        if let Some(corner) = free { placed.push((corner, width)); }
+        free
+    }).collect()
+}
+
+// of what is worked out about the bodies, on top of them
+fn paint_overlays(context: &cairo::Context, view: &View, situation: &Situation) {
+    paint_barycenter(context, view.at(situation.barycenter()));
+    if situation.show_hill_spheres { paint_hill_spheres(context, view, &situation.bodies, &situation.constants); }
+
+    if let Some(tracked) = situation.tracked_body {
+        if let Some(primary) = orbit::dominant_attractor(&situation.bodies, tracked) {
+            paint_apsides(context, view, &situation.bodies[tracked], &situation.bodies[primary], &situation.constants);
+            if situation.show_lagrange_points {
+                paint_lagrange_points(context, view, &situation.bodies[primary], &situation.bodies[tracked]);
+            }
+        }
+    }
+
+    if let Some((transfer, primary)) = situation.transfer() {
+        paint_transfer(context, view, &transfer, primary.position);
+    }
+    if let Some((node, path)) = &situation.maneuver_prediction {
+        paint_maneuver(context, view, *node, path);
+    }
+
+    context.set_source_rgb(0.3, 0.6, 0.3);
+    context.set_dash(&[4., 4.], 0.);
+    for path in &situation.prediction { paint_path(context, view, path); }
 }
 
 fn toggle_fullscreen(window: &gtk::ApplicationWindow, model: &mut Situation) {
@@ -250,12 +2140,28 @@ fn toggle_fullscreen(window: &gtk::ApplicationWindow, model: &mut Situation) {
 
 const DEFAULT_CONTEXT: Option<&glib::MainContext> = None;
 
+#[derive(Clone)]
 enum Event {
     UpdateModel,
     KeyPressed(gdk::keys::Key),
-    Scrolling(gdk::ScrollDirection),
-    MousePressed(Coordinate),
-    MouseDragged(Coordinate),
+    KeyReleased(gdk::keys::Key),
+    Scrolling(gdk::ScrollDirection, Point), // with the cursor relative to the center of the window
+    MousePressed(Point), // relative to the center of the window
+    MouseRightPressed(Point), // relative to the center of the window
+    MouseControlPressed(Point), // relative to the center of the window
+    MouseDoubleClicked(Point), // relative to the center of the window
+    MouseReleased,
+    MouseDragged(Point), // relative to the center of the window
+    Follow(Option<Following>), // chosen from the camera menu
+    Track(usize), // chosen from the body list
+    ToggleTrail(usize), // of the body, from the body list
+    ToggleLabel(usize), // of the body, from the body list
+    Delete(usize), // the body, from its menu
+    Duplicate(usize), // the body, from its menu
+    Center(usize), // on the body, from its menu
+    OpenEditor(usize), // of the body, from its menu
+    Edit(BodyId, Properties), // of the body, from its editor
+    EditorClosed,
 }
 
 macro_rules! with_clone_of {
@@ -269,11 +2175,16 @@ fn build_ui(application: &gtk::Application, model: Rc<RefCell<Situation>>) {
     let drawing_area = gtk::DrawingArea::new();
     drawing_area.add_events(
         gdk::EventMask::BUTTON_PRESS_MASK |
+        gdk::EventMask::BUTTON_RELEASE_MASK |
         gdk::EventMask::SCROLL_MASK |
         gdk::EventMask::POINTER_MOTION_MASK);
 
     with_clone_of!(model, drawing_area.connect_draw(move |drawing_area, cairo_context| {
-        paint(drawing_area, cairo_context, &model.borrow())
+        let mut model = model.borrow_mut();
+        model.frame_rate.tick(Instant::now());
+        model.refresh_heatmap(viewport_translation(drawing_area));
+        paint(cairo_context, &model, viewport_translation(drawing_area));
+        Inhibit(false)
     }));
 
     let window = gtk::ApplicationWindow::new(application);
@@ -281,30 +2192,59 @@ fn build_ui(application: &gtk::Application, model: Rc<RefCell<Situation>>) {
     window.set_border_width(0);
     window.set_position(gtk::WindowPosition::Center);
     window.set_default_size(1024, 768);
-    window.add(&drawing_area);
-    window.show_all();
 
     let (event_sender, event_receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
 
+    let (body_list, body_store) = build_body_list(&event_sender);
+    let paned = gtk::Paned::new(gtk::Orientation::Horizontal);
+    paned.pack1(&body_list, false, true);
+    paned.pack2(&drawing_area, true, false);
+    window.add(&paned);
+    window.show_all();
+
+    with_clone_of!(model, gtk::timeout_add(BODY_LIST_REFRESH, move || {
+        refresh_body_list(&body_store, &model.borrow());
+        glib::Continue(true)
+    }));
+
     with_clone_of!(event_sender, window.connect_key_press_event(move |_, gdk| {
         event_sender.send(Event::KeyPressed(gdk.get_keyval())).expect("Failed to raise KeyPressed event");
         Inhibit(false)
     }));
 
-    with_clone_of!(event_sender, drawing_area.connect_button_press_event(move |_, gdk| {
-        event_sender.send(Event::MousePressed(Coordinate::from(gdk.get_position()))).expect("Failed to raise MousePressed event");
+    with_clone_of!(event_sender, window.connect_key_release_event(move |_, gdk| {
+        event_sender.send(Event::KeyReleased(gdk.get_keyval())).expect("Failed to raise KeyReleased event");
+        Inhibit(false)
+    }));
+
+    with_clone_of!(event_sender, drawing_area.connect_button_press_event(move |drawing_area, gdk| {
+        let cursor = cursor_in(drawing_area, gdk.get_position());
+        if gdk.get_event_type() == gdk::EventType::DoubleButtonPress {
+            event_sender.send(Event::MouseDoubleClicked(cursor)).expect("Failed to raise MouseDoubleClicked event");
+        } else if gdk.get_button() == 3 {
+            event_sender.send(Event::MouseRightPressed(cursor)).expect("Failed to raise MouseRightPressed event");
+        } else if gdk.get_state().contains(gdk::ModifierType::CONTROL_MASK) {
+            event_sender.send(Event::MouseControlPressed(cursor)).expect("Failed to raise MouseControlPressed event");
+        } else {
+            event_sender.send(Event::MousePressed(cursor)).expect("Failed to raise MousePressed event");
+        }
+        Inhibit(false)
+    }));
+
+    with_clone_of!(event_sender, drawing_area.connect_button_release_event(move |_, _| {
+        event_sender.send(Event::MouseReleased).expect("Failed to raise MouseReleased event");
         Inhibit(false)
     }));
 
-    with_clone_of!(event_sender, drawing_area.connect_motion_notify_event(move |_, gdk| {
+    with_clone_of!(event_sender, drawing_area.connect_motion_notify_event(move |drawing_area, gdk| {
         if gdk.get_state().contains(gdk::ModifierType::BUTTON1_MASK) {
-            event_sender.send(Event::MouseDragged(Coordinate::from(gdk.get_position()))).expect("Failed to raise MouseDragged event");
+            event_sender.send(Event::MouseDragged(cursor_in(drawing_area, gdk.get_position()))).expect("Failed to raise MouseDragged event");
         }
         Inhibit(false)
     }));
 
-    with_clone_of!(event_sender, drawing_area.connect_scroll_event(move |_, gdk| {
-        event_sender.send(Event::Scrolling(gdk.get_direction())).expect("Failed to raise Scrolling event");
+    with_clone_of!(event_sender, drawing_area.connect_scroll_event(move |drawing_area, gdk| {
+        event_sender.send(Event::Scrolling(gdk.get_direction(), cursor_in(drawing_area, gdk.get_position()))).expect("Failed to raise Scrolling event");
         Inhibit(false)
     }));
 
@@ -313,30 +2253,121 @@ fn build_ui(application: &gtk::Application, model: Rc<RefCell<Situation>>) {
         glib::Continue(true)
     }));
 
-    gtk::timeout_add(1000 / REFRESH_RATE, move || {
+    with_clone_of!(model, with_clone_of!(drawing_area, gtk::timeout_add(1000 / REFRESH_RATE, move || {
         drawing_area.queue_draw();
+        model.borrow_mut().record_frame();
         glib::Continue(true)
-    });
+    })));
 
     event_receiver.attach(DEFAULT_CONTEXT, move |event| {
         let mut model = model.borrow_mut();
         match event {
             Event::UpdateModel => model.update(),
+            Event::KeyPressed(keys::constants::Escape) if model.tracking() => model.stop_tracking(),
             Event::KeyPressed(keys::constants::Escape) => window.close(),
             Event::KeyPressed(keys::constants::F12)    => window.close(),
+            Event::KeyPressed(keys::constants::F10)    => save_screenshot(&model),
+            Event::KeyPressed(keys::constants::S)      => save_figures(&model),
             Event::KeyPressed(keys::constants::F11)    => toggle_fullscreen(&window, &mut model),
+            Event::KeyPressed(keys::constants::F1)     => model.toggle_recording(),
+            Event::KeyPressed(keys::constants::M)      => model.toggle_animation(),
+            Event::KeyPressed(keys::constants::F2)     => model.toggle_hud(),
+            Event::KeyPressed(keys::constants::F3)     => model.toggle_clean(),
+            Event::KeyPressed(keys::constants::F4)     => model.toggle_labels(),
+            Event::KeyPressed(keys::constants::numbersign) => model.toggle_grid(),
+            Event::KeyPressed(keys::constants::m)      => model.toggle_minimap(),
+            Event::KeyPressed(keys::constants::C)      => model.switch_coloring(),
+            Event::KeyPressed(keys::constants::N)      => model.toggle_graphs(),
+            Event::KeyPressed(keys::constants::W)      => model.toggle_heatmap(),
+            Event::KeyPressed(keys::constants::Q)      => model.switch_quality(),
+            Event::KeyPressed(keys::constants::F5)     => model.take_snapshot(),
+            Event::KeyPressed(keys::constants::F6)     => model.save(),
+            Event::KeyPressed(keys::constants::F9)     => model.restore_snapshot(),
+            Event::KeyPressed(keys::constants::F8)     => model.toggle_performance(),
+            Event::KeyPressed(keys::constants::D)      => model.log_bodies(),
             Event::KeyPressed(keys::constants::plus)   => model.zoom_in(),
             Event::KeyPressed(keys::constants::minus)  => model.zoom_out(),
             Event::KeyPressed(keys::constants::_0)     => model.zoom_reset(),
+            Event::KeyPressed(keys::constants::Home)   => model.frame_all(viewport_translation(&drawing_area)),
             Event::KeyPressed(keys::constants::space)  => model.toggle_pause(),
-            Event::KeyPressed(keys::constants::Left)   => model.translation.dx += SCROLL_STEP,
-            Event::KeyPressed(keys::constants::Right)  => model.translation.dx -= SCROLL_STEP,
-            Event::KeyPressed(keys::constants::Up)     => model.translation.dy += SCROLL_STEP,
-            Event::KeyPressed(keys::constants::Down)   => model.translation.dy -= SCROLL_STEP,
+            Event::KeyPressed(keys::constants::p)      => model.toggle_piloting(),
+            Event::KeyPressed(keys::constants::P)      => model.toggle_prediction(),
+            Event::KeyPressed(keys::constants::L)      => model.toggle_lagrange_points(),
+            Event::KeyPressed(keys::constants::b)      => model.toggle_following_barycenter(),
+            Event::KeyPressed(keys::constants::B)      => show_camera_menu(&model, &event_sender),
+            Event::Follow(following)                   => model.follow(following),
+            Event::Track(body) if body < model.bodies.len() => model.track(Some(body)),
+            Event::ToggleTrail(body) if body < model.bodies.len() => model.toggle_trail_of(body),
+            Event::ToggleLabel(body) if body < model.bodies.len() => model.toggle_label_of(body),
+            Event::Delete(body) if body < model.bodies.len() => model.delete_body(body),
+            Event::Duplicate(body) if body < model.bodies.len() => model.duplicate_body(body),
+            Event::Center(body) if body < model.bodies.len() => model.center_on(body),
+            Event::OpenEditor(body) if body < model.bodies.len() => show_body_editor(&window, &mut model, body, &event_sender),
+            Event::Edit(body, properties)              => model.edit_body(body, properties),
+            Event::EditorClosed                        => model.finish_editing(),
+            Event::MouseDoubleClicked(coordinate)      => if let Some(body) = model.body_at(coordinate) { show_body_editor(&window, &mut model, body, &event_sender) },
+            Event::KeyPressed(keys::constants::e)      => if let Some(tracked) = model.tracked_body { show_body_editor(&window, &mut model, tracked, &event_sender) },
+            Event::KeyPressed(keys::constants::h)      => model.toggle_hill_spheres(),
+            Event::KeyPressed(keys::constants::v)      => model.toggle_velocities(),
+            Event::KeyPressed(keys::constants::f)      => model.toggle_forces(),
+            Event::KeyPressed(keys::constants::V)      => model.toggle_vectors(),
+            Event::KeyPressed(keys::constants::H)      => model.toggle_transfer_planner(),
+            Event::KeyPressed(keys::constants::n)      => model.toggle_maneuver(),
+            Event::KeyPressed(keys::constants::less)   => model.shift_maneuver(false),
+            Event::KeyPressed(keys::constants::greater) => model.shift_maneuver(true),
+            Event::KeyPressed(keys::constants::u)      => model.adjust_maneuver(1., 0.),
+            Event::KeyPressed(keys::constants::j)      => model.adjust_maneuver(-1., 0.),
+            Event::KeyPressed(keys::constants::l)      => model.adjust_maneuver(0., 1.),
+            Event::KeyPressed(keys::constants::k)      => model.adjust_maneuver(0., -1.),
+            Event::KeyPressed(keys::constants::braceleft)  => model.adjust_transfer_target(1. / TRANSFER_TARGET_STEP),
+            Event::KeyPressed(keys::constants::braceright) => model.adjust_transfer_target(TRANSFER_TARGET_STEP),
+            Event::KeyPressed(keys::constants::Left) | Event::KeyPressed(keys::constants::a) if model.piloting() => model.steer_ship(-ENGINE_ROTATION_STEP),
+            Event::KeyPressed(keys::constants::Right) | Event::KeyPressed(keys::constants::d) if model.piloting() => model.steer_ship(ENGINE_ROTATION_STEP),
+            Event::KeyPressed(keys::constants::Up) | Event::KeyPressed(keys::constants::w) if model.piloting() => model.throttle_ship(THROTTLE_STEP),
+            Event::KeyPressed(keys::constants::Down) | Event::KeyPressed(keys::constants::s) if model.piloting() => model.throttle_ship(-THROTTLE_STEP),
+            Event::KeyPressed(keys::constants::Left)   => model.scroll(SCROLL_STEP, 0.),
+            Event::KeyPressed(keys::constants::Right)  => model.scroll(-SCROLL_STEP, 0.),
+            Event::KeyPressed(keys::constants::Up)     => model.scroll(0., SCROLL_STEP),
+            Event::KeyPressed(keys::constants::Down)   => model.scroll(0., -SCROLL_STEP),
+            Event::KeyPressed(keys::constants::Delete) => if let Some(tracked) = model.tracked_body { model.delete_body(tracked) },
+            Event::KeyPressed(keys::constants::Insert) => if let Some(tracked) = model.tracked_body { model.duplicate_body(tracked) },
             Event::KeyPressed(keys::constants::Tab)    => model.track_next(),
-            Event::Scrolling(ScrollDirection::Down)    => model.zoom_out(),
-            Event::Scrolling(ScrollDirection::Up)      => model.zoom_in(),
-            Event::MousePressed(coordinate)            => model.drag_started(coordinate),
+            Event::KeyPressed(keys::constants::ISO_Left_Tab) => model.track_previous(),
+            Event::KeyPressed(keys::constants::Page_Up)   => model.tilt_camera(TILT_STEP),
+            Event::KeyPressed(keys::constants::Page_Down) => model.tilt_camera(-TILT_STEP),
+            Event::KeyPressed(keys::constants::y)      => model.adjust_trail_length(false),
+            Event::KeyPressed(keys::constants::Y)      => model.adjust_trail_length(true),
+            Event::KeyPressed(keys::constants::o)      => model.adjust_trail_spacing(false),
+            Event::KeyPressed(keys::constants::O)      => model.adjust_trail_spacing(true),
+            Event::KeyPressed(keys::constants::bracketleft)  => model.adjust_time_scale(false),
+            Event::KeyPressed(keys::constants::bracketright) => model.adjust_time_scale(true),
+            Event::KeyPressed(keys::constants::i)      => model.switch_integrator(),
+            Event::KeyPressed(keys::constants::BackSpace) => model.toggle_time_reversal(),
+            Event::KeyPressed(keys::constants::comma)  => model.rewind(),
+            Event::KeyPressed(keys::constants::period) => model.replay(),
+            Event::KeyPressed(keys::constants::t)      => model.switch_solver(),
+            Event::KeyPressed(keys::constants::parenleft)  => model.adjust_opening_angle(-OPENING_ANGLE_STEP),
+            Event::KeyPressed(keys::constants::parenright) => model.adjust_opening_angle(OPENING_ANGLE_STEP),
+            Event::KeyPressed(keys::constants::c)      => model.switch_collision_mode(),
+            Event::KeyPressed(keys::constants::semicolon)  => model.adjust_restitution(-RESTITUTION_STEP),
+            Event::KeyPressed(keys::constants::apostrophe) => model.adjust_restitution(RESTITUTION_STEP),
+            Event::KeyPressed(keys::constants::g)      => model.adjust_softening(-SOFTENING_STEP),
+            Event::KeyPressed(keys::constants::G)      => model.adjust_softening(SOFTENING_STEP),
+            Event::KeyPressed(keys::constants::r)      => model.toggle_relativity(),
+            Event::KeyPressed(keys::constants::R)      => model.toggle_tidal_disruption(),
+            Event::KeyPressed(keys::constants::T)      => model.toggle_tidal_locking(),
+            Event::KeyPressed(keys::constants::z)      => model.rotate_engine(-ENGINE_ROTATION_STEP),
+            Event::KeyPressed(keys::constants::x)      => model.rotate_engine(ENGINE_ROTATION_STEP),
+            Event::KeyPressed(keys::constants::Return) => model.throttle_engine(1.),
+            Event::KeyReleased(keys::constants::Return) => model.throttle_engine(0.),
+            Event::Scrolling(ScrollDirection::Down, _) if model.placing.is_some() => model.adjust_placed_mass(1. / PLACED_MASS_STEP),
+            Event::Scrolling(ScrollDirection::Up, _) if model.placing.is_some()   => model.adjust_placed_mass(PLACED_MASS_STEP),
+            Event::Scrolling(ScrollDirection::Down, cursor) => model.zoom_at(cursor, false),
+            Event::Scrolling(ScrollDirection::Up, cursor)   => model.zoom_at(cursor, true),
+            Event::MousePressed(coordinate)            => model.pressed_at(coordinate),
+            Event::MouseControlPressed(coordinate)     => model.start_placing(coordinate),
+            Event::MouseReleased                       => model.finish_placing(),
+            Event::MouseRightPressed(coordinate)       => if let Some(body) = model.body_at(coordinate) { show_body_menu(&model, body, &event_sender) },
             Event::MouseDragged(coordinate)            => model.dragging_to(coordinate),
             _ => (),
         };
@@ -344,22 +2375,307 @@ fn build_ui(application: &gtk::Application, model: Rc<RefCell<Situation>>) {
     });
 }
 
-fn build_situation() -> Situation {
-    Situation::new().with(
-        Body::new().with_mass(70.).at(Coordinate{x: 0., y: 0.}).moving(EuclideanVector{dx: 0., dy: 0.}).named("Imagirus*")
-    ).with(
-        Body::new().with_mass(1.).at(Coordinate{x: 150., y: 0.}).moving(EuclideanVector{dx: 0., dy: 2.}).named("Imagirus I")
-    ).with(
-        Body::new().with_mass(1.).at(Coordinate{x: -400., y: 0.}).moving(EuclideanVector{dx: 0., dy: 1.}).named("Imagirus II")
-    ).with(
-        Body::new().with_mass(0.1).at(Coordinate{x: 0., y: -300.}).moving(EuclideanVector{dx: 0.9, dy: 0.}).named("Feather")
-    )
+// Rows of the name, mass and speed of each body, and of whether it leaves a trail and is labelled, in that order.
+// Clicking a row tracks its body, and the check boxes toggle its trail and label.
+fn build_body_list(event_sender: &glib::Sender<Event>) -> (gtk::ScrolledWindow, gtk::ListStore) {
+    let store = gtk::ListStore::new(&[glib::Type::String, glib::Type::String, glib::Type::String, glib::Type::Bool, glib::Type::Bool]);
+    let tree_view = gtk::TreeView::with_model(&store);
+    tree_view.set_activate_on_single_click(true);
+    tree_view.set_can_focus(false); // leaving the keys to the window
+    with_clone_of!(event_sender, tree_view.connect_row_activated(move |_, path, _| {
+        event_sender.send(Event::Track(row_of(path))).expect("Failed to raise Track event");
+    }));
+
+    for (column, title) in IntoIterator::into_iter([(0, "name"), (1, "mass"), (2, "speed")]) {
+        let renderer = gtk::CellRendererText::new();
+        tree_view.append_column(&list_column(title, &renderer, "text", column));
+    }
+    for (column, title, event) in IntoIterator::into_iter([(3, "trail", Event::ToggleTrail as fn(usize) -> Event), (4, "label", Event::ToggleLabel)]) {
+        let renderer = gtk::CellRendererToggle::new();
+        with_clone_of!(event_sender, renderer.connect_toggled(move |_, path| {
+            event_sender.send(event(row_of(&path))).expect("Failed to raise toggling event");
+        }));
+        tree_view.append_column(&list_column(title, &renderer, "active", column));
+    }
+
+    let scrolled_window = gtk::ScrolledWindow::new(None::<&gtk::Adjustment>, None::<&gtk::Adjustment>);
+    scrolled_window.set_size_request(BODY_LIST_WIDTH, -1);
+    scrolled_window.add(&tree_view);
+    (scrolled_window, store)
+}
+
+fn list_column<P: IsA<gtk::CellRenderer>>(title: &str, renderer: &P, attribute: &str, column: i32) -> gtk::TreeViewColumn {
+    let tree_view_column = gtk::TreeViewColumn::new();
+    tree_view_column.set_title(title);
+    tree_view_column.pack_start(renderer, true);
+    tree_view_column.add_attribute(renderer, attribute, column);
+    tree_view_column
+}
+
+fn row_of(path: &gtk::TreePath) -> usize {
+    path.get_indices().first().map_or(0, |&row| row as usize)
+}
+
+// row by row, to keep the selection and the scrolling, unless bodies were added or removed
+fn refresh_body_list(store: &gtk::ListStore, situation: &Situation) {
+    if store.iter_n_children(None) as usize != situation.bodies.len() {
+        store.clear();
+        for _ in &situation.bodies { store.append(); }
+    }
+    let mut row = store.get_iter_first();
+    for body in &situation.bodies {
+        let iter = match row { Some(iter) => iter, None => return };
+        store.set(&iter, &[0, 1, 2, 3, 4], &[
+            &body.name,
+            &format!("{:.4e}", body.mass),
+            &format!("{:.4e}", body.velocity.magnitude()),
+            &!situation.hidden_trails.contains(&body.id),
+            &!situation.hidden_labels.contains(&body.id),
+        ]);
+        row = if store.iter_next(&iter) { Some(iter) } else { None };
+    }
+}
+
+// of what can be done with the body, popped up under the cursor
+fn show_body_menu(situation: &Situation, body: usize, event_sender: &glib::Sender<Event>) {
+    let menu = gtk::Menu::new();
+    let title = gtk::MenuItem::with_label(&situation.bodies[body].name);
+    title.set_sensitive(false);
+    menu.append(&title);
+    let actions = [("Track", Event::Track(body)), ("Center", Event::Center(body)), ("Edit", Event::OpenEditor(body)), ("Duplicate", Event::Duplicate(body)), ("Delete", Event::Delete(body))];
+    for (label, event) in IntoIterator::into_iter(actions) {
+        let item = gtk::MenuItem::with_label(label);
+        with_clone_of!(event_sender, item.connect_activate(move |_| {
+            event_sender.send(event.clone()).expect("Failed to raise body menu event");
+        }));
+        menu.append(&item);
+    }
+    menu.show_all();
+    menu.popup_easy(3, gtk::get_current_event_time());
+}
+
+// Lets the user type in the properties of the body, with the simulation paused meanwhile and left as it was once
+// done. They are applied only once all of them are numbers where they need to be, the first one that is not named.
+// Modal, so that the bodies stay as they are until then, and one at a time.
+fn show_body_editor(window: &gtk::ApplicationWindow, situation: &mut Situation, body: usize, event_sender: &glib::Sender<Event>) {
+    if !situation.start_editing() { return; }
+    let (id, properties) = (situation.bodies[body].id, Properties::of(&situation.bodies[body]));
+    let dialog = gtk::Dialog::with_buttons(Some(&format!("Edit {}", properties.name)), Some(window), gtk::DialogFlags::MODAL | gtk::DialogFlags::DESTROY_WITH_PARENT,
+        &[("Cancel", gtk::ResponseType::Cancel), ("Apply", gtk::ResponseType::Apply)]);
+
+    let grid = gtk::Grid::new();
+    grid.set_column_spacing(10);
+    grid.set_row_spacing(5);
+    grid.set_border_width(10);
+    let fields: Vec<(&str, gtk::Entry)> = IntoIterator::into_iter([
+        ("name", properties.name.clone()),
+        ("mass", properties.mass.to_string()),
+        ("position x", properties.position.x.to_string()),
+        ("position y", properties.position.y.to_string()),
+        ("position z", properties.position.z.to_string()),
+        ("velocity x", properties.velocity.dx.to_string()),
+        ("velocity y", properties.velocity.dy.to_string()),
+        ("velocity z", properties.velocity.dz.to_string()),
+    ]).enumerate().map(|(row, (label, value))| {
+        let entry = gtk::Entry::new();
+        entry.set_text(&value);
+        grid.attach(&gtk::Label::new(Some(label)), 0, row as i32, 1, 1);
+        grid.attach(&entry, 1, row as i32, 1, 1);
+        (label, entry)
+    }).collect();
+    let error = gtk::Label::new(None); // naming the field that could not be parsed
+    grid.attach(&error, 0, fields.len() as i32, 2, 1);
+    dialog.get_content_area().add(&grid);
+
+    with_clone_of!(event_sender, dialog.connect_response(move |dialog, response| {
+        if response == gtk::ResponseType::Apply {
+            let mut numbers = Vec::new();
+            for (label, field) in &fields[1..] {
+                match field.get_text().trim().parse::<Real>() {
+                    Ok(number) => numbers.push(number),
+                    Err(_) => {
+                        error.set_text(&format!("Invalid {}: {}", label, field.get_text()));
+                        field.grab_focus();
+                        return;
+                    }
+                }
+            }
+            let properties = Properties {
+                name: fields[0].1.get_text().to_string(),
+                mass: numbers[0],
+                position: Coordinate { x: numbers[1], y: numbers[2], z: numbers[3] },
+                velocity: EuclideanVector { dx: numbers[4], dy: numbers[5], dz: numbers[6] },
+            };
+            event_sender.send(Event::Edit(id, properties)).expect("Failed to raise Edit event");
+        }
+        event_sender.send(Event::EditorClosed).expect("Failed to raise EditorClosed event");
+        dialog.close();
+    }));
+    dialog.show_all();
+}
+
+// of what to follow, popped up under the cursor
+fn show_camera_menu(situation: &Situation, event_sender: &glib::Sender<Event>) {
+    let menu = gtk::Menu::new();
+    let add = |label: &str, following: Option<Following>| {
+        let item = gtk::MenuItem::with_label(label);
+        with_clone_of!(event_sender, item.connect_activate(move |_| {
+            event_sender.send(Event::Follow(following)).expect("Failed to raise Follow event");
+        }));
+        menu.append(&item);
+    };
+    add("Free camera", None);
+    add("Barycenter of all bodies", Some(Following::Barycenter));
+    if let Some(tracked) = situation.tracked_body {
+        let others = physics::heaviest_first(&situation.bodies).into_iter().filter(|&other| other != tracked);
+        for other in others.take(CAMERA_MENU_BODIES) {
+            let label = format!("Midpoint of {} and {}", situation.bodies[tracked].name, situation.bodies[other].name);
+            add(&label, Some(Following::Midpoint(tracked, other)));
+        }
+    }
+    menu.show_all();
+    menu.popup_easy(0, gtk::get_current_event_time());
+}
+
+struct Options<'a> {
+    scenario: &'a str,
+    log_flybys: bool,
+    solver: Option<&'a str>,
+    opening_angle: Option<Real>, // of the Barnes-Hut solver
+    gravitational_constant: Option<Real>,
+    density: Option<Real>,
+    imports: Vec<(&'a str, Real)>, // paths of JPL Horizons exports, with masses of the bodies
+    satellites: Vec<&'a str>, // paths of two-line element sets, of satellites to put around the Earth
+    rings: Vec<Ring>, // of particles to put around the most massive body
+    epoch: Option<Real>, // of the imported state vectors, as a Julian day number
+    seed: Option<u64>, // of a generated planetary system, to show instead of the scenario
+    load: Option<&'a str>, // path of a saved situation, to show instead of any of the above
+    record_to: Option<&'a str>, // directory to record frames to
+    record_every: u64, // frame shown that is recorded
+    ramp: Option<&'a str>, // of colors that bodies are colored with by their properties
+    quality: Option<&'a str>, // of rendering
+}
+
+impl Options<'_> {
+    // the ones given on the command line take precedence over those of the scenario
+    fn constants(&self, mut constants: Constants) -> Constants {
+        if let Some(gravitational) = self.gravitational_constant { constants.gravitational = gravitational; }
+        if let Some(density) = self.density { constants.density = density; }
+        constants
+    }
+}
+
+// takes out the options of the simulation, leaving the rest to GTK
+fn parse_options(arguments: &[String]) -> (Options<'_>, Vec<String>) {
+    let mut options = Options { scenario: scenarios::DEFAULT, log_flybys: false, solver: None, opening_angle: None, gravitational_constant: None, density: None, imports: Vec::new(), satellites: Vec::new(), rings: Vec::new(), epoch: None, seed: None, load: None, record_to: None, record_every: 1, ramp: None, quality: None };
+    let mut remaining = Vec::new();
+
+    for argument in arguments {
+        let value = |prefix: &str| argument.strip_prefix(prefix).map(|value| value.parse::<Real>().unwrap_or_else(|_| panic!("Invalid value of {}", argument)));
+        if let Some(scenario) = argument.strip_prefix("--scenario=") {
+            options.scenario = scenario;
+        } else if argument == "--log-flybys" {
+            options.log_flybys = true;
+        } else if let Some(solver) = argument.strip_prefix("--solver=") {
+            options.solver = Some(solver);
+        } else if let Some(opening_angle) = value("--opening-angle=") {
+            options.opening_angle = Some(opening_angle);
+        } else if let Some(gravitational) = value("--gravitational-constant=") {
+            options.gravitational_constant = Some(gravitational);
+        } else if let Some(density) = value("--density=") {
+            options.density = Some(density);
+        } else if let Some(epoch) = value("--epoch=") {
+            options.epoch = Some(epoch);
+        } else if argument == "--seed" {
+            options.seed = Some(Utc::now().timestamp_nanos() as u64);
+        } else if let Some(seed) = argument.strip_prefix("--seed=") {
+            options.seed = Some(seed.parse().unwrap_or_else(|_| panic!("Invalid value of {}", argument)));
+        } else if let Some(path) = argument.strip_prefix("--load=") {
+            options.load = Some(path);
+        } else if let Some(directory) = argument.strip_prefix("--record-to=") {
+            options.record_to = Some(directory);
+        } else if let Some(every) = argument.strip_prefix("--record-every=") {
+            options.record_every = every.parse().unwrap_or_else(|_| panic!("Invalid value of {}", argument));
+        } else if let Some(quality) = argument.strip_prefix("--quality=") {
+            options.quality = Some(quality);
+        } else if let Some(ramp) = argument.strip_prefix("--color-ramp=") {
+            options.ramp = Some(ramp);
+        } else if let Some(ring) = argument.strip_prefix("--ring=") {
+            options.rings.push(parse_ring(ring).unwrap_or_else(|| panic!("Expected --ring=<inner radius>:<outer radius>:<count>[:<dispersion>], got {}", argument)));
+        } else if let Some(path) = argument.strip_prefix("--tle=") {
+            options.satellites.push(path);
+        } else if let Some(import) = argument.strip_prefix("--horizons=") {
+            let (path, mass) = import.rsplit_once(':').unwrap_or_else(|| panic!("Expected --horizons=<path>:<mass>, got {}", argument));
+            options.imports.push((path, mass.parse().unwrap_or_else(|_| panic!("Invalid mass in {}", argument))));
+        } else {
+            remaining.push(argument.clone());
+        }
+    }
+
+    (options, remaining)
+}
+
+fn parse_ring(text: &str) -> Option<Ring> {
+    let fields: Vec<&str> = text.split(':').collect();
+    if fields.len() < 3 || fields.len() > 4 { return None; }
+    Some(Ring {
+        inner_radius: fields[0].parse().ok()?,
+        outer_radius: fields[1].parse().ok()?,
+        count: fields[2].parse().ok()?,
+        dispersion: fields.get(3).map_or(Some(0.), |dispersion| dispersion.parse().ok())?,
+    })
 }
 
 fn main() {
     let application = gtk::Application::new(Some("com.rs-kepler"), gio::ApplicationFlags::default())
         .expect("Failed to initialize GTK application");
 
-    application.connect_activate(move |app| { build_ui(app, Rc::new(RefCell::new(build_situation()))); });
-    application.run(&args().collect::<Vec<_>>());
+    let arguments: Vec<String> = args().collect();
+    let (options, gtk_arguments) = parse_options(&arguments);
+    let mut situation = match (options.load, options.seed) {
+        (Some(path), _) => {
+            let text = std::fs::read_to_string(path).unwrap_or_else(|error| panic!("Failed to read {}: {}", path, error));
+            Situation::loaded(&text).unwrap_or_else(|error| panic!("Failed to load {}: {}", path, error))
+        }
+        (None, Some(seed)) => scenarios::generated(seed, options.constants(Constants::new())),
+        (None, None) => scenarios::build(options.scenario, |constants| options.constants(constants))
+            .unwrap_or_else(|| panic!("Unknown scenario {}, available are: {}", options.scenario, scenarios::NAMES.join(", "))),
+    };
+    if let Some(solver) = options.solver {
+        situation.solver = SOLVERS.iter().position(|&name| name == solver)
+            .unwrap_or_else(|| panic!("Unknown solver {}, available are: {}", solver, SOLVERS.join(", ")));
+    }
+    if let Some(opening_angle) = options.opening_angle {
+        situation.set_opening_angle(opening_angle);
+    }
+    if let Some(ramp) = options.ramp {
+        situation.ramp = RAMPS.iter().position(|&(name, _)| name == ramp)
+            .unwrap_or_else(|| panic!("Unknown color ramp {}, available are: {}", ramp, RAMPS.iter().map(|&(name, _)| name).collect::<Vec<_>>().join(", ")));
+    }
+    if let Some(quality) = options.quality {
+        situation.quality = Quality::named(quality).unwrap_or_else(|| panic!("Unknown quality {}, available are: low, medium, high", quality));
+    }
+    situation.log_flybys = options.log_flybys;
+    situation.record_to = options.record_to.map(str::to_string);
+    situation.record_every = options.record_every;
+    for &(path, mass) in &options.imports {
+        let export = std::fs::read_to_string(path).unwrap_or_else(|error| panic!("Failed to read {}: {}", path, error));
+        situation.add(horizons::body_from(&export, mass, options.epoch).unwrap_or_else(|error| panic!("Failed to import {}: {}", path, error)));
+    }
+    for &path in &options.satellites {
+        let earth = situation.bodies.iter().find(|body| body.name == "Earth").cloned().expect("Satellites need a scenario with the Earth");
+        let gravitational_parameter = situation.constants.gravitational * earth.mass;
+        let text = std::fs::read_to_string(path).unwrap_or_else(|error| panic!("Failed to read {}: {}", path, error));
+        for elements in tle::parse(&text).unwrap_or_else(|error| panic!("Failed to parse {}: {}", path, error)) {
+            situation.add(elements.body_around(&earth, gravitational_parameter));
+        }
+    }
+    let mut random = Random::seeded(RING_SEED);
+    for ring in &options.rings {
+        let center = orbit::most_massive(&situation.bodies).map(|index| situation.bodies[index].clone()).expect("Rings need a body to go around");
+        for particle in ring.around(&center, &situation.constants, &mut random) { situation.add_particle(particle); }
+    }
+    let situation = Rc::new(RefCell::new(situation));
+    application.connect_activate(move |app| { build_ui(app, situation.clone()); });
+    application.run(&gtk_arguments);
 }
@@ -1,39 +1,229 @@
-use derive_more::{Add, AddAssign, Div, Mul, Sub};
+use derive_more::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 
-#[derive(Copy, Clone)]
+// precision of all the simulated quantities, double unless built with the single-precision feature
+#[cfg(not(feature = "single-precision"))]
+mod precision {
+    pub use std::f64::consts;
+    pub type Real = f64;
+
+    // for handing values over to the drawing, always done in doubles
+    pub const fn widened(value: Real) -> f64 {
+        value
+    }
+
+    // for handing values over to the compute shader, always done in singles
+    #[cfg(feature = "gpu")]
+    pub fn narrowed(value: Real) -> f32 {
+        value as f32
+    }
+}
+#[cfg(feature = "single-precision")]
+mod precision {
+    pub use std::f32::consts;
+    pub type Real = f32;
+
+    pub fn widened(value: Real) -> f64 {
+        f64::from(value)
+    }
+
+    #[cfg(feature = "gpu")]
+    pub const fn narrowed(value: Real) -> f32 {
+        value
+    }
+}
+pub use precision::{consts, widened, Real};
+#[cfg(feature = "gpu")]
+pub use precision::narrowed;
+
+// The plane of the simulation is the one of the x and y axes, and the z axis completes them to the right, so with the
+// y axis pointing down the screen it points away from the camera; counter-clockwise is from the x axis towards the y
+// one, about the z axis.
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Coordinate {
-    pub x: f64,
-    pub y: f64,
+    pub x: Real,
+    pub y: Real,
+    pub z: Real,
 }
 
+serializable!(Coordinate { x, y, z } or Coordinate { x: 0., y: 0., z: 0. });
+
 impl Coordinate {
-    pub const fn from(tuple: (f64, f64)) -> Self {
-        Self { x: tuple.0, y: tuple.1 }
+    // at the given distance from the origin, counter-clockwise from the x axis, in the plane of the simulation
+    pub fn from_polar(distance: Real, angle: Real) -> Self {
+        let offset = EuclideanVector::from_polar(distance, angle);
+        Self { x: offset.dx, y: offset.dy, z: 0. }
+    }
+
+    // no further apart than epsilon
+    pub fn approx_eq(&self, other: Self, epsilon: Real) -> bool {
+        (*self - other).magnitude() <= epsilon
+    }
+
+    // the given fraction of the way towards the other one
+    pub fn lerp(&self, other: Self, fraction: Real) -> Self {
+        *self + (other - *self) * fraction
     }
 }
 
-#[derive(Copy, Clone, AddAssign, Debug, Div, Mul, Add, Sub)]
+#[derive(Copy, Clone, AddAssign, SubAssign, Debug, Div, Mul, Add, Sub)]
 pub struct EuclideanVector {
-    pub dx: f64,
-    pub dy: f64,
+    pub dx: Real,
+    pub dy: Real,
+    pub dz: Real,
 }
 
+serializable!(EuclideanVector { dx, dy, dz } or EuclideanVector { dx: 0., dy: 0., dz: 0. });
+
 impl EuclideanVector {
+    pub const UNIT_X: Self = Self { dx: 1., dy: 0., dz: 0. };
+    pub const UNIT_Z: Self = Self { dx: 0., dy: 0., dz: 1. }; // perpendicular to the plane of the simulation
+
+    // of the given length, counter-clockwise from the x axis, in the plane of the simulation
+    pub fn from_polar(magnitude: Real, angle: Real) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self { dx: magnitude * cos, dy: magnitude * sin, dz: 0. }
+    }
+
     pub fn between(from: Coordinate, to: Coordinate) -> Self {
-        Self { dx: to.x - from.x, dy: to.y - from.y }
+        Self { dx: to.x - from.x, dy: to.y - from.y, dz: to.z - from.z }
     }
 
-    pub fn magnitude(&self) -> f64 {
-        self.dx.hypot(self.dy)
+    // exactly the same as in two dimensions for vectors in the plane
+    pub fn magnitude(&self) -> Real {
+        self.dx.hypot(self.dy).hypot(self.dz)
     }
 
     pub fn versor(&self) -> Self {
         let len = self.magnitude();
-        Self { dx: self.dx / len, dy: self.dy / len }
+        Self { dx: self.dx / len, dy: self.dy / len, dz: self.dz / len }
     }
 
     pub fn towards(to: Coordinate) -> EuclideanVector {
-        Self { dx: to.x, dy: to.y }
+        Self { dx: to.x, dy: to.y, dz: to.z }
+    }
+
+    // differing by no more than epsilon in length
+    pub fn approx_eq(&self, other: Self, epsilon: Real) -> bool {
+        (*self - other).magnitude() <= epsilon
+    }
+
+    pub fn dot(&self, other: Self) -> Real {
+        self.dx * other.dx + self.dy * other.dy + self.dz * other.dz
+    }
+
+    // of vectors in the plane of the simulation, it points along the z axis, positively when the other one is
+    // counter-clockwise of this one
+    pub fn cross(&self, other: Self) -> Self {
+        Self {
+            dx: self.dy * other.dz - self.dz * other.dy,
+            dy: self.dz * other.dx - self.dx * other.dz,
+            dz: self.dx * other.dy - self.dy * other.dx,
+        }
+    }
+
+    // counter-clockwise from this vector to the other one, between -π and π, as both are seen along the z axis
+    pub fn angle_to(&self, other: Self) -> Real {
+        self.cross(other).dz.atan2(self.dx * other.dx + self.dy * other.dy)
+    }
+
+    pub fn lerp(&self, other: Self, fraction: Real) -> Self {
+        *self + (other - *self) * fraction
+    }
+
+    // counter-clockwise about the z axis
+    pub fn rotated(&self, angle: Real) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self { dx: self.dx * cos - self.dy * sin, dy: self.dx * sin + self.dy * cos, dz: self.dz }
+    }
+
+    // counter-clockwise about the given axis of unit length, as seen looking down the axis towards the origin
+    pub fn rotated_about(&self, axis: Self, angle: Real) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        *self * cos + axis.cross(*self) * sin + axis * (axis.dot(*self) * (1. - cos))
+    }
+}
+
+// the given fraction of the way towards the other angle, turning the shorter way round; not wrapped into a single turn
+pub fn lerp_angle(from: Real, to: Real, fraction: Real) -> Real {
+    let difference = (to - from + consts::PI).rem_euclid(2. * consts::PI) - consts::PI;
+    from + difference * fraction
+}
+
+// Axis-aligned bounding box, the smallest one containing all the points it was grown from.
+#[derive(Copy, Clone, Debug)]
+pub struct Aabb {
+    pub min: Coordinate,
+    pub max: Coordinate,
+}
+
+impl Aabb {
+    pub const fn around(point: Coordinate) -> Self {
+        Self { min: point, max: point }
+    }
+
+    // none for no points at all
+    pub fn of(points: impl IntoIterator<Item = Coordinate>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::around(first), |bounds, point| bounds.grown_to(point)))
+    }
+
+    pub fn grown_to(&self, point: Coordinate) -> Self {
+        Self {
+            min: Coordinate { x: self.min.x.min(point.x), y: self.min.y.min(point.y), z: self.min.z.min(point.z) },
+            max: Coordinate { x: self.max.x.max(point.x), y: self.max.y.max(point.y), z: self.max.z.max(point.z) },
+        }
+    }
+
+    pub fn center(&self) -> Coordinate {
+        self.min.lerp(self.max, 0.5)
+    }
+
+    pub fn size(&self) -> EuclideanVector {
+        self.max - self.min
+    }
+}
+
+// Tilting about the horizontal axis and scaling, followed by a translation; maps the world onto the screen, or a part
+// of the way there, with the screen's y axis pointing downwards just like the world's. Tilting turns the upper part of
+// the plane of the simulation away from the camera, foreshortening it vertically and lifting whatever lies in front
+// of the plane up the screen.
+#[derive(Copy, Clone, Debug)]
+pub struct Transform {
+    pub scale: Real,
+    pub tilt: Real, // of the plane of the simulation away from the camera
+    pub translation: EuclideanVector,
+}
+
+impl Transform {
+    pub const fn scaling(scale: Real) -> Self {
+        Self { scale, tilt: 0., translation: EuclideanVector { dx: 0., dy: 0., dz: 0. } }
+    }
+
+    // of the given vector, before scaling
+    pub fn tilted(&self, vector: EuclideanVector) -> EuclideanVector {
+        let (sin, cos) = self.tilt.sin_cos();
+        EuclideanVector { dx: vector.dx, dy: vector.dy * cos + vector.dz * sin, dz: vector.dz * cos - vector.dy * sin }
+    }
+
+    pub fn world_to_screen(&self, world: Coordinate) -> Coordinate {
+        let screen = self.tilted(EuclideanVector::towards(world)) * self.scale + self.translation;
+        Coordinate { x: screen.dx, y: screen.dy, z: screen.dz }
+    }
+
+    // the point of the plane of the simulation seen at the given one of the screen, whatever its depth
+    pub fn screen_to_world(&self, screen: Coordinate) -> Coordinate {
+        Coordinate { x: (screen.x - self.translation.dx) / self.scale, y: (screen.y - self.translation.dy) / (self.scale * self.tilt.cos()), z: 0. }
+    }
+
+    // this one, and the outer one on top of it
+    pub fn then(&self, outer: &Self) -> Self {
+        let translation = outer.world_to_screen(Coordinate { x: self.translation.dx, y: self.translation.dy, z: self.translation.dz });
+        Self {
+            scale: self.scale * outer.scale,
+            tilt: self.tilt + outer.tilt,
+            translation: EuclideanVector::towards(translation),
+        }
     }
 }
 
@@ -41,13 +231,43 @@ impl std::ops::Neg for EuclideanVector {
     type Output = EuclideanVector;
 
     fn neg(self) -> Self {
-        Self { dx: -self.dx, dy: -self.dy }
+        Self { dx: -self.dx, dy: -self.dy, dz: -self.dz }
+    }
+}
+
+impl std::ops::Mul<EuclideanVector> for Real {
+    type Output = EuclideanVector;
+
+    fn mul(self, vector: EuclideanVector) -> EuclideanVector {
+        vector * self
+    }
+}
+
+impl std::ops::MulAssign<Real> for EuclideanVector {
+    fn mul_assign(&mut self, factor: Real) {
+        self.dx *= factor;
+        self.dy *= factor;
+        self.dz *= factor;
+    }
+}
+
+impl std::ops::DivAssign<Real> for EuclideanVector {
+    fn div_assign(&mut self, divisor: Real) {
+        self.dx /= divisor;
+        self.dy /= divisor;
+        self.dz /= divisor;
     }
 }
 
 impl std::fmt::Display for EuclideanVector {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
-        write!(f, "({:.4}, {:.4})", self.dx, self.dy)
+        write!(f, "({:.4}, {:.4}, {:.4})", self.dx, self.dy, self.dz)
+    }
+}
+
+impl std::fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "({:.4}, {:.4}, {:.4})", self.x, self.y, self.z)
     }
 }
 
@@ -55,6 +275,48 @@ impl std::ops::AddAssign<EuclideanVector> for Coordinate {
     fn add_assign(&mut self, delta: EuclideanVector) {
         self.x += delta.dx;
         self.y += delta.dy;
+        self.z += delta.dz;
+    }
+}
+
+impl std::ops::SubAssign<EuclideanVector> for Coordinate {
+    fn sub_assign(&mut self, delta: EuclideanVector) {
+        self.x -= delta.dx;
+        self.y -= delta.dy;
+        self.z -= delta.dz;
+    }
+}
+
+// scaling and mirroring about the origin
+impl std::ops::MulAssign<Real> for Coordinate {
+    fn mul_assign(&mut self, factor: Real) {
+        self.x *= factor;
+        self.y *= factor;
+        self.z *= factor;
+    }
+}
+
+impl std::ops::DivAssign<Real> for Coordinate {
+    fn div_assign(&mut self, divisor: Real) {
+        self.x /= divisor;
+        self.y /= divisor;
+        self.z /= divisor;
+    }
+}
+
+impl std::ops::Neg for Coordinate {
+    type Output = Coordinate;
+
+    fn neg(self) -> Self {
+        Self { x: -self.x, y: -self.y, z: -self.z }
+    }
+}
+
+impl std::ops::Add<EuclideanVector> for Coordinate {
+    type Output = Coordinate;
+
+    fn add(self, delta: EuclideanVector) -> Self {
+        Self { x: self.x + delta.dx, y: self.y + delta.dy, z: self.z + delta.dz }
     }
 }
 
@@ -62,18 +324,71 @@ impl std::ops::Sub for Coordinate {
     type Output = EuclideanVector;
 
     fn sub(self, other: Self) -> EuclideanVector {
-        EuclideanVector { dx: self.x - other.x, dy: self.y - other.y }
+        EuclideanVector { dx: self.x - other.x, dy: self.y - other.y, dz: self.z - other.z }
     }
 }
 
-impl std::cmp::PartialEq<f64> for EuclideanVector {
-    fn eq(&self, other: &f64) -> bool {
+impl std::cmp::PartialEq<Real> for EuclideanVector {
+    fn eq(&self, other: &Real) -> bool {
         self.magnitude() == *other
     }
 }
 
 impl std::cmp::PartialEq for EuclideanVector {
     fn eq(&self, other: &Self) -> bool {
-        self.dx == other.dx && self.dy == other.dy
+        self.dx == other.dx && self.dy == other.dy && self.dz == other.dz
+    }
+}
+
+// Kahan summation, carrying the low-order bits lost to rounding in one addition over into the next one
+#[derive(Copy, Clone, Debug)]
+pub struct CompensatedSum {
+    pub sum: EuclideanVector,
+    compensation: EuclideanVector,
+}
+
+impl CompensatedSum {
+    pub const fn new() -> Self {
+        Self { sum: EuclideanVector { dx: 0., dy: 0., dz: 0. }, compensation: EuclideanVector { dx: 0., dy: 0., dz: 0. } }
+    }
+
+    pub fn of(values: impl Iterator<Item = EuclideanVector>) -> EuclideanVector {
+        values.fold(Self::new(), |mut sum, value| { sum.add(value); sum }).sum
+    }
+
+    pub fn add(&mut self, value: EuclideanVector) {
+        let corrected = value - self.compensation;
+        let sum = self.sum + corrected;
+        self.compensation = (sum - self.sum) - corrected;
+        self.sum = sum;
+    }
+}
+
+// SplitMix64, chosen for being tiny and yielding the same sequence on every platform
+#[derive(Clone)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub const fn seeded(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // uniformly distributed in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    pub fn between(&mut self, low: Real, high: Real) -> Real {
+        low + (high - low) * self.next_f64() as Real
     }
 }
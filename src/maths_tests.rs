@@ -1,17 +1,20 @@
 #[cfg(test)]
 mod tests {
-    use crate::maths::EuclideanVector;
+    use crate::maths::{lerp_angle, Aabb, CompensatedSum, Coordinate, EuclideanVector, Random, Real, Transform};
+    use crate::maths::consts::{FRAC_PI_2, PI};
     type EV = EuclideanVector;
 
-    const VECTOR1: EV = EV { dx: 4.4, dy: 7.7 };
+    const VECTOR1: EV = EV { dx: 4.4, dy: 7.7, dz: 0. };
     const VECTOR_EQUAL_TO_VECTOR1: EV = VECTOR1;
     const VECTOR_WITH_DIFFERENT_DX: EV = EV {
         dx: VECTOR1.dx + 0.1,
         dy: VECTOR1.dy,
+        dz: 0.,
     };
     const VECTOR_WITH_DIFFERENT_DY: EV = EV {
         dx: VECTOR1.dx,
         dy: VECTOR1.dy + 0.1,
+        dz: 0.,
     };
 
     #[test]
@@ -34,8 +37,8 @@ mod tests {
         assert_ne!(VECTOR1, VECTOR_WITH_DIFFERENT_DY);
     }
 
-    const VECTOR_WITH_LENGTH_1: EV = EV { dx: 1., dy: 0. };
-    const VECTOR_WITH_LENGTH_5: EV = EV { dx: 4., dy: 3. };
+    const VECTOR_WITH_LENGTH_1: EV = EV { dx: 1., dy: 0., dz: 0. };
+    const VECTOR_WITH_LENGTH_5: EV = EV { dx: 4., dy: 3., dz: 0. };
 
     #[test]
     fn euclidean_vector_is_comparable_to_its_length() {
@@ -44,4 +47,173 @@ mod tests {
         assert_eq!(VECTOR_WITH_LENGTH_5, 5.);
         assert_ne!(VECTOR_WITH_LENGTH_5, 1.);
     }
+
+    #[test]
+    fn approximate_equality_tolerates_differences_up_to_epsilon() {
+        assert!(VECTOR1.approx_eq(VECTOR_WITH_DIFFERENT_DX, 0.11));
+        assert!(!VECTOR1.approx_eq(VECTOR_WITH_DIFFERENT_DX, 0.09));
+        assert!(Coordinate { x: 1., y: 1., z: 0. }.approx_eq(Coordinate { x: 1., y: 1.1, z: 0. }, 0.11));
+        assert!(!Coordinate { x: 1., y: 1., z: 0. }.approx_eq(Coordinate { x: 1., y: 1.1, z: 0. }, 0.09));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn dot_product_of_perpendicular_vectors_is_zero() {
+        assert_eq!(EV { dx: 3., dy: 4., dz: 0. }.dot(EV { dx: -4., dy: 3., dz: 0. }), 0.);
+        assert!((VECTOR1.dot(VECTOR1) - VECTOR1.magnitude().powi(2)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn cross_product_is_positive_counter_clockwise() {
+        let (east, north) = (EV { dx: 2., dy: 0., dz: 0. }, EV { dx: 0., dy: 3., dz: 0. });
+        assert_eq!(east.cross(north), EV { dx: 0., dy: 0., dz: 6. });
+        assert_eq!(north.cross(east), EV { dx: 0., dy: 0., dz: -6. });
+        assert_eq!(VECTOR1.cross(VECTOR1 * 2.).magnitude(), 0.);
+    }
+
+    #[test]
+    fn cross_product_is_perpendicular_to_both_vectors() {
+        let (first, second) = (EV { dx: 1., dy: 2., dz: 3. }, EV { dx: -2., dy: 0.5, dz: 4. });
+        let cross = first.cross(second);
+        assert_eq!(cross, EV { dx: 6.5, dy: -10., dz: 4.5 });
+        assert_eq!((cross.dot(first), cross.dot(second)), (0., 0.));
+    }
+
+    #[test]
+    fn angle_to_is_signed() {
+        let (east, north) = (EV { dx: 1., dy: 0., dz: 0. }, EV { dx: 0., dy: 5., dz: 0. });
+        assert!((east.angle_to(north) - FRAC_PI_2).abs() < 1e-12);
+        assert!((north.angle_to(east) + FRAC_PI_2).abs() < 1e-12);
+        assert!((east.angle_to(-east) - PI).abs() < 1e-12);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn polar_construction_starts_at_x_axis_and_turns_counter_clockwise() {
+        assert!(EV::from_polar(2., 0.).approx_eq(EV { dx: 2., dy: 0., dz: 0. }, 1e-12));
+        assert!(EV::from_polar(2., FRAC_PI_2).approx_eq(EV { dx: 0., dy: 2., dz: 0. }, 1e-12));
+        assert!(Coordinate::from_polar(3., PI).approx_eq(Coordinate { x: -3., y: 0., z: 0. }, 1e-12));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn rotation_keeps_magnitude_and_turns_by_angle() {
+        let rotated = VECTOR1.rotated(1.);
+        assert!((rotated.magnitude() - VECTOR1.magnitude()).abs() < 1e-12);
+        assert!((VECTOR1.angle_to(rotated) - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn rotation_about_an_axis_turns_counter_clockwise_around_it() {
+        let (x, y, z) = (EV { dx: 1., dy: 0., dz: 0. }, EV { dx: 0., dy: 1., dz: 0. }, EV::UNIT_Z);
+        assert!(x.rotated_about(z, FRAC_PI_2).approx_eq(y, 1e-12));
+        assert!(y.rotated_about(x, FRAC_PI_2).approx_eq(z, 1e-12));
+        assert!(VECTOR1.rotated_about(z, 1.).approx_eq(VECTOR1.rotated(1.), 1e-12));
+
+        let axis = EV { dx: 1., dy: 1., dz: 1. }.versor();
+        assert!(x.rotated_about(axis, 2. * PI / 3.).approx_eq(y, 1e-12));
+        assert!(axis.rotated_about(axis, 1.).approx_eq(axis, 1e-12));
+    }
+
+    #[test]
+    fn scalar_multiplies_vector_from_either_side() {
+        assert_eq!(0.5 * VECTOR1, VECTOR1 * 0.5);
+    }
+
+    #[test]
+    fn compound_assignments_match_plain_operators() {
+        let mut vector = VECTOR1;
+        vector -= VECTOR_WITH_DIFFERENT_DX;
+        vector *= 3.;
+        vector /= 2.;
+        assert_eq!(vector, (VECTOR1 - VECTOR_WITH_DIFFERENT_DX) * 3. / 2.);
+
+        let mut point = Coordinate { x: 1., y: -2., z: 0. };
+        point -= VECTOR1;
+        point *= 2.;
+        point /= 4.;
+        assert_eq!(-point - Coordinate { x: 0., y: 0., z: 0. }, -(Coordinate { x: 1., y: -2., z: 0. } - Coordinate { x: 0., y: 0., z: 0. } - VECTOR1) / 2.);
+    }
+
+    #[test]
+    fn interpolation_goes_the_given_fraction_of_the_way() {
+        assert_eq!(EV { dx: 0., dy: 2., dz: 0. }.lerp(EV { dx: 4., dy: -2., dz: 0. }, 0.25), EV { dx: 1., dy: 1., dz: 0. });
+        assert!(Coordinate { x: 1., y: 1., z: 0. }.lerp(Coordinate { x: 3., y: 5., z: 0. }, 0.5).approx_eq(Coordinate { x: 2., y: 3., z: 0. }, 1e-12));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn angles_are_interpolated_the_shorter_way_round() {
+        assert!((lerp_angle(0.5, 1.5, 0.5) - 1.).abs() < 1e-12);
+        assert!(lerp_angle(0.1, 2. * PI - 0.1, 0.5).abs() < 1e-12);
+        assert!((lerp_angle(PI - 0.1, -PI + 0.1, 0.5) - PI).abs() < 1e-12);
+    }
+
+    #[test]
+    fn bounding_box_contains_all_the_points() {
+        let points = [Coordinate { x: 1., y: -2., z: 0. }, Coordinate { x: -3., y: 4., z: 0. }, Coordinate { x: 0., y: 0., z: 0. }];
+        let bounds = Aabb::of(IntoIterator::into_iter(points)).unwrap();
+        assert!(bounds.min.approx_eq(Coordinate { x: -3., y: -2., z: 0. }, 0.));
+        assert!(bounds.max.approx_eq(Coordinate { x: 1., y: 4., z: 0. }, 0.));
+        assert!(bounds.center().approx_eq(Coordinate { x: -1., y: 1., z: 0. }, 1e-12));
+        assert!(Aabb::of(Vec::new()).is_none());
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn transform_maps_screen_back_onto_the_world() {
+        let transform = Transform { scale: 2., tilt: PI / 3., translation: EV { dx: 10., dy: -4., dz: 0. } };
+        let world = Coordinate { x: 3., y: 8., z: 0. };
+        assert!(transform.world_to_screen(world).approx_eq(Coordinate { x: 16., y: 4., z: -8. * Real::sqrt(3.) }, 1e-12));
+        assert!(transform.screen_to_world(transform.world_to_screen(world)).approx_eq(world, 1e-12));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn tilted_transform_lifts_what_lies_in_front_of_the_plane() {
+        let transform = Transform { scale: 2., tilt: PI / 6., translation: EV { dx: 10., dy: -4., dz: 0. } };
+        let (on_plane, in_front) = (Coordinate { x: 3., y: 8., z: 0. }, Coordinate { x: 3., y: 8., z: -6. });
+        let lifted = transform.world_to_screen(on_plane) - transform.world_to_screen(in_front);
+        assert_eq!(lifted.dx, 0.);
+        assert!((lifted.dy - 6.).abs() < 1e-12);
+        assert!(Transform::scaling(2.).world_to_screen(in_front).approx_eq(Coordinate { x: 6., y: 16., z: -12. }, 0.));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn composed_transform_applies_both_in_order() {
+        let inner = Transform { scale: 2., tilt: PI / 3., translation: EV { dx: 10., dy: -4., dz: 1. } };
+        let outer = Transform { scale: 3., tilt: 0.2, translation: EV { dx: -1., dy: 1., dz: 0. } };
+        let world = Coordinate { x: 3., y: 8., z: -5. };
+        assert!(inner.then(&outer).world_to_screen(world).approx_eq(outer.world_to_screen(inner.world_to_screen(world)), 1e-12));
+    }
+
+    #[test]
+    fn compensated_sum_keeps_digits_lost_by_naive_summation() {
+        let values = || std::iter::once(EV { dx: 1., dy: 0., dz: 0. }).chain(std::iter::repeat_n(EV { dx: 1e-16, dy: 0., dz: 0. }, 10_000));
+        let naive = values().fold(EV { dx: 0., dy: 0., dz: 0. }, |sum, value| sum + value);
+        assert_eq!(naive.dx, 1.);
+        assert!((CompensatedSum::of(values()).dx - (1. + 1e-12)).abs() < 1e-15);
+    }
+
+    #[test]
+    fn random_sequence_is_reproducible_from_seed() {
+        let (mut first, mut second) = (Random::seeded(42), Random::seeded(42));
+        for _ in 0..100 { assert_eq!(first.next_u64(), second.next_u64()); }
+    }
+
+    #[test]
+    fn random_sequences_differ_for_different_seeds() {
+        assert_ne!(Random::seeded(1).next_u64(), Random::seeded(2).next_u64());
+    }
+
+    #[test]
+    fn random_values_stay_within_requested_range() {
+        let mut random = Random::seeded(7);
+        for _ in 0..1000 {
+            let value = random.between(-2., 3.);
+            assert!((-2. ..3.).contains(&value));
+        }
+    }
 }
@@ -1,30 +1,159 @@
-use crate::maths::{Coordinate, EuclideanVector};
-use core::f64::consts::PI;
+use crate::maths::{consts::PI, CompensatedSum, Coordinate, EuclideanVector, Real};
+use quantities::{Length, Mass};
+use drag::{Atmosphere, Drag};
+use engine::{Engine, Thrust};
+use oblateness::Oblateness;
+use radiation::RadiationPressure;
+use relativity::Relativity;
 
-pub const GRAVITATIONAL_CONSTANT: f64 = 10.;
+pub mod binary;
+mod binary_tests;
+pub mod collision;
+mod collision_tests;
+pub mod conics;
+mod conics_tests;
+#[cfg(feature = "gpu")]
+pub mod compute;
+#[cfg(feature = "gpu")]
+mod compute_tests;
+pub mod conservation;
+mod conservation_tests;
+pub mod drag;
+mod drag_tests;
+pub mod engine;
+mod engine_tests;
+pub mod fmm;
+mod fmm_tests;
+pub mod flyby;
+mod flyby_tests;
+pub mod integration;
+mod integration_tests;
+pub mod kernel;
+mod kernel_tests;
+pub mod lagrange;
+mod lagrange_tests;
+pub mod maneuver;
+mod maneuver_tests;
+pub mod oblateness;
+mod oblateness_tests;
+pub mod orbit;
+mod orbit_tests;
+mod regression_tests;
+pub mod quantities;
+mod quantities_tests;
+pub mod radiation;
+mod radiation_tests;
+pub mod relativity;
+mod relativity_tests;
+pub mod swarm;
+mod swarm_tests;
+pub mod tidal;
+mod tidal_tests;
+pub mod transfer;
+mod transfer_tests;
+pub mod tree;
+mod tree_tests;
+pub mod units;
+mod units_tests;
 
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 256; // bodies, below which spreading the gathering across threads costs more than it saves
+
+// Constants of the simulated universe, along with the parameters of how gravity is evaluated in it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Constants {
+    pub gravitational: Real,
+    pub density: Real, // of all bodies, determining their radius
+    pub softening: Real, // keeps the pull finite when bodies (nearly) coincide, by using 1/(d² + ε²) instead of 1/d²
+    pub speed_of_light: Option<Real>, // relativistic corrections are applied only when set
+}
+
+serializable!(Constants { gravitational, density, softening, speed_of_light } or Constants::new());
+
+impl Constants {
+    pub const fn new() -> Self {
+        Self { gravitational: 10., density: 3., softening: 0., speed_of_light: None }
+    }
+
+    pub const fn si() -> Self {
+        Self { gravitational: units::GRAVITATIONAL_CONSTANT, density: units::EARTH_DENSITY, softening: 0., speed_of_light: None }
+    }
+
+    pub fn radius_of(&self, mass: Mass) -> Length {
+        radius_at(mass, self.density)
+    }
+}
+
+// of a uniform sphere
+pub fn radius_at(mass: Mass, density: Real) -> Length {
+    let volume = mass.0 / density;
+    Length(((3. / (4. * PI)) * volume).cbrt())
+}
+
+// Stays the same for the whole life of a body, through its clones in the history, snapshots and predictions, and
+// is never given to another one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BodyId(u64);
+
+impl BodyId {
+    fn next() -> Self {
+        static NEXT: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, std::sync::atomic::Ordering::Relaxed))
+    }
+}
+
+// Equal only to the same body, in exactly the same state.
+#[derive(Clone, Debug, PartialEq)]
 pub struct Body {
+    pub id: BodyId,
     pub name: String,
     pub position: Coordinate,
-    pub mass: f64,
-    pub radius: f64,
+    pub mass: Real,
+    pub radius: Real,
     pub velocity: EuclideanVector,
     pub forces: Vec<EuclideanVector>,
     pub highlighted: bool,
+    pub atmosphere: Option<Atmosphere>,
+    pub engine: Option<Engine>,
+    pub oblateness: Real, // J2 coefficient of the equatorial bulge
+    pub radiation: Real, // momentum radiated per unit of time, i.e. luminosity over the speed of light
+    pub area_to_mass: Real, // cross-section per unit of mass, exposed to the pressure of radiation
+    pub density: Option<Real>, // its own, determining its radius instead of the one of the constants
+    pub rotation: Real, // angle of the prime meridian, within a single turn
+    pub spin: Real, // angular velocity, positive counter-clockwise
+    pub color: Option<(f64, f64, f64)>, // red, green and blue in range 0..=1, to tell it apart from the others on screen
+    position_compensation: EuclideanVector, // rounding errors of past displacements, see `displace`
+    velocity_compensation: EuclideanVector,
 }
 
-impl Body {
-    const DENSITY: f64 = 3.;
+// forces are left out, being recomputed on every update anyway, and so are the compensations of rounding errors
+serializable!(Body {
+    name, mass, radius, density, oblateness, radiation, area_to_mass, rotation, spin, color,
+    position, velocity, atmosphere, engine,
+} or Body::new());
 
-    pub const fn new() -> Self {
+impl Body {
+    pub fn new() -> Self {
         Self {
+            id: BodyId::next(),
             name: String::new(),
-            position: Coordinate { x: 0., y: 0. },
+            position: Coordinate { x: 0., y: 0., z: 0. },
             mass: 0.,
             radius: 0.,
-            velocity: EuclideanVector { dx: 0., dy: 0. },
+            velocity: EuclideanVector { dx: 0., dy: 0., dz: 0. },
             forces: Vec::<EuclideanVector>::new(),
             highlighted: false,
+            atmosphere: None,
+            engine: None,
+            oblateness: 0.,
+            radiation: 0.,
+            area_to_mass: 0.,
+            density: None,
+            rotation: 0.,
+            spin: 0.,
+            color: None,
+            position_compensation: EuclideanVector { dx: 0., dy: 0., dz: 0. },
+            velocity_compensation: EuclideanVector { dx: 0., dy: 0., dz: 0. },
         }
     }
     pub const fn at(mut self, arg: Coordinate) -> Self {
@@ -39,36 +168,250 @@ impl Body {
         self.name = arg.to_string();
         self
     }
-    pub fn with_mass(mut self, arg: f64) -> Self {
+    // the same in every respect but its identity
+    pub fn duplicated(&self) -> Self {
+        Self { id: BodyId::next(), ..self.clone() }
+    }
+    // the radius follows from the body's own density if it has one, and from the default one otherwise
+    pub fn with_mass(mut self, arg: Real) -> Self {
         self.mass = arg;
-        let volume = self.mass / Self::DENSITY;
-        self.radius = ((3. / (4. * PI)) * volume).powf(0.33);
+        self.radius = radius_at(Mass(arg), self.density.unwrap_or(Constants::new().density)).0;
+        self
+    }
+    pub fn with_density(mut self, arg: Real) -> Self {
+        self.density = Some(arg);
+        self.radius = radius_at(Mass(self.mass), arg).0;
+        self
+    }
+    // the density follows from it, so that the radius is kept by the situation the body is added to
+    pub fn with_radius(mut self, arg: Real) -> Self {
+        self.radius = arg;
+        if self.mass > 0. && arg > 0. { self.density = Some(self.mass / (4. / 3. * PI * arg.powi(3))); }
+        self
+    }
+    pub fn with_atmosphere(mut self, radius: Real, density: Real) -> Self {
+        self.atmosphere = Some(Atmosphere { radius, density });
+        self
+    }
+    pub const fn with_oblateness(mut self, arg: Real) -> Self {
+        self.oblateness = arg;
+        self
+    }
+    pub const fn with_radiation(mut self, arg: Real) -> Self {
+        self.radiation = arg;
+        self
+    }
+    pub const fn with_sail(mut self, area_to_mass: Real) -> Self {
+        self.area_to_mass = area_to_mass;
+        self
+    }
+    pub const fn with_spin(mut self, arg: Real) -> Self {
+        self.spin = arg;
+        self
+    }
+    pub const fn with_color(mut self, red: f64, green: f64, blue: f64) -> Self {
+        self.color = Some((red, green, blue));
         self
     }
+    pub fn with_engine(mut self, thrust: Real, propellant: Real) -> Self {
+        self.engine = Some(Engine::new(thrust, propellant));
+        self
+    }
+
+    pub fn update(&mut self, time_step: Real) {
+        self.displace(self.velocity * time_step);
+
+        if self.forces.is_empty() { return; }
+        let force = CompensatedSum::of(self.forces.iter().copied());
+        self.accelerate(force / self.mass * time_step);
+    }
+
+    // Small steps added to large coordinates lose most of their digits to rounding, which adds up over
+    // millions of steps; Kahan summation keeps the lost part and adds it back with the next step.
+    pub fn displace(&mut self, displacement: EuclideanVector) {
+        let corrected = displacement - self.position_compensation;
+        let position = self.position + corrected;
+        self.position_compensation = (position - self.position) - corrected;
+        self.position = position;
+    }
+
+    pub fn accelerate(&mut self, change_of_velocity: EuclideanVector) {
+        let corrected = change_of_velocity - self.velocity_compensation;
+        let velocity = self.velocity + corrected;
+        self.velocity_compensation = (velocity - self.velocity) - corrected;
+        self.velocity = velocity;
+    }
+
+    pub fn turn(&mut self, time_step: Real) {
+        self.rotation = (self.rotation + self.spin * time_step).rem_euclid(2. * PI);
+    }
+
+    // of a uniform sphere
+    pub fn moment_of_inertia(&self) -> Real {
+        0.4 * self.mass * self.radius * self.radius
+    }
+
+    pub fn pull_from(&self, other: &Self, constants: &Constants) -> EuclideanVector {
+        gravitational_pull(self.position, other.position, self.mass * other.mass, constants)
+    }
+
+    pub fn momentum(&self) -> EuclideanVector {
+        self.velocity * self.mass
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        (other.position - self.position).magnitude() < self.radius + other.radius
+    }
+}
+
+pub fn gravitational_pull(from: Coordinate, to: Coordinate, masses_product: Real, constants: &Constants) -> EuclideanVector {
+    let joining_vector = EuclideanVector::between(from, to);
+    let distance = joining_vector.magnitude();
+    if distance == 0. { return EuclideanVector { dx: 0., dy: 0., dz: 0. }; }
+
+    let softening = constants.softening;
+    joining_vector.versor() * (masses_product / (distance * distance + softening * softening)) * constants.gravitational
+}
+
+// indices of the given bodies, in order of decreasing mass
+pub fn heaviest_first(bodies: &[Body]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..bodies.len()).collect();
+    order.sort_by(|&lhs, &rhs| bodies[rhs].mass.partial_cmp(&bodies[lhs].mass).unwrap_or(std::cmp::Ordering::Equal));
+    order
+}
+
+// mass-weighted center of all given bodies
+pub fn barycenter(bodies: &[Body]) -> Coordinate {
+    let total_mass: Real = bodies.iter().map(|body| body.mass).sum();
+    if total_mass == 0. { return Coordinate { x: 0., y: 0., z: 0. }; }
+
+    let weighted = bodies.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, body| sum + EuclideanVector::towards(body.position) * body.mass);
+    let center = weighted / total_mass;
+    Coordinate { x: center.dx, y: center.dy, z: center.dz }
+}
+
+// of the exact bits of the state of all bodies, to tell whether two runs of the simulation diverged
+pub fn checksum(bodies: &[Body]) -> u64 {
+    let values = bodies.iter().flat_map(|body| [body.position.x, body.position.y, body.position.z, body.velocity.dx, body.velocity.dy, body.velocity.dz, body.mass]);
+    fnv(values.flat_map(|value| value.to_bits().to_le_bytes()))
+}
+
+// FNV-1a, which unlike the hasher of the standard library is the same in every build and on every platform
+pub fn fnv(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.into_iter().fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(PRIME))
+}
+
+// Any force besides the Newtonian gravity of the force solver, acting on a body given all the other ones.
+// Bodies without mass feel none of them.
+pub trait ForceModel {
+    fn force_on(&self, body: &Body, others: &[Body], constants: &Constants) -> EuclideanVector;
+
+    // whether it may act on any of the bodies at all, so that it can be skipped altogether otherwise
+    fn acts_on(&self, _bodies: &[Body], _constants: &Constants) -> bool {
+        true
+    }
+}
+
+pub fn standard_models() -> Vec<Box<dyn ForceModel>> {
+    vec![Box::new(Drag), Box::new(Oblateness), Box::new(Thrust), Box::new(RadiationPressure), Box::new(Relativity)]
+}
+
+pub trait ForceSolver: std::fmt::Display {
+    fn accelerations(&self, bodies: &[Body], positions: &[Coordinate], constants: &Constants) -> Vec<EuclideanVector>;
+
+    // of the given bodies only, for integrators that do not advance all of them at once
+    fn accelerations_of(&self, bodies: &[Body], positions: &[Coordinate], indices: &[usize], constants: &Constants) -> Vec<EuclideanVector> {
+        let all = self.accelerations(bodies, positions, constants);
+        indices.iter().map(|&i| all[i]).collect()
+    }
 
-    pub fn update(&mut self) {
-        self.position += self.velocity;
+    fn refresh_forces(&self, bodies: &mut [Body], constants: &Constants) {
+        let positions: Vec<Coordinate> = bodies.iter().map(|body| body.position).collect();
+        let accelerations = self.accelerations(bodies, &positions, constants);
 
-        for force in &self.forces {
-            let acceleration = *force / self.mass;
-            self.velocity += acceleration; // * 1 unit of time
+        for (body, acceleration) in bodies.iter_mut().zip(accelerations) {
+            body.forces.clear();
+            body.forces.push(acceleration * body.mass);
         }
     }
+}
 
-    pub fn pull_from(&self, other: &Self) -> EuclideanVector {
-        let joining_vector = EuclideanVector::between(self.position, other.position);
-        let distance = joining_vector.magnitude();
+// Forces on a body are summed in the order of bodies, and bodies are always iterated over in the same order,
+// so that the simulation, given the same initial situation, follows exactly the same trajectory on every run.
+pub struct DirectSummation;
 
-        joining_vector.versor() * ((self.mass * other.mass) / (distance * distance)) * GRAVITATIONAL_CONSTANT
+impl ForceSolver for DirectSummation {
+    fn accelerations(&self, bodies: &[Body], positions: &[Coordinate], constants: &Constants) -> Vec<EuclideanVector> {
+        gathered(positions.len(), |i| acceleration_of(bodies, positions, i, constants))
     }
 
-    pub fn add_pull_from(&mut self, other: &Self) {
-        self.forces.push(self.pull_from(other));
+    fn accelerations_of(&self, bodies: &[Body], positions: &[Coordinate], indices: &[usize], constants: &Constants) -> Vec<EuclideanVector> {
+        indices.iter().map(|&i| acceleration_of(bodies, positions, i, constants)).collect()
+    }
+
+    // gathers the pulls on every body first, reading the bodies only, and applies them afterwards, so that
+    // the gathering phase, being a plain map over independent bodies, can be spread across threads
+    fn refresh_forces(&self, bodies: &mut [Body], constants: &Constants) {
+        let forces = gathered(bodies.len(), |i| pulls_on(bodies, i, constants));
+
+        for (body, forces) in bodies.iter_mut().zip(forces) {
+            body.forces = forces;
+        }
+    }
+}
+
+// of each of the bodies, in their order, spread across all the cores with the parallel feature
+#[cfg(feature = "parallel")]
+fn gathered<T: Send>(count: usize, gather: impl Fn(usize) -> T + Sync) -> Vec<T> {
+    let threads = std::thread::available_parallelism().map_or(1, usize::from);
+    if count < PARALLEL_THRESHOLD || threads == 1 { return (0..count).map(gather).collect(); }
+
+    let chunk = count.div_ceil(threads);
+    let gather = &gather;
+    std::thread::scope(|scope| {
+        let chunks: Vec<_> = (0..count).step_by(chunk)
+            .map(|start| scope.spawn(move || (start..count.min(start + chunk)).map(gather).collect::<Vec<T>>()))
+            .collect();
+        chunks.into_iter().flat_map(|chunk| chunk.join().expect("Gathering thread panicked")).collect()
+    })
+}
+
+#[cfg(not(feature = "parallel"))]
+fn gathered<T>(count: usize, gather: impl Fn(usize) -> T) -> Vec<T> {
+    (0..count).map(gather).collect()
+}
+
+fn acceleration_of(bodies: &[Body], positions: &[Coordinate], index: usize, constants: &Constants) -> EuclideanVector {
+    CompensatedSum::of(bodies.iter().enumerate()
+        .filter(|&(j, _)| j != index)
+        .map(|(j, other)| gravitational_pull(positions[index], positions[j], other.mass, constants)))
+}
+
+fn pulls_on(bodies: &[Body], index: usize, constants: &Constants) -> Vec<EuclideanVector> {
+    bodies.iter().enumerate()
+        .filter(|&(j, _)| j != index)
+        .map(|(_, other)| bodies[index].pull_from(other, constants))
+        .collect()
+}
+
+impl std::fmt::Display for DirectSummation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "direct")
+    }
+}
+
+impl std::fmt::Display for Body {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "{} (mass: {}) at {} moving {}", self.name, self.mass, self.position, self.velocity)
     }
 }
 
-impl std::cmp::PartialEq for Body {
-    fn eq(&self, other: &Self) -> bool {
-        self == other
+// by identity alone, which is consistent with equality as equal bodies are always the same one
+impl std::hash::Hash for Body {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
     }
 }
@@ -0,0 +1,37 @@
+use crate::maths::{EuclideanVector, Real};
+use crate::physics::{barycenter, Body, Constants};
+
+// The companion put at the periapsis of an orbit of the given semi-major axis and eccentricity around the whole
+// group, treated as a single body, and the group recoiling so that their common barycenter and momentum stay where
+// those of the group were. The orbit goes counter-clockwise, with its periapsis towards positive x.
+pub fn paired(group: Vec<Body>, companion: Body, semi_major_axis: Real, eccentricity: Real, constants: &Constants) -> Vec<Body> {
+    let group_mass: Real = group.iter().map(|body| body.mass).sum();
+    let total_mass = group_mass + companion.mass;
+    if total_mass <= 0. { return group.into_iter().chain(std::iter::once(companion)).collect(); }
+
+    let center = barycenter(&group);
+    let momentum = group.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, body| sum + body.momentum());
+    let velocity = if group_mass > 0. { momentum / group_mass } else { EuclideanVector { dx: 0., dy: 0., dz: 0. } };
+
+    let periapsis = semi_major_axis * (1. - eccentricity);
+    let speed = (constants.gravitational * total_mass * (1. + eccentricity) / periapsis).sqrt();
+    let (offset, relative_velocity) = (EuclideanVector { dx: periapsis, dy: 0., dz: 0. }, EuclideanVector { dx: 0., dy: speed, dz: 0. });
+
+    let (group_share, companion_share) = (companion.mass / total_mass, group_mass / total_mass);
+    let mut result: Vec<Body> = group.into_iter()
+        .map(|body| Body { position: body.position + -offset * group_share, velocity: body.velocity + -relative_velocity * group_share, ..body })
+        .collect();
+    result.push(Body { position: center + offset * companion_share, velocity: velocity + relative_velocity * companion_share, ..companion });
+    result
+}
+
+// both around their barycenter, which is where the first of them is
+pub fn binary(first: Body, second: Body, semi_major_axis: Real, eccentricity: Real, constants: &Constants) -> Vec<Body> {
+    paired(vec![first], second, semi_major_axis, eccentricity, constants)
+}
+
+// an inner binary, and a third body going around it on a wider orbit
+pub fn hierarchical_triple(inner: (Body, Body), inner_orbit: (Real, Real), outer: Body, outer_orbit: (Real, Real), constants: &Constants) -> Vec<Body> {
+    let binary = binary(inner.0, inner.1, inner_orbit.0, inner_orbit.1, constants);
+    paired(binary, outer, outer_orbit.0, outer_orbit.1, constants)
+}
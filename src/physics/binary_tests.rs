@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector};
+    use crate::physics::binary::{binary, hierarchical_triple, paired};
+    use crate::physics::orbit::Elements;
+    use crate::physics::{barycenter, Body, Constants};
+
+    fn momentum_of(bodies: &[Body]) -> EuclideanVector {
+        bodies.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, body| sum + body.momentum())
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn binary_goes_around_the_place_of_the_first_body_on_the_given_orbit() {
+        let first = Body::new().with_mass(60.).at(Coordinate { x: 10., y: -5., z: 0. }).moving(EuclideanVector { dx: 1., dy: 0., dz: 0. });
+        let pair = binary(first.clone(), Body::new().with_mass(40.), 80., 0.4, &Constants::new());
+        let elements = Elements::of(&pair[1], &pair[0], &Constants::new());
+        assert!((elements.semi_major_axis - 80.).abs() < 1e-9);
+        assert!((elements.eccentricity - 0.4).abs() < 1e-9);
+        assert!(barycenter(&pair).approx_eq(first.position, 1e-12));
+        assert!(momentum_of(&pair).approx_eq(EuclideanVector { dx: 100., dy: 0., dz: 0. }, 1e-12));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn companion_goes_around_the_whole_group() {
+        let pair = binary(Body::new().with_mass(30.), Body::new().with_mass(20.), 20., 0.1, &Constants::new());
+        let center = barycenter(&pair);
+        let bodies = paired(pair, Body::new().with_mass(40.), 300., 0.3, &Constants::new());
+        let group = Body::new().with_mass(50.).at(barycenter(&bodies[..2])).moving(momentum_of(&bodies[..2]) / 50.);
+        let elements = Elements::of(&bodies[2], &group, &Constants::new());
+        assert!((elements.semi_major_axis - 300.).abs() < 1e-9);
+        assert!((elements.eccentricity - 0.3).abs() < 1e-9);
+        assert!(barycenter(&bodies).approx_eq(center, 1e-12));
+    }
+
+    #[test]
+    fn inner_binary_of_a_triple_keeps_its_orbit() {
+        let bodies = hierarchical_triple((Body::new().with_mass(30.), Body::new().with_mass(20.)), (20., 0.1), Body::new().with_mass(40.), (300., 0.3), &Constants::new());
+        assert_eq!(bodies.len(), 3);
+        let elements = Elements::of(&bodies[1], &bodies[0], &Constants::new());
+        assert!((elements.semi_major_axis - 20.).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,283 @@
+use crate::maths::{consts::PI, EuclideanVector, Random, Real};
+use crate::physics::Body;
+
+const FRAGMENTATION_ENERGY: Real = 100.;
+const FRAGMENTS: usize = 5;
+const MIN_FRAGMENT_MASS: Real = 0.05;
+const FRAGMENTS_ENERGY_SHARE: Real = 0.5; // the rest is assumed to be lost to heat
+const SEED: u64 = 0x6b65_706c_6572;
+const ACCRETION_RATE: Real = 0.05; // fraction of the donor's mass flowing over per unit of time, per its radius of overlap
+const MIN_DONOR_MASS: Real = 0.01; // below which what is left of the donor is swallowed whole
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CollisionMode {
+    Ignore,
+    Merge,
+    Bounce,
+    Fragment,
+    Accrete,
+}
+
+serializable!(enum CollisionMode { Ignore, Merge, Bounce, Fragment, Accrete });
+
+impl CollisionMode {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Ignore => Self::Merge,
+            Self::Merge => Self::Bounce,
+            Self::Bounce => Self::Fragment,
+            Self::Fragment => Self::Accrete,
+            Self::Accrete => Self::Ignore,
+        }
+    }
+}
+
+impl std::fmt::Display for CollisionMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Ignore => write!(f, "ignore"),
+            Self::Merge => write!(f, "merge"),
+            Self::Bounce => write!(f, "bounce"),
+            Self::Fragment => write!(f, "fragment"),
+            Self::Accrete => write!(f, "accrete"),
+        }
+    }
+}
+
+pub struct Collisions {
+    pub mode: CollisionMode,
+    pub restitution: Real,
+    pub fragmentation_energy: Real,
+    pub fragments: usize,
+    pub accretion_rate: Real,
+    random: Random,
+}
+
+// the random numbers are drawn afresh
+serializable!(Collisions { mode, restitution, fragmentation_energy, fragments, accretion_rate } or Collisions::new());
+
+impl Collisions {
+    pub const fn new() -> Self {
+        Self {
+            mode: CollisionMode::Merge,
+            restitution: 1.,
+            fragmentation_energy: FRAGMENTATION_ENERGY,
+            fragments: FRAGMENTS,
+            accretion_rate: ACCRETION_RATE,
+            random: Random::seeded(SEED),
+        }
+    }
+
+    // returns pairs of (surviving, removed) indices, in order of removal; fragments are appended at the end
+    pub fn resolve(&mut self, bodies: &mut Vec<Body>, time_step: Real) -> Vec<(usize, usize)> {
+        match self.mode {
+            CollisionMode::Ignore => Vec::new(),
+            CollisionMode::Merge => self.merge_overlapping(bodies),
+            CollisionMode::Bounce => { bounce_overlapping(bodies, self.restitution); Vec::new() }
+            CollisionMode::Fragment => self.merge_overlapping(bodies),
+            CollisionMode::Accrete => self.accrete_overlapping(bodies, time_step),
+        }
+    }
+
+    fn merge_overlapping(&mut self, bodies: &mut Vec<Body>) -> Vec<(usize, usize)> {
+        let mut merges = Vec::new();
+
+        let mut i = 0;
+        while i < bodies.len() {
+            let mut j = i + 1;
+            while j < bodies.len() {
+                if bodies[i].overlaps(&bodies[j]) {
+                    let absorbed = bodies.remove(j);
+                    if self.shatters(&bodies[i], &absorbed) {
+                        let mut fragments = fragmented(&bodies[i], &absorbed, self.fragments, &mut self.random);
+                        bodies[i] = fragments.remove(0);
+                        bodies.append(&mut fragments);
+                    } else {
+                        bodies[i] = merged(&bodies[i], &absorbed);
+                    }
+                    merges.push((i, j));
+                    j = i + 1; // the merged body has grown, so it may now overlap bodies checked before
+                } else {
+                    j += 1;
+                }
+            }
+            i += 1;
+        }
+
+        merges
+    }
+
+    // the lighter of overlapping bodies loses mass to the heavier one gradually, the deeper they overlap the faster,
+    // until too little of it is left
+    fn accrete_overlapping(&self, bodies: &mut Vec<Body>, time_step: Real) -> Vec<(usize, usize)> {
+        let mut merges = Vec::new();
+
+        let mut i = 0;
+        while i < bodies.len() {
+            let mut j = i + 1;
+            while j < bodies.len() {
+                if !bodies[i].overlaps(&bodies[j]) { j += 1; continue; }
+
+                let (accretor, donor) = if bodies[i].mass >= bodies[j].mass { (i, j) } else { (j, i) };
+                let overlap = bodies[i].radius + bodies[j].radius - (bodies[j].position - bodies[i].position).magnitude();
+                let share = (self.accretion_rate * overlap / bodies[donor].radius * time_step.abs()).min(1.);
+                if bodies[donor].mass * (1. - share) < MIN_DONOR_MASS {
+                    let absorbed = bodies.remove(j);
+                    bodies[i] = merged(&bodies[i], &absorbed);
+                    merges.push((i, j));
+                    j = i + 1;
+                } else {
+                    let (accreted, depleted) = transferred(&bodies[accretor], &bodies[donor], share);
+                    bodies[accretor] = accreted;
+                    bodies[donor] = depleted;
+                    j += 1;
+                }
+            }
+            i += 1;
+        }
+
+        merges
+    }
+
+    fn shatters(&self, first: &Body, second: &Body) -> bool {
+        self.mode == CollisionMode::Fragment
+            && self.fragments >= 2
+            && impact_energy(first, second) > self.fragmentation_energy
+            && (first.mass + second.mass) / self.fragments as Real >= MIN_FRAGMENT_MASS
+    }
+}
+
+// kinetic energy of the relative motion, i.e. the part that is not bound to be conserved as momentum
+pub fn impact_energy(first: &Body, second: &Body) -> Real {
+    let reduced_mass = first.mass * second.mass / (first.mass + second.mass);
+    0.5 * reduced_mass * (second.velocity - first.velocity).magnitude().powi(2)
+}
+
+pub fn merged(first: &Body, second: &Body) -> Body {
+    let (heavier, lighter) = if first.mass >= second.mass { (first, second) } else { (second, first) };
+    let mass = heavier.mass + lighter.mass;
+    let center = heavier.position.lerp(lighter.position, lighter.mass / mass);
+    let velocity = (heavier.momentum() + lighter.momentum()) / mass;
+
+    let radius = (heavier.radius.powi(3) + lighter.radius.powi(3)).cbrt(); // whatever the density, volumes add up
+
+    let mut result = Body::new().named(&heavier.name).at(center).moving(velocity).with_mass(mass).with_radius(radius);
+    result.id = heavier.id; // the lighter one is swallowed by it
+    result.forces = heavier.forces.iter().chain(lighter.forces.iter()).copied().collect();
+    result.atmosphere = heavier.atmosphere;
+    result.engine = heavier.engine;
+    result.oblateness = heavier.oblateness;
+    result.radiation = heavier.radiation + lighter.radiation;
+    result.area_to_mass = (heavier.area_to_mass * heavier.mass + lighter.area_to_mass * lighter.mass) / mass;
+    result.color = heavier.color;
+
+    // spins and the angular momentum of the pair around its center of mass, about the z axis, end up in the spin of the whole
+    let angular_momentum = |body: &Body| {
+        let (offset, relative_velocity) = (body.position - center, body.velocity - velocity);
+        body.moment_of_inertia() * body.spin + body.mass * offset.cross(relative_velocity).dz
+    };
+    result.rotation = heavier.rotation;
+    if result.moment_of_inertia() > 0. { result.spin = (angular_momentum(heavier) + angular_momentum(lighter)) / result.moment_of_inertia(); }
+    result
+}
+
+// the given share of the donor's mass moves over to the accretor, along with its momentum; volumes add up as in a merge
+pub fn transferred(accretor: &Body, donor: &Body, share: Real) -> (Body, Body) {
+    let mass = donor.mass * share;
+    let mut accreted = accretor.clone();
+    accreted.mass += mass;
+    accreted.velocity = (accretor.momentum() + donor.velocity * mass) / accreted.mass;
+    accreted.radius = (accretor.radius.powi(3) + donor.radius.powi(3) * share).cbrt();
+
+    let mut depleted = donor.clone();
+    depleted.mass -= mass;
+    depleted.radius = donor.radius * (1. - share).cbrt();
+    (accreted, depleted)
+}
+
+pub fn bounce(first: &mut Body, second: &mut Body, restitution: Real) {
+    let joining_vector = second.position - first.position;
+    let normal = joining_vector.versor();
+    let inverse_masses = 1. / first.mass + 1. / second.mass;
+
+    let relative_velocity = second.velocity - first.velocity;
+    let approaching_speed = relative_velocity.dot(normal);
+    if approaching_speed < 0. {
+        let impulse = normal * (-(1. + restitution) * approaching_speed / inverse_masses);
+        first.velocity += -impulse / first.mass;
+        second.velocity += impulse / second.mass;
+    }
+
+    // push the bodies apart so that they do not remain stuck in each other
+    let overlap = first.radius + second.radius - joining_vector.magnitude();
+    if overlap > 0. {
+        let correction = normal * (overlap / inverse_masses);
+        first.position += -correction / first.mass;
+        second.position += correction / second.mass;
+    }
+}
+
+fn bounce_overlapping(bodies: &mut [Body], restitution: Real) {
+    for i in 0..bodies.len() {
+        let (head, tail) = bodies.split_at_mut(i + 1);
+        let body = &mut head[i];
+
+        for other_body in tail.iter_mut() {
+            if body.overlaps(other_body) {
+                bounce(body, other_body, restitution);
+            }
+        }
+    }
+}
+
+// the largest fragment comes first; total mass and momentum of the pair are conserved
+pub fn fragmented(first: &Body, second: &Body, count: usize, random: &mut Random) -> Vec<Body> {
+    let whole = merged(first, second);
+    let energy = FRAGMENTS_ENERGY_SHARE * impact_energy(first, second);
+
+    let mut masses: Vec<Real> = (0..count).map(|_| random.between(0.5, 1.5)).collect();
+    masses.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let total: Real = masses.iter().sum();
+    for mass in &mut masses { *mass *= whole.mass / total; }
+
+    let radius_of = |mass: Real| whole.radius * (mass / whole.mass).cbrt();
+    let largest_radius = radius_of(masses[0]);
+    let spread = 1.1 * largest_radius / (PI / count as Real).sin();
+
+    let mut offsets = Vec::with_capacity(count);
+    let mut velocities = Vec::with_capacity(count);
+    for k in 0..count {
+        let angle = 2. * PI * k as Real / count as Real;
+        let heading = angle + random.between(-0.5, 0.5);
+        offsets.push(EuclideanVector::from_polar(spread, angle));
+        velocities.push(EuclideanVector::from_polar(random.between(0.5, 1.5), heading));
+    }
+
+    // keep the center of mass and momentum of the fragments equal to those of the merged pair
+    let mean_offset = mass_weighted_mean(&offsets, &masses, whole.mass);
+    let mean_velocity = mass_weighted_mean(&velocities, &masses, whole.mass);
+    for k in 0..count {
+        offsets[k] -= mean_offset;
+        velocities[k] -= mean_velocity;
+    }
+
+    let fragments_energy: Real = velocities.iter().zip(&masses).map(|(velocity, mass)| 0.5 * mass * velocity.magnitude().powi(2)).sum();
+    let scale = if fragments_energy > 0. { (energy / fragments_energy).sqrt() } else { 0. };
+
+    (0..count).map(|k| {
+        let mut fragment = Body::new()
+            .named(&format!("{} fragment {}", whole.name, k + 1))
+            .at(whole.position + offsets[k])
+            .moving(whole.velocity + velocities[k] * scale)
+            .with_mass(masses[k])
+            .with_radius(radius_of(masses[k]))
+            .with_radiation(whole.radiation * masses[k] / whole.mass)
+            .with_sail(whole.area_to_mass);
+        fragment.color = whole.color;
+        fragment
+    }).collect()
+}
+
+fn mass_weighted_mean(vectors: &[EuclideanVector], masses: &[Real], total_mass: Real) -> EuclideanVector {
+    vectors.iter().zip(masses).fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, (&vector, &mass)| sum + vector * mass) / total_mass
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Random, Real};
+    use crate::physics::collision::{bounce, fragmented, merged, CollisionMode, Collisions};
+    use crate::physics::Body;
+
+    fn colliding_pair() -> Vec<Body> {
+        vec![
+            Body::new().named("heavy").with_mass(30.).at(Coordinate { x: 0., y: 0., z: 0. }).moving(EuclideanVector { dx: 1., dy: 0., dz: 0. }),
+            Body::new().named("light").with_mass(10.).at(Coordinate { x: 1., y: 0., z: 0. }).moving(EuclideanVector { dx: -1., dy: 2., dz: 0. }),
+        ]
+    }
+
+    #[test]
+    fn merging_conserves_mass_and_momentum() {
+        let bodies = colliding_pair();
+        let result = merged(&bodies[0], &bodies[1]);
+        assert_eq!(result.mass, 40.);
+        assert_eq!(result.momentum(), bodies[0].momentum() + bodies[1].momentum());
+        assert_eq!(result.position.x, 0.25);
+    }
+
+    #[test]
+    fn merging_turns_orbital_angular_momentum_into_spin() {
+        let mut bodies = colliding_pair();
+        bodies[0].spin = 0.5;
+        let angular_momentum = |body: &Body| body.moment_of_inertia() * body.spin + body.position.x * body.momentum().dy - body.position.y * body.momentum().dx;
+        let result = merged(&bodies[0], &bodies[1]);
+        let orbital = result.position.x * result.momentum().dy - result.position.y * result.momentum().dx;
+        assert!((result.moment_of_inertia() * result.spin + orbital - angular_momentum(&bodies[0]) - angular_momentum(&bodies[1])).abs() < 1e-9);
+    }
+
+    #[test]
+    fn merged_body_keeps_name_and_identity_of_the_heavier_one_and_grows() {
+        let bodies = colliding_pair();
+        let result = merged(&bodies[1], &bodies[0]);
+        assert_eq!(result.name, "heavy");
+        assert_eq!(result.id, bodies[0].id);
+        assert!(result.radius > bodies[0].radius);
+    }
+
+    #[test]
+    fn merged_body_keeps_radiating_and_the_sail_of_both() {
+        let bodies = colliding_pair();
+        let star = bodies[0].clone().with_radiation(5.).with_sail(0.2);
+        let sail = bodies[1].clone().with_radiation(1.).with_sail(0.8);
+        let result = merged(&sail, &star);
+        assert_eq!(result.radiation, 6.);
+        assert!((result.area_to_mass - (0.2 * 30. + 0.8 * 10.) / 40.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn merged_body_and_fragments_keep_the_color_of_the_heavier_one() {
+        let mut bodies = colliding_pair();
+        bodies[0] = bodies[0].clone().with_color(0.1, 0.2, 0.3);
+        assert_eq!(merged(&bodies[1], &bodies[0]).color, Some((0.1, 0.2, 0.3)));
+        let fragments = fragmented(&bodies[0], &bodies[1], 4, &mut Random::seeded(1));
+        assert!(fragments.iter().all(|fragment| fragment.color == Some((0.1, 0.2, 0.3))));
+    }
+
+    fn collisions(mode: CollisionMode) -> Collisions {
+        let mut result = Collisions::new();
+        result.mode = mode;
+        result
+    }
+
+    #[test]
+    fn overlapping_bodies_are_merged() {
+        let mut bodies = colliding_pair();
+        assert_eq!(collisions(CollisionMode::Merge).resolve(&mut bodies, 1.), vec![(0, 1)]);
+        assert_eq!(bodies.len(), 1);
+    }
+
+    #[test]
+    fn distant_bodies_are_not_merged() {
+        let mut bodies = colliding_pair();
+        bodies[1].position = Coordinate { x: 100., y: 0., z: 0. };
+        assert!(collisions(CollisionMode::Merge).resolve(&mut bodies, 1.).is_empty());
+        assert_eq!(bodies.len(), 2);
+    }
+
+    #[test]
+    fn collisions_may_be_ignored() {
+        let mut bodies = colliding_pair();
+        assert!(collisions(CollisionMode::Ignore).resolve(&mut bodies, 1.).is_empty());
+        assert_eq!(bodies.len(), 2);
+    }
+
+    fn head_on_pair() -> (Body, Body) {
+        (
+            Body::new().with_mass(2.).at(Coordinate { x: 0., y: 0., z: 0. }).moving(EuclideanVector { dx: 1., dy: 0., dz: 0. }),
+            Body::new().with_mass(1.).at(Coordinate { x: 1., y: 0., z: 0. }).moving(EuclideanVector { dx: -1., dy: 0., dz: 0. }),
+        )
+    }
+
+    #[test]
+    fn bouncing_conserves_momentum() {
+        let (mut first, mut second) = head_on_pair();
+        let momentum = first.momentum() + second.momentum();
+        bounce(&mut first, &mut second, 0.5);
+        assert!((first.momentum() + second.momentum()).approx_eq(momentum, 1e-12));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn perfectly_elastic_bounce_conserves_kinetic_energy() {
+        let kinetic_energy = |body: &Body| 0.5 * body.mass * body.velocity.magnitude().powi(2);
+        let (mut first, mut second) = head_on_pair();
+        let energy = kinetic_energy(&first) + kinetic_energy(&second);
+        bounce(&mut first, &mut second, 1.);
+        assert!((kinetic_energy(&first) + kinetic_energy(&second) - energy).abs() < 1e-12);
+        assert!(second.velocity.dx > first.velocity.dx);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn perfectly_inelastic_bounce_leaves_bodies_moving_together() {
+        let (mut first, mut second) = head_on_pair();
+        bounce(&mut first, &mut second, 0.);
+        assert!(first.velocity.approx_eq(second.velocity, 1e-12));
+    }
+
+    #[test]
+    fn bounced_bodies_no_longer_overlap() {
+        let (mut first, mut second) = head_on_pair();
+        bounce(&mut first, &mut second, 1.);
+        assert!((second.position - first.position).magnitude() >= first.radius + second.radius - 1e-9);
+    }
+
+    fn violent_pair() -> Vec<Body> {
+        let mut bodies = colliding_pair();
+        bodies[1].velocity = EuclideanVector { dx: -50., dy: 0., dz: 0. };
+        bodies
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn fragmentation_conserves_mass_and_momentum() {
+        let bodies = violent_pair();
+        let fragments = fragmented(&bodies[0], &bodies[1], 5, &mut Random::seeded(1));
+        let mass: Real = fragments.iter().map(|fragment| fragment.mass).sum();
+        let momentum = fragments.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, fragment| sum + fragment.momentum());
+
+        assert_eq!(fragments.len(), 5);
+        assert!((mass - 40.).abs() < 1e-9);
+        assert!(momentum.approx_eq(bodies[0].momentum() + bodies[1].momentum(), 1e-9));
+    }
+
+    #[test]
+    fn fragments_do_not_overlap_each_other() {
+        let bodies = violent_pair();
+        let fragments = fragmented(&bodies[0], &bodies[1], 5, &mut Random::seeded(1));
+        for (i, fragment) in fragments.iter().enumerate() {
+            assert!(fragments[i + 1..].iter().all(|other| !fragment.overlaps(other)));
+        }
+    }
+
+    #[test]
+    fn violent_impact_shatters_bodies() {
+        let mut bodies = violent_pair();
+        assert_eq!(collisions(CollisionMode::Fragment).resolve(&mut bodies, 1.), vec![(0, 1)]);
+        assert_eq!(bodies.len(), 5);
+    }
+
+    #[test]
+    fn gentle_impact_merges_bodies_even_in_fragmentation_mode() {
+        let mut bodies = colliding_pair();
+        collisions(CollisionMode::Fragment).resolve(&mut bodies, 1.);
+        assert_eq!(bodies.len(), 1);
+    }
+
+    #[test]
+    fn accretion_moves_mass_and_momentum_over_gradually() {
+        let mut bodies = colliding_pair();
+        let momentum = bodies[0].momentum() + bodies[1].momentum();
+        assert!(collisions(CollisionMode::Accrete).resolve(&mut bodies, 1.).is_empty());
+        assert!(bodies[0].mass > 30. && bodies[1].mass < 10.);
+        assert!((bodies[0].mass + bodies[1].mass - 40.).abs() < 1e-12);
+        assert!((bodies[0].momentum() + bodies[1].momentum()).approx_eq(momentum, 1e-12));
+        assert!(bodies[1].radius < Body::new().with_mass(10.).radius);
+    }
+
+    #[test]
+    fn depleted_donor_is_swallowed_whole() {
+        let mut bodies = colliding_pair();
+        bodies[1].mass = 0.005;
+        assert_eq!(collisions(CollisionMode::Accrete).resolve(&mut bodies, 1.), vec![(0, 1)]);
+        assert_eq!(bodies.len(), 1);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::maths::{narrowed, Coordinate, EuclideanVector, Real};
+use crate::physics::{Body, Constants, ForceSolver};
+
+// the compute shader of the pulls, dispatched over workgroups of this many invocations
+pub const SHADER: &str = include_str!("gravity.wgsl");
+pub const WORKGROUP_SIZE: usize = 64;
+
+// The uniform buffer of the shader, laid out as declared in it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[repr(C)]
+pub struct Parameters {
+    pub count: u32,
+    pub softening_squared: f32,
+    pub gravitational: f32,
+    padding: f32,
+}
+
+// What the shader is dispatched on: given its source, its parameters and its buffer of bodies, runs a workgroup for
+// every WORKGROUP_SIZE of them and returns what it wrote into its buffer of accelerations, one for every body.
+pub trait Device {
+    fn dispatch(&self, shader: &str, parameters: &Parameters, bodies: &[[f32; 4]]) -> Vec<[f32; 4]>;
+}
+
+// Runs its own rendition of the shader on the CPU, the workgroups one after another and the invocations of each in
+// lockstep between the barriers, for machines without a compute capable adapter. No device on top of an actual GPU
+// API comes with the application, as none of them can be built without a dependency on one; such a device would
+// compile the shader for the adapter instead, and the solver would be none the wiser.
+pub struct Emulated;
+
+impl Device for Emulated {
+    fn dispatch(&self, _shader: &str, parameters: &Parameters, bodies: &[[f32; 4]]) -> Vec<[f32; 4]> {
+        let count = parameters.count as usize;
+        let mut accelerations = vec![[0.; 4]; count];
+        let mut tile = [[0.; 4]; WORKGROUP_SIZE];
+
+        for workgroup in (0..count).step_by(WORKGROUP_SIZE) {
+            let invocations = workgroup..count.min(workgroup + WORKGROUP_SIZE);
+            let mut sums = [[0.; 3]; WORKGROUP_SIZE];
+            for start in (0..count).step_by(WORKGROUP_SIZE) {
+                for (local, body) in tile.iter_mut().enumerate() {
+                    *body = bodies.get(start + local).copied().unwrap_or([0.; 4]);
+                }
+                for (index, sum) in invocations.clone().zip(sums.iter_mut()) {
+                    let [x, y, z, _] = bodies[index];
+                    for &[tx, ty, tz, mass] in &tile {
+                        let (dx, dy, dz) = (tx - x, ty - y, tz - z);
+                        let distance_squared = dx * dx + dy * dy + dz * dz;
+                        if distance_squared > 0. {
+                            let magnitude = mass / ((distance_squared + parameters.softening_squared) * distance_squared.sqrt());
+                            sum[0] += dx * magnitude;
+                            sum[1] += dy * magnitude;
+                            sum[2] += dz * magnitude;
+                        }
+                    }
+                }
+            }
+            for (index, sum) in invocations.zip(&sums) {
+                accelerations[index] = [sum[0] * parameters.gravitational, sum[1] * parameters.gravitational, sum[2] * parameters.gravitational, 0.];
+            }
+        }
+
+        accelerations
+    }
+}
+
+// Exact pairwise summation like DirectSummation, offloaded to a compute shader in single precision: the positions and
+// masses are uploaded into its buffer of bodies, and the accelerations it leaves are copied back for the integrator,
+// and through the forces of the bodies for rendering.
+pub struct Compute<D: Device = Emulated> {
+    pub device: D,
+}
+
+impl Compute {
+    pub const fn new() -> Self {
+        Self { device: Emulated }
+    }
+}
+
+impl<D: Device> ForceSolver for Compute<D> {
+    fn accelerations(&self, bodies: &[Body], positions: &[Coordinate], constants: &Constants) -> Vec<EuclideanVector> {
+        let uploaded: Vec<[f32; 4]> = bodies.iter().zip(positions)
+            .map(|(body, position)| [narrowed(position.x), narrowed(position.y), narrowed(position.z), narrowed(body.mass)])
+            .collect();
+        let parameters = Parameters {
+            count: uploaded.len() as u32,
+            softening_squared: narrowed(constants.softening * constants.softening),
+            gravitational: narrowed(constants.gravitational),
+            padding: 0.,
+        };
+
+        self.device.dispatch(SHADER, &parameters, &uploaded).into_iter()
+            .map(|[dx, dy, dz, _]| EuclideanVector { dx: Real::from(dx), dy: Real::from(dy), dz: Real::from(dz) })
+            .collect()
+    }
+}
+
+impl<D: Device> std::fmt::Display for Compute<D> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "direct (compute shader)")
+    }
+}
@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::compute::{Compute, Device, Emulated, Parameters, SHADER, WORKGROUP_SIZE};
+    use crate::physics::{Body, Constants, DirectSummation, ForceSolver};
+    use std::cell::Cell;
+
+    // more than a workgroup, and not a multiple of its size, for a tile to be padded
+    fn cloud() -> Vec<Body> {
+        (0..150).map(|i| {
+            let i = i as Real;
+            Body::new().with_mass(1. + i % 3.).at(Coordinate { x: (i * 37.) % 101., y: (i * 53.) % 97., z: (i * 71.) % 89. })
+        }).collect()
+    }
+
+    fn positions_of(bodies: &[Body]) -> Vec<Coordinate> {
+        bodies.iter().map(|body| body.position).collect()
+    }
+
+    fn largest_difference(lhs: &[EuclideanVector], rhs: &[EuclideanVector]) -> Real {
+        lhs.iter().zip(rhs).map(|(&l, &r)| (l - r).magnitude() / r.magnitude()).fold(0., Real::max)
+    }
+
+    // counting the dispatches it is handed over to the emulation
+    struct Counting(Cell<usize>);
+
+    impl Device for Counting {
+        fn dispatch(&self, shader: &str, parameters: &Parameters, bodies: &[[f32; 4]]) -> Vec<[f32; 4]> {
+            assert_eq!(shader, SHADER);
+            self.0.set(self.0.get() + 1);
+            Emulated.dispatch(shader, parameters, bodies)
+        }
+    }
+
+    #[test]
+    fn shader_is_dispatched_over_workgroups_of_the_declared_size() {
+        assert!(SHADER.contains(&format!("@workgroup_size({})", WORKGROUP_SIZE)));
+        assert!(SHADER.contains(&format!("const WORKGROUP_SIZE: u32 = {}u;", WORKGROUP_SIZE)));
+    }
+
+    #[test]
+    fn compute_shader_matches_direct_summation_within_single_precision() {
+        let bodies = cloud();
+        let positions = positions_of(&bodies);
+        for constants in &[Constants::new(), Constants { softening: 2., ..Constants::new() }] {
+            let exact = DirectSummation.accelerations(&bodies, &positions, constants);
+            let computed = Compute::new().accelerations(&bodies, &positions, constants);
+            assert!(largest_difference(&computed, &exact) < 1e-5);
+        }
+    }
+
+    #[test]
+    fn coinciding_bodies_do_not_pull_each_other() {
+        let bodies: Vec<Body> = (0..3).map(|_| Body::new().with_mass(1.)).collect();
+        let accelerations = Compute::new().accelerations(&bodies, &positions_of(&bodies), &Constants::new());
+        assert!(accelerations.iter().all(|&acceleration| acceleration == 0.));
+    }
+
+    #[test]
+    fn forces_are_copied_back_into_the_bodies_after_a_single_dispatch() {
+        let mut bodies = cloud();
+        let compute = Compute { device: Counting(Cell::new(0)) };
+        compute.refresh_forces(&mut bodies, &Constants::new());
+        assert_eq!(compute.device.0.get(), 1);
+
+        let exact = DirectSummation.accelerations(&bodies, &positions_of(&bodies), &Constants::new());
+        let copied: Vec<EuclideanVector> = bodies.iter().map(|body| body.forces[0] / body.mass).collect();
+        assert!(largest_difference(&copied, &exact) < 1e-5);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::maths::{Coordinate, EuclideanVector, Real};
+use crate::physics::{heaviest_first, orbit, Body, Constants};
+
+const INFLUENCE_EXPONENT: Real = 0.4; // of the mass ratio, in Laplace's radius of the sphere of influence
+
+// For each body, the one whose sphere of influence it is in: the innermost of those of heavier bodies containing it.
+// The heaviest body of all is attracted by none, and its sphere of influence is boundless.
+pub fn attractors(bodies: &[Body]) -> Vec<Option<usize>> {
+    let by_mass = heaviest_first(bodies);
+    let mut attractors = vec![None; bodies.len()];
+    let mut spheres = vec![Real::INFINITY; bodies.len()];
+    for (rank, &index) in by_mass.iter().enumerate().skip(1) {
+        let body = &bodies[index];
+        let attractor = by_mass[..rank].iter().copied()
+            .filter(|&other| (body.position - bodies[other].position).magnitude() < spheres[other])
+            .fold(by_mass[0], |innermost, other| if spheres[other] < spheres[innermost] { other } else { innermost });
+
+        let primary = &bodies[attractor];
+        spheres[index] = (body.position - primary.position).magnitude() * (body.mass / primary.mass).powf(INFLUENCE_EXPONENT);
+        attractors[index] = Some(attractor);
+    }
+    attractors
+}
+
+// Moves every body along the conic around its attractor, as if nothing else pulled it, and carries it along with
+// the attractor itself. Bodies switch attractors between steps, whenever they cross a sphere of influence.
+pub fn advance(bodies: &mut [Body], constants: &Constants, time_step: Real) {
+    let attractors = attractors(bodies);
+    let mut states: Vec<(Coordinate, EuclideanVector)> = bodies.iter().map(|body| (body.position, body.velocity)).collect();
+
+    // attractors are heavier, so they are already moved by the time their satellites are
+    for index in heaviest_first(bodies) {
+        let body = &bodies[index];
+        states[index] = match attractors[index] {
+            None => (body.position + body.velocity * time_step, body.velocity),
+            Some(attractor) => {
+                let primary = &bodies[attractor];
+                let (offset, relative_velocity) = orbit::propagated(body, primary, constants, time_step).unwrap_or_else(|| {
+                    let relative_velocity = body.velocity - primary.velocity;
+                    (body.position - primary.position + relative_velocity * time_step, relative_velocity)
+                });
+                let (position, velocity) = states[attractor];
+                (position + offset, velocity + relative_velocity)
+            }
+        };
+    }
+
+    for (body, (position, velocity)) in bodies.iter_mut().zip(states) {
+        body.displace(position - body.position);
+        body.accelerate(velocity - body.velocity);
+    }
+}
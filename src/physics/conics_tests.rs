@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::conics::{advance, attractors};
+    use crate::physics::{Body, Constants};
+
+    fn circular_speed(mass: Real, distance: Real) -> Real {
+        (Constants::new().gravitational * mass / distance).sqrt()
+    }
+
+    // a star, a planet orbiting it, and a moon orbiting the planet
+    fn system() -> Vec<Body> {
+        let planet_speed = circular_speed(1010., 1000.);
+        vec![
+            Body::new().with_mass(1000.),
+            Body::new().with_mass(10.).at(Coordinate { x: 1000., y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: planet_speed, dz: 0. }),
+            Body::new().with_mass(0.01).at(Coordinate { x: 1020., y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: planet_speed + circular_speed(10.01, 20.), dz: 0. }),
+        ]
+    }
+
+    #[test]
+    fn bodies_are_attracted_by_the_innermost_sphere_of_influence_they_are_in() {
+        let mut bodies = system();
+        bodies.push(Body::new().with_mass(0.01).at(Coordinate { x: 0., y: 500., z: 0. }));
+        assert_eq!(attractors(&bodies), vec![None, Some(0), Some(1), Some(0)]);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn moon_keeps_its_orbit_around_a_moving_planet() {
+        let mut bodies = system();
+        for _ in 0..1000 { advance(&mut bodies, &Constants::new(), 1.); }
+        assert!(((bodies[1].position - bodies[0].position).magnitude() - 1000.).abs() < 1e-6);
+        assert!(((bodies[2].position - bodies[1].position).magnitude() - 20.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn escaping_probe_passes_over_to_the_star() {
+        let mut bodies = system();
+        bodies[2].velocity = bodies[1].velocity + EuclideanVector { dx: 0., dy: 3. * circular_speed(10., 20.), dz: 0. };
+        assert_eq!(attractors(&bodies)[2], Some(1));
+        for _ in 0..200 { advance(&mut bodies, &Constants::new(), 1.); }
+        assert_eq!(attractors(&bodies)[2], Some(0));
+    }
+}
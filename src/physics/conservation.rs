@@ -0,0 +1,50 @@
+use crate::maths::{Coordinate, EuclideanVector, Real};
+use crate::physics::{Body, Constants};
+
+// Quantities that an ideal integrator keeps constant for an isolated system.
+#[derive(Copy, Clone, Debug)]
+pub struct Totals {
+    pub energy: Real, // kinetic and potential
+    pub momentum: EuclideanVector,
+    pub angular_momentum: Real, // about the z axis through the origin
+}
+
+impl Totals {
+    pub fn of(bodies: &[Body], constants: &Constants) -> Self {
+        let mut result = Self { energy: 0., momentum: EuclideanVector { dx: 0., dy: 0., dz: 0. }, angular_momentum: 0. };
+
+        for (i, body) in bodies.iter().enumerate() {
+            let momentum = body.momentum();
+            result.energy += momentum.dot(body.velocity) / 2.;
+            result.momentum += momentum;
+            result.angular_momentum += EuclideanVector::towards(body.position).cross(momentum).dz;
+
+            for other in &bodies[i + 1..] {
+                let distance = ((other.position - body.position).magnitude().powi(2) + constants.softening.powi(2)).sqrt();
+                if distance > 0. { result.energy -= constants.gravitational * body.mass * other.mass / distance; }
+            }
+        }
+
+        result
+    }
+
+    // relative for scalar quantities, absolute for the momentum that is usually zero to begin with
+    pub fn drift_from(&self, initial: &Self) -> Self {
+        let relative = |current: Real, initial: Real| if initial == 0. { current - initial } else { (current - initial) / initial.abs() };
+        Self {
+            energy: relative(self.energy, initial.energy),
+            momentum: self.momentum - initial.momentum,
+            angular_momentum: relative(self.angular_momentum, initial.angular_momentum),
+        }
+    }
+}
+
+// gravitational, per unit of mass at the given position, softened like the pull of the bodies is
+pub fn potential_at(bodies: &[Body], constants: &Constants, position: Coordinate) -> Real {
+    bodies.iter()
+        .map(|body| {
+            let distance = ((body.position - position).magnitude().powi(2) + constants.softening.powi(2)).sqrt();
+            if distance > 0. { -constants.gravitational * body.mass / distance } else { 0. }
+        })
+        .sum()
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector};
+    use crate::physics::conservation::{potential_at, Totals};
+    use crate::physics::{Body, Constants};
+
+    #[test]
+    fn resting_pair_has_only_potential_energy() {
+        let bodies = vec![Body::new().with_mass(2.), Body::new().with_mass(3.).at(Coordinate { x: 4., y: 0., z: 0. })];
+        let totals = Totals::of(&bodies, &Constants::new());
+        assert_eq!(totals.energy, -Constants::new().gravitational * 6. / 4.);
+        assert_eq!(totals.momentum, 0.);
+        assert_eq!(totals.angular_momentum, 0.);
+    }
+
+    #[test]
+    fn potential_deepens_towards_bodies_and_skips_their_centers() {
+        let bodies = vec![Body::new().with_mass(2.), Body::new().with_mass(3.).at(Coordinate { x: 4., y: 0., z: 0. })];
+        let gravitational = Constants::new().gravitational;
+        assert_eq!(potential_at(&bodies, &Constants::new(), Coordinate { x: 0., y: 0., z: 0. }), -gravitational * 3. / 4.);
+        assert!(potential_at(&bodies, &Constants::new(), Coordinate { x: 2., y: 0., z: 0. }) < potential_at(&bodies, &Constants::new(), Coordinate { x: 2., y: 10., z: 0. }));
+    }
+
+    #[test]
+    fn moving_body_has_kinetic_energy_and_momentum() {
+        let bodies = vec![Body::new().with_mass(2.).at(Coordinate { x: 0., y: 5., z: 0. }).moving(EuclideanVector { dx: 3., dy: 0., dz: 0. })];
+        let totals = Totals::of(&bodies, &Constants::new());
+        assert_eq!(totals.energy, 9.);
+        assert_eq!(totals.momentum, EuclideanVector { dx: 6., dy: 0., dz: 0. });
+        assert_eq!(totals.angular_momentum, -30.);
+    }
+
+    #[test]
+    fn drift_is_relative_to_initial_values() {
+        let initial = Totals { energy: -10., momentum: EuclideanVector { dx: 0., dy: 0., dz: 0. }, angular_momentum: 4. };
+        let current = Totals { energy: -9., momentum: EuclideanVector { dx: 0.5, dy: 0., dz: 0. }, angular_momentum: 5. };
+        let drift = current.drift_from(&initial);
+        assert_eq!(drift.energy, 0.1);
+        assert_eq!(drift.momentum, EuclideanVector { dx: 0.5, dy: 0., dz: 0. });
+        assert_eq!(drift.angular_momentum, 0.25);
+    }
+}
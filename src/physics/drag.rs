@@ -0,0 +1,50 @@
+use crate::maths::{consts::PI, EuclideanVector, Real};
+use crate::physics::{Body, Constants, ForceModel};
+
+const SCALE_HEIGHTS: Real = 5.; // how many times density falls by e between the surface and the edge of an atmosphere
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Atmosphere {
+    pub radius: Real, // measured from the center of the body
+    pub density: Real, // at the surface
+}
+
+serializable!(Atmosphere { radius, density } or Atmosphere { radius: 0., density: 0. });
+
+impl Atmosphere {
+    pub fn density_at(&self, distance: Real, surface: Real) -> Real {
+        if distance >= self.radius { return 0.; }
+        let altitude = (distance - surface).max(0.);
+        let scale_height = (self.radius - surface) / SCALE_HEIGHTS;
+        self.density * (-altitude / scale_height).exp()
+    }
+}
+
+// quadratic drag, proportional to the cross-section of the body moving through the atmosphere,
+// along with the reaction of the atmospheres of the body itself on whatever moves through them
+pub struct Drag;
+
+impl Drag {
+    fn on(body: &Body, planet: &Body) -> EuclideanVector {
+        let atmosphere = match &planet.atmosphere {
+            Some(atmosphere) => atmosphere,
+            None => return EuclideanVector { dx: 0., dy: 0., dz: 0. },
+        };
+
+        let density = atmosphere.density_at((body.position - planet.position).magnitude(), planet.radius);
+        if density == 0. { return EuclideanVector { dx: 0., dy: 0., dz: 0. }; }
+
+        let relative_velocity = body.velocity - planet.velocity;
+        -relative_velocity * (density * PI * body.radius * body.radius * relative_velocity.magnitude())
+    }
+}
+
+impl ForceModel for Drag {
+    fn force_on(&self, body: &Body, others: &[Body], _constants: &Constants) -> EuclideanVector {
+        others.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, other| sum + Self::on(body, other) - Self::on(other, body))
+    }
+
+    fn acts_on(&self, bodies: &[Body], _constants: &Constants) -> bool {
+        bodies.iter().any(|body| body.atmosphere.is_some())
+    }
+}
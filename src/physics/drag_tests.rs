@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::drag::Drag;
+    use crate::physics::{Body, Constants, ForceModel};
+
+    fn planet_and_satellite_at(altitude: Real) -> Vec<Body> {
+        let planet = Body::new().with_mass(100.).with_atmosphere(20., 0.1);
+        let satellite_distance = planet.radius + altitude;
+        vec![
+            planet,
+            Body::new().with_mass(1.).at(Coordinate { x: satellite_distance, y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: 2., dz: 0. }),
+        ]
+    }
+
+    fn drag_on_satellite_at(altitude: Real) -> EuclideanVector {
+        let bodies = planet_and_satellite_at(altitude);
+        Drag.force_on(&bodies[1], &bodies[..1], &Constants::new())
+    }
+
+    #[test]
+    fn there_is_no_drag_outside_of_atmosphere() {
+        assert_eq!(drag_on_satellite_at(50.), 0.);
+    }
+
+    #[test]
+    fn drag_opposes_motion_through_atmosphere() {
+        let drag = drag_on_satellite_at(5.);
+        assert!(drag.dy < 0.);
+        assert_eq!(drag.dx, 0.);
+    }
+
+    #[test]
+    fn drag_grows_deeper_in_atmosphere() {
+        assert!(drag_on_satellite_at(2.).magnitude() > drag_on_satellite_at(10.).magnitude());
+    }
+
+    #[test]
+    fn drag_conserves_momentum() {
+        let bodies = planet_and_satellite_at(5.);
+        let on_planet = Drag.force_on(&bodies[0], &bodies[1..], &Constants::new());
+        let on_satellite = Drag.force_on(&bodies[1], &bodies[..1], &Constants::new());
+        assert!((on_planet + on_satellite).magnitude() < 1e-12);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::maths::{consts::PI, EuclideanVector, Real};
+use crate::physics::{Body, Constants, ForceModel};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Engine {
+    pub thrust: Real, // at full throttle
+    pub heading: Real, // in radians, direction in which the engine pushes the body
+    pub throttle: Real, // in range 0..=1
+    pub propellant: Real, // remaining impulse, i.e. thrust integrated over time
+    pub spent: Real, // impulse burned so far
+}
+
+serializable!(Engine { thrust, heading, throttle, propellant, spent } or Engine::new(0., 0.));
+
+impl Engine {
+    pub const fn new(thrust: Real, propellant: Real) -> Self {
+        Self { thrust, heading: 0., throttle: 0., propellant, spent: 0. }
+    }
+
+    pub fn is_firing(&self) -> bool {
+        self.throttle > 0. && self.propellant > 0.
+    }
+
+    pub fn thrust_vector(&self) -> EuclideanVector {
+        if !self.is_firing() { return EuclideanVector { dx: 0., dy: 0., dz: 0. }; }
+        EuclideanVector::from_polar(self.thrust * self.throttle, self.heading)
+    }
+
+    pub fn rotate(&mut self, angle: Real) {
+        self.heading = (self.heading + angle) % (2. * PI);
+    }
+
+    pub fn burn(&mut self, time_step: Real) {
+        if !self.is_firing() { return; }
+        let impulse = (self.thrust * self.throttle * time_step.abs()).min(self.propellant);
+        self.propellant -= impulse;
+        self.spent += impulse;
+    }
+
+    // the change of velocity the burned propellant gave to a body of the given mass, and the one it still can give
+    pub fn delta_v(&self, mass: Real) -> (Real, Real) {
+        (self.spent / mass, self.propellant / mass)
+    }
+}
+
+// of the engines of the bodies, at their current throttle
+pub struct Thrust;
+
+impl ForceModel for Thrust {
+    fn force_on(&self, body: &Body, _others: &[Body], _constants: &Constants) -> EuclideanVector {
+        body.engine.map_or(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |engine| engine.thrust_vector())
+    }
+
+    fn acts_on(&self, bodies: &[Body], _constants: &Constants) -> bool {
+        bodies.iter().any(|body| body.engine.is_some_and(|engine| engine.is_firing()))
+    }
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector};
+    use crate::physics::engine::Engine;
+    use crate::physics::integration::{Integrator, NOMINAL_TIME_STEP};
+    use crate::physics::{Body, Constants, DirectSummation, standard_models};
+
+    #[test]
+    fn idle_engine_does_not_push() {
+        assert_eq!(Engine::new(1., 10.).thrust_vector(), 0.);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn firing_engine_pushes_along_its_heading() {
+        let mut engine = Engine::new(2., 10.);
+        engine.throttle = 0.5;
+        engine.rotate(crate::maths::consts::FRAC_PI_2);
+        let thrust = engine.thrust_vector();
+        assert!(thrust.dx.abs() < 1e-12);
+        assert!((thrust.dy - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn burning_consumes_propellant_until_tank_is_empty() {
+        let mut engine = Engine::new(2., 3.);
+        engine.throttle = 1.;
+        engine.burn(1.);
+        assert_eq!(engine.propellant, 1.);
+        engine.burn(1.);
+        assert_eq!(engine.propellant, 0.);
+        assert!(!engine.is_firing());
+        assert_eq!(engine.thrust_vector(), 0.);
+    }
+
+    #[test]
+    fn delta_v_spent_adds_up_to_what_the_tank_held() {
+        let mut engine = Engine::new(2., 3.);
+        engine.throttle = 1.;
+        engine.burn(1.);
+        assert_eq!(engine.delta_v(0.5), (4., 2.));
+        engine.burn(1.);
+        assert_eq!(engine.delta_v(0.5), (6., 0.));
+    }
+
+    #[test]
+    fn firing_engine_accelerates_the_body() {
+        for &integrator in &[Integrator::Euler, Integrator::RungeKutta4] {
+            let mut bodies = vec![Body::new().with_mass(1.).at(Coordinate { x: 0., y: 0., z: 0. }).with_engine(1., 10.)];
+            bodies[0].engine.as_mut().unwrap().throttle = 1.;
+
+            integrator.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP);
+            integrator.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP);
+
+            assert!(bodies[0].velocity.dx > 0.);
+            assert_eq!(bodies[0].velocity.dy, 0.);
+            assert_eq!(bodies[0].engine.unwrap().propellant, 8.);
+        }
+    }
+
+    #[test]
+    fn engine_is_kept_by_a_body_moving_with_it() {
+        let body = Body::new().with_mass(1.).moving(EuclideanVector { dx: 1., dy: 0., dz: 0. }).with_engine(1., 10.);
+        assert!(body.engine.is_some());
+    }
+}
@@ -0,0 +1,42 @@
+use crate::maths::{EuclideanVector, Real};
+use crate::physics::orbit::Elements;
+use crate::physics::{Body, Constants};
+
+// A hyperbolic pass by a planet, as seen from the frame of the body the planet itself orbits, assuming the planet
+// keeps its velocity for the duration of the pass.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Flyby {
+    pub excess_speed: Real, // hyperbolic excess velocity, i.e. the speed relative to the planet far away from it
+    pub turn_angle: Real, // between the incoming and outgoing asymptotes
+    pub periapsis: Real,
+    pub speed_change: Real, // relative to the frame, from before to after the pass
+}
+
+impl Flyby {
+    // none unless the body is on a hyperbolic trajectory relative to the planet
+    pub fn of(body: &Body, planet: &Body, frame_velocity: EuclideanVector, constants: &Constants) -> Option<Self> {
+        let elements = Elements::of(body, planet, constants);
+        if elements.eccentricity <= 1. { return None; }
+
+        let gravitational_parameter = constants.gravitational * (body.mass + planet.mass);
+        let excess_speed = (-gravitational_parameter / elements.semi_major_axis).sqrt();
+
+        let asymptote = (-1. / elements.eccentricity).acos(); // true anomaly of the outgoing one
+        let incoming = -elements.direction_at(-asymptote) * excess_speed;
+        let outgoing = elements.direction_at(asymptote) * excess_speed;
+
+        let planet_velocity = planet.velocity - frame_velocity;
+        Some(Self {
+            excess_speed,
+            turn_angle: 2. * (1. / elements.eccentricity).asin(),
+            periapsis: elements.periapsis,
+            speed_change: (planet_velocity + outgoing).magnitude() - (planet_velocity + incoming).magnitude(),
+        })
+    }
+}
+
+// i.e. a body approaching its closest point to the other one; a pass gets reported once it is behind it
+pub fn is_approaching(body: &Body, other: &Body) -> bool {
+    let (position, velocity) = (body.position - other.position, body.velocity - other.velocity);
+    position.dot(velocity) < 0.
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::flyby::{is_approaching, Flyby};
+    use crate::physics::orbit::propagated;
+    use crate::physics::{Body, Constants};
+
+    const FAR_AWAY: Real = 1e6; // of time, for the body to be practically on its asymptotes
+
+    fn planet() -> Body {
+        Body::new().with_mass(100.).moving(EuclideanVector { dx: 3., dy: 0., dz: 0. })
+    }
+
+    // crossing the orbit of the planet right behind it or right ahead of it
+    fn probe(side: Real) -> Body {
+        Body::new().with_mass(0.).at(Coordinate { x: side * 20., y: -50., z: 0. }).moving(EuclideanVector { dx: 3., dy: 8., dz: 0. })
+    }
+
+    fn frame() -> EuclideanVector {
+        EuclideanVector { dx: 0., dy: 0., dz: 0. }
+    }
+
+    #[test]
+    fn excess_velocity_and_turn_match_the_asymptotic_motion() {
+        let (planet, probe) = (planet(), probe(-1.));
+        let flyby = Flyby::of(&probe, &planet, frame(), &Constants::new()).unwrap();
+        let (_, before) = propagated(&probe, &planet, &Constants::new(), -FAR_AWAY).unwrap();
+        let (_, after) = propagated(&probe, &planet, &Constants::new(), FAR_AWAY).unwrap();
+
+        assert!((before.magnitude() - flyby.excess_speed).abs() < 1e-3);
+        assert!((after.magnitude() - flyby.excess_speed).abs() < 1e-3);
+        let turn = (before.dx * after.dx + before.dy * after.dy) / (before.magnitude() * after.magnitude());
+        assert!((turn.acos() - flyby.turn_angle).abs() < 1e-3);
+        assert!(((planet.velocity + after).magnitude() - (planet.velocity + before).magnitude() - flyby.speed_change).abs() < 1e-3);
+    }
+
+    #[test]
+    fn passing_behind_the_planet_speeds_the_body_up() {
+        assert!(Flyby::of(&probe(-1.), &planet(), frame(), &Constants::new()).unwrap().speed_change > 0.);
+        assert!(Flyby::of(&probe(1.), &planet(), frame(), &Constants::new()).unwrap().speed_change < 0.);
+    }
+
+    #[test]
+    fn captured_body_is_not_flying_by() {
+        let captured = Body::new().with_mass(0.).at(Coordinate { x: 10., y: 0., z: 0. }).moving(EuclideanVector { dx: 3., dy: 0.5, dz: 0. });
+        assert!(Flyby::of(&captured, &planet(), frame(), &Constants::new()).is_none());
+    }
+
+    #[test]
+    fn incoming_body_is_approaching() {
+        assert!(is_approaching(&probe(-1.), &planet()));
+        assert!(!is_approaching(&probe(-1.).at(Coordinate { x: -20., y: 50., z: 0. }), &planet()));
+    }
+}
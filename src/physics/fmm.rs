@@ -0,0 +1,352 @@
+use crate::maths::{Coordinate, EuclideanVector, Real};
+use crate::physics::{gravitational_pull, Body, Constants, ForceSolver};
+
+const BODIES_PER_LEAF: usize = 4; // on average, deciding the depth of the grid
+const MIN_LEVEL: u32 = 2; // the shallowest one with cells well separated from one another
+const SEPARATION: usize = 2; // cells up to that many cells apart are neighbours, whose bodies interact directly
+
+// symmetric 3×3 matrix, i.e. a quadrupole moment or a gradient of acceleration
+#[derive(Copy, Clone)]
+struct Symmetric {
+    xx: Real,
+    xy: Real,
+    xz: Real,
+    yy: Real,
+    yz: Real,
+    zz: Real,
+}
+
+impl Symmetric {
+    const ZERO: Self = Self { xx: 0., xy: 0., xz: 0., yy: 0., yz: 0., zz: 0. };
+
+    const fn diagonal(value: Real) -> Self {
+        Self { xx: value, xy: 0., xz: 0., yy: value, yz: 0., zz: value }
+    }
+
+    // a·bᵀ + b·aᵀ, halved, so that outer(a, a) is the plain outer product
+    fn outer(a: EuclideanVector, b: EuclideanVector) -> Self {
+        Self {
+            xx: a.dx * b.dx,
+            xy: (a.dx * b.dy + a.dy * b.dx) / 2.,
+            xz: (a.dx * b.dz + a.dz * b.dx) / 2.,
+            yy: a.dy * b.dy,
+            yz: (a.dy * b.dz + a.dz * b.dy) / 2.,
+            zz: a.dz * b.dz,
+        }
+    }
+
+    fn trace(&self) -> Real {
+        self.xx + self.yy + self.zz
+    }
+
+    fn times(&self, vector: EuclideanVector) -> EuclideanVector {
+        EuclideanVector {
+            dx: self.xx * vector.dx + self.xy * vector.dy + self.xz * vector.dz,
+            dy: self.xy * vector.dx + self.yy * vector.dy + self.yz * vector.dz,
+            dz: self.xz * vector.dx + self.yz * vector.dy + self.zz * vector.dz,
+        }
+    }
+
+    fn add(&mut self, other: Self, factor: Real) {
+        self.xx += other.xx * factor;
+        self.xy += other.xy * factor;
+        self.xz += other.xz * factor;
+        self.yy += other.yy * factor;
+        self.yz += other.yz * factor;
+        self.zz += other.zz * factor;
+    }
+}
+
+// symmetric 3×3×3 tensor, i.e. the curvature of acceleration
+#[derive(Copy, Clone)]
+struct Curvature {
+    xxx: Real,
+    xxy: Real,
+    xxz: Real,
+    xyy: Real,
+    xyz: Real,
+    xzz: Real,
+    yyy: Real,
+    yyz: Real,
+    yzz: Real,
+    zzz: Real,
+}
+
+impl Curvature {
+    const ZERO: Self = Self { xxx: 0., xxy: 0., xxz: 0., xyy: 0., xyz: 0., xzz: 0., yyy: 0., yyz: 0., yzz: 0., zzz: 0. };
+
+    // contracted with a vector once, yielding the change of the gradient along it
+    fn times(&self, vector: EuclideanVector) -> Symmetric {
+        let (x, y, z) = (vector.dx, vector.dy, vector.dz);
+        Symmetric {
+            xx: self.xxx * x + self.xxy * y + self.xxz * z,
+            xy: self.xxy * x + self.xyy * y + self.xyz * z,
+            xz: self.xxz * x + self.xyz * y + self.xzz * z,
+            yy: self.xyy * x + self.yyy * y + self.yyz * z,
+            yz: self.xyz * x + self.yyz * y + self.yzz * z,
+            zz: self.xzz * x + self.yzz * y + self.zzz * z,
+        }
+    }
+
+    fn add(&mut self, other: Self, factor: Real) {
+        self.xxx += other.xxx * factor;
+        self.xxy += other.xxy * factor;
+        self.xxz += other.xxz * factor;
+        self.xyy += other.xyy * factor;
+        self.xyz += other.xyz * factor;
+        self.xzz += other.xzz * factor;
+        self.yyy += other.yyy * factor;
+        self.yyz += other.yyz * factor;
+        self.yzz += other.yzz * factor;
+        self.zzz += other.zzz * factor;
+    }
+}
+
+// moments of the bodies within a cell, about the center of the cell
+#[derive(Copy, Clone)]
+struct Multipole {
+    count: usize,
+    mass: Real,
+    dipole: EuclideanVector,
+    quadrupole: Symmetric,
+}
+
+impl Multipole {
+    const EMPTY: Self = Self { count: 0, mass: 0., dipole: EuclideanVector { dx: 0., dy: 0., dz: 0. }, quadrupole: Symmetric::ZERO };
+
+    // of mass located at the given offset from the center of the cell
+    fn add(&mut self, offset: EuclideanVector, mass: Real) {
+        self.count += 1;
+        self.mass += mass;
+        self.dipole += offset * mass;
+        self.quadrupole.add(Symmetric::outer(offset, offset), mass);
+    }
+
+    // of a child cell, whose center lies at the given offset from the center of this one
+    fn add_shifted(&mut self, child: &Self, offset: EuclideanVector) {
+        self.count += child.count;
+        self.mass += child.mass;
+        self.dipole += child.dipole + offset * child.mass;
+        self.quadrupole.add(child.quadrupole, 1.);
+        self.quadrupole.add(Symmetric::outer(child.dipole, offset), 2.);
+        self.quadrupole.add(Symmetric::outer(offset, offset), child.mass);
+    }
+
+    // at the given offset from the center of the cell, expanded up to the quadrupole
+    fn acceleration_at(&self, offset: EuclideanVector) -> EuclideanVector {
+        let r = offset.magnitude();
+        let (r3, r5) = (r * r * r, r * r * r * r * r);
+        let projected_dipole = offset.dot(self.dipole);
+        let quadrupole_along = self.quadrupole.times(offset);
+        let projected_quadrupole = offset.dot(quadrupole_along);
+
+        let monopole = -offset * (self.mass / r3);
+        let dipole = self.dipole / r3 - offset * (3. * projected_dipole / r5);
+        let quadrupole = (quadrupole_along * 3. - offset * self.quadrupole.trace()) / r5
+            - offset * (5. * (3. * projected_quadrupole - r * r * self.quadrupole.trace()) / (2. * r5 * r * r));
+        monopole + dipole + quadrupole
+    }
+
+    // of the acceleration with respect to the point it is taken at, of the monopole alone
+    fn gradient_at(&self, offset: EuclideanVector) -> Symmetric {
+        let r = offset.magnitude();
+        let (r3, r5) = (r * r * r, r * r * r * r * r);
+        let mut gradient = Symmetric::diagonal(-self.mass / r3);
+        gradient.add(Symmetric::outer(offset, offset), 3. * self.mass / r5);
+        gradient
+    }
+
+    // of the acceleration, likewise of the monopole alone
+    fn curvature_at(&self, offset: EuclideanVector) -> Curvature {
+        let r = offset.magnitude();
+        let (r5, r7) = (r.powi(5), r.powi(7));
+        let (x, y, z, m) = (offset.dx, offset.dy, offset.dz, self.mass);
+        Curvature {
+            xxx: m * (9. * x / r5 - 15. * x * x * x / r7),
+            xxy: m * (3. * y / r5 - 15. * x * x * y / r7),
+            xxz: m * (3. * z / r5 - 15. * x * x * z / r7),
+            xyy: m * (3. * x / r5 - 15. * x * y * y / r7),
+            xyz: m * (-15. * x * y * z / r7),
+            xzz: m * (3. * x / r5 - 15. * x * z * z / r7),
+            yyy: m * (9. * y / r5 - 15. * y * y * y / r7),
+            yyz: m * (3. * z / r5 - 15. * y * y * z / r7),
+            yzz: m * (3. * y / r5 - 15. * y * z * z / r7),
+            zzz: m * (9. * z / r5 - 15. * z * z * z / r7),
+        }
+    }
+}
+
+// field of the far away cells, expanded up to the second order about the center of a cell
+#[derive(Copy, Clone)]
+struct Local {
+    acceleration: EuclideanVector,
+    gradient: Symmetric,
+    curvature: Curvature,
+}
+
+impl Local {
+    const ZERO: Self = Self { acceleration: EuclideanVector { dx: 0., dy: 0., dz: 0. }, gradient: Symmetric::ZERO, curvature: Curvature::ZERO };
+
+    fn at(&self, offset: EuclideanVector) -> EuclideanVector {
+        self.acceleration + self.gradient.times(offset) + self.curvature.times(offset).times(offset) / 2.
+    }
+
+    // about a point at the given offset from the current center
+    fn shifted(&self, offset: EuclideanVector) -> Self {
+        let mut gradient = self.gradient;
+        gradient.add(self.curvature.times(offset), 1.);
+        Self { acceleration: self.at(offset), gradient, curvature: self.curvature }
+    }
+}
+
+type Cell = (usize, usize, usize);
+
+// Uniform grid of cells split in eight at every level, or in four when the bodies all lie in a single plane parallel
+// to the one of the simulation, with the bodies in its deepest one
+struct Grid {
+    origin: Coordinate,
+    size: Real,
+    depth: u32,
+    flat: bool, // with a single layer of cells
+}
+
+impl Grid {
+    fn covering(positions: &[Coordinate]) -> Self {
+        let (mut min, mut max) = (positions[0], positions[0]);
+        for position in positions {
+            min = Coordinate { x: min.x.min(position.x), y: min.y.min(position.y), z: min.z.min(position.z) };
+            max = Coordinate { x: max.x.max(position.x), y: max.y.max(position.y), z: max.z.max(position.z) };
+        }
+
+        let size = (max.x - min.x).max(max.y - min.y).max(max.z - min.z) + 1.; // with a margin keeping the bodies off the far edges
+        let mut grid = Self { origin: min, size, depth: MIN_LEVEL, flat: max.z == min.z };
+        while grid.count(grid.depth) * BODIES_PER_LEAF < positions.len() { grid.depth += 1; }
+        grid
+    }
+
+    fn side(level: u32) -> usize {
+        1 << level
+    }
+
+    fn layers(&self, level: u32) -> usize {
+        if self.flat { 1 } else { Self::side(level) }
+    }
+
+    fn count(&self, level: u32) -> usize {
+        Self::side(level) * Self::side(level) * self.layers(level)
+    }
+
+    fn cells(&self, level: u32) -> impl Iterator<Item = Cell> {
+        let side = Self::side(level);
+        (0..self.layers(level)).flat_map(move |z| (0..side).flat_map(move |y| (0..side).map(move |x| (x, y, z))))
+    }
+
+    fn center(&self, level: u32, (x, y, z): Cell) -> Coordinate {
+        let cell_size = self.size / Self::side(level) as Real;
+        let at = |origin: Real, index: usize| origin + (index as Real + 0.5) * cell_size;
+        Coordinate { x: at(self.origin.x, x), y: at(self.origin.y, y), z: if self.flat { self.origin.z } else { at(self.origin.z, z) } }
+    }
+
+    fn leaf_of(&self, position: Coordinate) -> Cell {
+        let side = Self::side(self.depth);
+        let cell = |offset: Real| ((offset / self.size * side as Real) as usize).min(side - 1);
+        (cell(position.x - self.origin.x), cell(position.y - self.origin.y), cell(position.z - self.origin.z))
+    }
+
+    fn index(level: u32, (x, y, z): Cell) -> usize {
+        (z * Self::side(level) + y) * Self::side(level) + x
+    }
+
+    // of the given cell, at the next level
+    fn children(&self, level: u32, (x, y, z): Cell) -> impl Iterator<Item = Cell> {
+        (2 * z..(2 * z + 2).min(self.layers(level + 1)))
+            .flat_map(move |z| IntoIterator::into_iter([(2 * x, 2 * y, z), (2 * x + 1, 2 * y, z), (2 * x, 2 * y + 1, z), (2 * x + 1, 2 * y + 1, z)]))
+    }
+
+    // of the given cell, itself included
+    fn neighbours(&self, level: u32, (x, y, z): Cell) -> impl Iterator<Item = Cell> {
+        let (side, layers) = (Self::side(level), self.layers(level));
+        let around = |center: usize, count: usize| center.saturating_sub(SEPARATION)..=(center + SEPARATION).min(count - 1);
+        around(z, layers).flat_map(move |nz| around(y, side).flat_map(move |ny| around(x, side).map(move |nx| (nx, ny, nz))))
+    }
+
+    fn are_neighbours(first: Cell, second: Cell) -> bool {
+        first.0.abs_diff(second.0) <= SEPARATION && first.1.abs_diff(second.1) <= SEPARATION && first.2.abs_diff(second.2) <= SEPARATION
+    }
+}
+
+// Fast multipole method: far away cells interact with one another through their multipoles, rather than
+// with every body; the resulting field is passed down the grid and evaluated at the bodies, leaving only
+// the neighbouring bodies to be summed up directly.
+pub struct FastMultipole;
+
+impl ForceSolver for FastMultipole {
+    fn accelerations(&self, bodies: &[Body], positions: &[Coordinate], constants: &Constants) -> Vec<EuclideanVector> {
+        if positions.is_empty() { return Vec::new(); }
+
+        let grid = Grid::covering(positions);
+        let depth = grid.depth;
+
+        let mut leaves = vec![Vec::new(); grid.count(depth)];
+        let mut multipoles: Vec<Vec<Multipole>> = (0..=depth).map(|level| vec![Multipole::EMPTY; grid.count(level)]).collect();
+        for (i, (body, &position)) in bodies.iter().zip(positions).enumerate() {
+            let leaf = grid.leaf_of(position);
+            leaves[Grid::index(depth, leaf)].push(i);
+            multipoles[depth as usize][Grid::index(depth, leaf)].add(position - grid.center(depth, leaf), body.mass);
+        }
+
+        for level in (0..depth).rev() {
+            for cell in grid.cells(level) {
+                let center = grid.center(level, cell);
+                for child in grid.children(level, cell) {
+                    let moments = multipoles[level as usize + 1][Grid::index(level + 1, child)];
+                    multipoles[level as usize][Grid::index(level, cell)].add_shifted(&moments, grid.center(level + 1, child) - center);
+                }
+            }
+        }
+
+        let mut locals: Vec<Local> = vec![Local::ZERO; 1];
+        for level in MIN_LEVEL..=depth {
+            let parents = locals;
+            locals = vec![Local::ZERO; grid.count(level)];
+
+            for cell in grid.cells(level).filter(|&cell| multipoles[level as usize][Grid::index(level, cell)].count > 0) {
+                let center = grid.center(level, cell);
+                let parent = (cell.0 / 2, cell.1 / 2, cell.2 / 2);
+                let mut local = if level > MIN_LEVEL {
+                    parents[Grid::index(level - 1, parent)].shifted(center - grid.center(level - 1, parent))
+                } else {
+                    Local::ZERO
+                };
+
+                let well_separated = grid.neighbours(level - 1, parent)
+                    .flat_map(|neighbour| grid.children(level - 1, neighbour))
+                    .filter(|&source| !Grid::are_neighbours(source, cell));
+                for source in well_separated {
+                    let moments = &multipoles[level as usize][Grid::index(level, source)];
+                    if moments.count == 0 { continue; }
+                    let offset = center - grid.center(level, source);
+                    local.acceleration += moments.acceleration_at(offset) * constants.gravitational;
+                    local.gradient.add(moments.gradient_at(offset), constants.gravitational);
+                    local.curvature.add(moments.curvature_at(offset), constants.gravitational);
+                }
+                locals[Grid::index(level, cell)] = local;
+            }
+        }
+
+        positions.iter().enumerate().map(|(i, &position)| {
+            let leaf = grid.leaf_of(position);
+            let far = locals[Grid::index(depth, leaf)].at(position - grid.center(depth, leaf));
+            grid.neighbours(depth, leaf)
+                .flat_map(|neighbour| &leaves[Grid::index(depth, neighbour)])
+                .filter(|&&j| j != i)
+                .fold(far, |sum, &j| sum + gravitational_pull(position, positions[j], bodies[j].mass, constants))
+        }).collect()
+    }
+}
+
+impl std::fmt::Display for FastMultipole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "fast multipole")
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::fmm::FastMultipole;
+    use crate::physics::{Body, Constants, DirectSummation, ForceSolver};
+
+    fn cluster(count: u32) -> Vec<Body> {
+        (0..count).map(|i| {
+            let i = i as Real;
+            Body::new().with_mass(1. + i % 3.).at(Coordinate { x: (i * 37.) % 101., y: (i * 53.) % 97., z: 0. })
+        }).collect()
+    }
+
+    // the same, spread out in depth as well
+    fn cloud(count: u32) -> Vec<Body> {
+        cluster(count).into_iter().enumerate().map(|(i, body)| {
+            let position = Coordinate { z: (i as Real * 71.) % 89., ..body.position };
+            body.at(position)
+        }).collect()
+    }
+
+    fn positions_of(bodies: &[Body]) -> Vec<Coordinate> {
+        bodies.iter().map(|body| body.position).collect()
+    }
+
+    fn largest_difference(lhs: &[EuclideanVector], rhs: &[EuclideanVector]) -> Real {
+        lhs.iter().zip(rhs).map(|(&l, &r)| (l - r).magnitude() / r.magnitude()).fold(0., Real::max)
+    }
+
+    #[test]
+    fn multipoles_approximate_direct_summation() {
+        let bodies = cluster(50);
+        let positions = positions_of(&bodies);
+        let exact = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+        let approximated = FastMultipole.accelerations(&bodies, &positions, &Constants::new());
+        assert!(largest_difference(&approximated, &exact) < 0.01);
+    }
+
+    // deep enough for the field of far away cells to be passed down several levels
+    #[test]
+    fn multipoles_approximate_direct_summation_of_many_bodies() {
+        let bodies = cluster(500);
+        let positions = positions_of(&bodies);
+        let exact = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+        let approximated = FastMultipole.accelerations(&bodies, &positions, &Constants::new());
+        assert!(largest_difference(&approximated, &exact) < 0.1);
+    }
+
+    #[test]
+    fn multipoles_approximate_direct_summation_in_three_dimensions() {
+        for &(count, tolerance) in &[(50, 0.02), (500, 0.1)] {
+            let bodies = cloud(count);
+            let positions = positions_of(&bodies);
+            let exact = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+            let approximated = FastMultipole.accelerations(&bodies, &positions, &Constants::new());
+            assert!(largest_difference(&approximated, &exact) < tolerance, "{} bodies", count);
+        }
+    }
+
+    #[test]
+    fn bodies_in_a_plane_off_the_origin_are_kept_in_it() {
+        let bodies: Vec<Body> = cluster(500).into_iter().map(|body| { let position = Coordinate { z: 1e3, ..body.position }; body.at(position) }).collect();
+        let positions = positions_of(&bodies);
+        let exact = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+        let approximated = FastMultipole.accelerations(&bodies, &positions, &Constants::new());
+        assert!(largest_difference(&approximated, &exact) < 0.1);
+        assert!(approximated.iter().all(|acceleration| acceleration.dz == 0.));
+    }
+
+    #[test]
+    fn lonely_body_is_not_pulled_by_itself() {
+        let bodies = vec![Body::new().with_mass(5.).at(Coordinate { x: 3., y: 4., z: 0. })];
+        let accelerations = FastMultipole.accelerations(&bodies, &positions_of(&bodies), &Constants::new());
+        assert_eq!(accelerations[0], 0.);
+    }
+
+    #[test]
+    fn coinciding_bodies_do_not_break_the_grid() {
+        let bodies = vec![Body::new().with_mass(1.), Body::new().with_mass(1.), Body::new().with_mass(1.).at(Coordinate { x: 10., y: 0., z: 0. })];
+        let accelerations = FastMultipole.accelerations(&bodies, &positions_of(&bodies), &Constants::new());
+        assert!(accelerations[0].dx > 0.);
+        assert!(accelerations[2].dx < 0.);
+    }
+}
@@ -0,0 +1,45 @@
+// Direct summation of the pulls on every body, one invocation per body. The bodies are pulled through workgroup
+// memory a tile at a time, each of them loaded once per workgroup rather than once per invocation; the tiles are
+// padded with massless bodies, which pull with nothing.
+
+struct Parameters {
+    count: u32,
+    softening_squared: f32,
+    gravitational: f32,
+    padding: f32,
+}
+
+@group(0) @binding(0) var<uniform> parameters: Parameters;
+@group(0) @binding(1) var<storage, read> bodies: array<vec4<f32>>; // position, and mass in w
+@group(0) @binding(2) var<storage, read_write> accelerations: array<vec4<f32>>; // nothing in w
+
+const WORKGROUP_SIZE: u32 = 64u;
+
+var<workgroup> tile: array<vec4<f32>, WORKGROUP_SIZE>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) global: vec3<u32>, @builtin(local_invocation_id) local: vec3<u32>) {
+    let index = global.x;
+    var position = vec3<f32>(0.0);
+    if (index < parameters.count) { position = bodies[index].xyz; }
+
+    var sum = vec3<f32>(0.0);
+    for (var start = 0u; start < parameters.count; start += WORKGROUP_SIZE) {
+        let loaded = start + local.x;
+        tile[local.x] = vec4<f32>(0.0);
+        if (loaded < parameters.count) { tile[local.x] = bodies[loaded]; }
+        workgroupBarrier();
+
+        for (var k = 0u; k < WORKGROUP_SIZE; k++) {
+            let offset = tile[k].xyz - position;
+            let distance_squared = dot(offset, offset);
+            // the body itself, or one coinciding with it, pulls with nothing
+            if (distance_squared > 0.0) {
+                sum += offset * (tile[k].w / ((distance_squared + parameters.softening_squared) * sqrt(distance_squared)));
+            }
+        }
+        workgroupBarrier();
+    }
+
+    if (index < parameters.count) { accelerations[index] = vec4<f32>(sum * parameters.gravitational, 0.0); }
+}
@@ -0,0 +1,290 @@
+use crate::maths::{CompensatedSum, Coordinate, EuclideanVector, Real};
+use crate::physics::orbit::{self, Elements};
+use crate::physics::{barycenter, conics, Body, Constants, ForceModel, ForceSolver};
+
+pub const NOMINAL_TIME_STEP: Real = 1.;
+const MAX_SUBSTEPS: u32 = 256;
+const MAX_ENCOUNTER_SUBSTEPS: u32 = 4096; // for bodies in close encounters, which would not survive coarser ones
+const MAX_LEVEL: u32 = 12; // of block time steps, so that they get as short as MAX_ENCOUNTER_SUBSTEPS allows
+const SUBSTEP_TOLERANCE: Real = 0.05; // fraction of a pair's separation that may be covered within a single substep
+const ENCOUNTER_RADII: Real = 5.; // bodies closer than that many times their radii combined are in a close encounter
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Integrator {
+    Euler,
+    RungeKutta4,
+    Leapfrog,
+    BlockLeapfrog,
+    Kepler,
+    PatchedConics,
+}
+
+serializable!(enum Integrator { Euler, RungeKutta4, Leapfrog, BlockLeapfrog, Kepler, PatchedConics });
+
+impl Integrator {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Euler => Self::RungeKutta4,
+            Self::RungeKutta4 => Self::Leapfrog,
+            Self::Leapfrog => Self::BlockLeapfrog,
+            Self::BlockLeapfrog => Self::Kepler,
+            Self::Kepler => Self::PatchedConics,
+            Self::PatchedConics => Self::Euler,
+        }
+    }
+
+    // i.e. retraces its own steps exactly when run with a negated time step
+    pub const fn is_time_symmetric(self) -> bool {
+        matches!(self, Self::Leapfrog | Self::Kepler)
+    }
+
+    // the block time steps subdivide the step on their own, each body as much as it needs
+    pub fn substeps_needed(self, bodies: &[Body], models: &[Box<dyn ForceModel>], constants: &Constants, time_step: Real) -> u32 {
+        match self {
+            Self::BlockLeapfrog | Self::PatchedConics => 1,
+            Self::Kepler if is_keplerian(bodies, models, constants) => 1,
+            _ => substeps_needed(bodies, constants, time_step),
+        }
+    }
+
+    pub fn advance(self, bodies: &mut [Body], solver: &dyn ForceSolver, models: &[Box<dyn ForceModel>], constants: &Constants, time_step: Real) {
+        match self {
+            Self::Euler => advance_euler(bodies, solver, models, constants, time_step),
+            Self::RungeKutta4 => advance_runge_kutta(bodies, solver, models, constants, time_step),
+            Self::Leapfrog => advance_leapfrog(bodies, solver, models, constants, time_step),
+            Self::BlockLeapfrog => advance_block_leapfrog(bodies, solver, models, constants, time_step),
+            Self::Kepler => advance_kepler(bodies, solver, models, constants, time_step),
+            Self::PatchedConics => {
+                conics::advance(bodies, constants, time_step);
+                solver.refresh_forces(bodies, constants);
+            }
+        }
+
+        for body in bodies.iter_mut() {
+            body.turn(time_step);
+        }
+        for engine in bodies.iter_mut().filter_map(|body| body.engine.as_mut()) {
+            engine.burn(time_step);
+        }
+    }
+}
+
+impl std::fmt::Display for Integrator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::Euler => write!(f, "Euler"),
+            Self::RungeKutta4 => write!(f, "RK4"),
+            Self::Leapfrog => write!(f, "leapfrog"),
+            Self::BlockLeapfrog => write!(f, "leapfrog with block time steps"),
+            Self::Kepler => write!(f, "Kepler (analytic)"),
+            Self::PatchedConics => write!(f, "patched conics"),
+        }
+    }
+}
+
+pub fn substeps_needed(bodies: &[Body], constants: &Constants, time_step: Real) -> u32 {
+    substeps_needed_by_each(bodies, constants, time_step).into_iter().max().unwrap_or(1)
+}
+
+// by the closest encounter of each body; the steps of bodies in close encounters collapse further still,
+// down to what their coming periapsis needs, so that the pass is resolved before it happens
+fn substeps_needed_by_each(bodies: &[Body], constants: &Constants, time_step: Real) -> Vec<u32> {
+    let mut needed: Vec<Real> = vec![1.; bodies.len()];
+    let mut limits: Vec<u32> = vec![MAX_SUBSTEPS; bodies.len()];
+
+    for (i, body) in bodies.iter().enumerate() {
+        for (j, other) in bodies.iter().enumerate().skip(i + 1) {
+            let mut separation = (other.position - body.position).magnitude();
+            let relative_speed = (other.velocity - body.velocity).magnitude();
+            if separation < ENCOUNTER_RADII * (body.radius + other.radius) {
+                separation = separation.min(closest_approach(other, body, constants, time_step));
+                limits[i] = MAX_ENCOUNTER_SUBSTEPS;
+                limits[j] = MAX_ENCOUNTER_SUBSTEPS;
+            }
+            let relative_acceleration = constants.gravitational * (body.mass + other.mass) / (separation * separation);
+            let allowed_displacement = SUBSTEP_TOLERANCE * separation;
+
+            let pair = Real::max(
+                relative_speed * time_step.abs() / allowed_displacement,
+                (relative_acceleration / allowed_displacement).sqrt() * time_step.abs(),
+            );
+            needed[i] = needed[i].max(pair);
+            needed[j] = needed[j].max(pair);
+        }
+    }
+
+    needed.iter().zip(limits).map(|(needed, limit)| needed.ceil().min(limit as Real) as u32).collect()
+}
+
+// within the coming step, being the periapsis of the pair if they pass it by then
+fn closest_approach(body: &Body, other: &Body, constants: &Constants, time_step: Real) -> Real {
+    let (position, velocity) = (body.position - other.position, body.velocity - other.velocity);
+    let separation = position.magnitude();
+    let closing_speed = -position.dot(velocity) / separation * time_step.signum();
+    if closing_speed <= 0. || separation / closing_speed > time_step.abs() { return separation; }
+
+    let periapsis = Elements::of(body, other, constants).periapsis;
+    if periapsis.is_finite() { periapsis.max(0.) } else { separation }
+}
+
+fn advance_euler(bodies: &mut [Body], solver: &dyn ForceSolver, models: &[Box<dyn ForceModel>], constants: &Constants, time_step: Real) {
+    for body in bodies.iter_mut() {
+        body.update(time_step);
+    }
+
+    solver.refresh_forces(bodies, constants);
+
+    let positions: Vec<Coordinate> = bodies.iter().map(|body| body.position).collect();
+    let velocities: Vec<EuclideanVector> = bodies.iter().map(|body| body.velocity).collect();
+    let perturbations = perturbations(bodies, &positions, &velocities, models, constants);
+    for (body, acceleration) in bodies.iter_mut().zip(perturbations) {
+        if acceleration != 0. { body.forces.push(acceleration * body.mass); }
+    }
+}
+
+fn advance_runge_kutta(bodies: &mut [Body], solver: &dyn ForceSolver, models: &[Box<dyn ForceModel>], constants: &Constants, dt: Real) {
+    let positions: Vec<Coordinate> = bodies.iter().map(|body| body.position).collect();
+    let velocities: Vec<EuclideanVector> = bodies.iter().map(|body| body.velocity).collect();
+    let accelerations = |positions: &[Coordinate], velocities: &[EuclideanVector]| -> Vec<EuclideanVector> {
+        let gravity = solver.accelerations(bodies, positions, constants);
+        let perturbations = perturbations(bodies, positions, velocities, models, constants);
+        gravity.iter().zip(perturbations).map(|(&gravity, perturbation)| gravity + perturbation).collect()
+    };
+
+    let k1_velocities = velocities.clone();
+    let k1_accelerations = accelerations(&positions, &k1_velocities);
+
+    let k2_velocities = shifted(&velocities, &k1_accelerations, dt / 2.);
+    let k2_accelerations = accelerations(&displaced(&positions, &k1_velocities, dt / 2.), &k2_velocities);
+
+    let k3_velocities = shifted(&velocities, &k2_accelerations, dt / 2.);
+    let k3_accelerations = accelerations(&displaced(&positions, &k2_velocities, dt / 2.), &k3_velocities);
+
+    let k4_velocities = shifted(&velocities, &k3_accelerations, dt);
+    let k4_accelerations = accelerations(&displaced(&positions, &k3_velocities, dt), &k4_velocities);
+
+    for (i, body) in bodies.iter_mut().enumerate() {
+        body.displace(dt / 6. * (k1_velocities[i] + 2. * k2_velocities[i] + 2. * k3_velocities[i] + k4_velocities[i]));
+        body.accelerate(dt / 6. * (k1_accelerations[i] + 2. * k2_accelerations[i] + 2. * k3_accelerations[i] + k4_accelerations[i]));
+    }
+
+    // forces are not used by this integrator, but are kept up to date so that they can still be displayed
+    solver.refresh_forces(bodies, constants);
+}
+
+// kick-drift-kick, symmetric in time as long as the accelerations do not depend on velocities
+fn advance_leapfrog(bodies: &mut [Body], solver: &dyn ForceSolver, models: &[Box<dyn ForceModel>], constants: &Constants, dt: Real) {
+    let everyone: Vec<usize> = (0..bodies.len()).collect();
+
+    let initial_accelerations = accelerations_of(bodies, solver, models, constants, &everyone);
+    for (body, acceleration) in bodies.iter_mut().zip(initial_accelerations) {
+        body.accelerate(acceleration * (dt / 2.));
+        body.displace(body.velocity * dt);
+    }
+    let final_accelerations = accelerations_of(bodies, solver, models, constants, &everyone);
+    for (body, acceleration) in bodies.iter_mut().zip(final_accelerations) {
+        body.accelerate(acceleration * (dt / 2.));
+    }
+
+    solver.refresh_forces(bodies, constants);
+}
+
+// Hierarchical time steps: each body gets the longest step of dt/2^level short enough for its closest encounter,
+// so that only the bodies in tight encounters are kicked often. All of them drift together at the finest level.
+fn advance_block_leapfrog(bodies: &mut [Body], solver: &dyn ForceSolver, models: &[Box<dyn ForceModel>], constants: &Constants, dt: Real) {
+    let levels: Vec<u32> = substeps_needed_by_each(bodies, constants, dt).iter()
+        .map(|substeps| substeps.next_power_of_two().trailing_zeros().min(MAX_LEVEL))
+        .collect();
+    let deepest = levels.iter().copied().max().unwrap_or(0);
+    let fine_step = dt / (1_u32 << deepest) as Real;
+    let strides: Vec<u32> = levels.iter().map(|level| 1 << (deepest - level)).collect();
+    let half_step = |i: usize| fine_step * strides[i] as Real / 2.;
+
+    let everyone: Vec<usize> = (0..bodies.len()).collect();
+    let mut accelerations = accelerations_of(bodies, solver, models, constants, &everyone);
+
+    for step in 0..1_u32 << deepest {
+        for (i, body) in bodies.iter_mut().enumerate().filter(|&(i, _)| step % strides[i] == 0) {
+            body.accelerate(accelerations[i] * half_step(i));
+        }
+        for body in bodies.iter_mut() {
+            body.displace(body.velocity * fine_step);
+        }
+
+        let finishing: Vec<usize> = (0..bodies.len()).filter(|&i| (step + 1) % strides[i] == 0).collect();
+        for (&i, acceleration) in finishing.iter().zip(accelerations_of(bodies, solver, models, constants, &finishing)) {
+            accelerations[i] = acceleration;
+            bodies[i].accelerate(acceleration * half_step(i));
+        }
+    }
+
+    solver.refresh_forces(bodies, constants);
+}
+
+// Two bodies with nothing else disturbing them are propagated exactly, by solving Kepler's equation
+// for their relative orbit while their barycenter drifts along. Anything else falls back to the leapfrog.
+fn advance_kepler(bodies: &mut [Body], solver: &dyn ForceSolver, models: &[Box<dyn ForceModel>], constants: &Constants, dt: Real) {
+    let relative = match &*bodies {
+        [primary, body] if is_keplerian(bodies, models, constants) => orbit::propagated(body, primary, constants, dt),
+        _ => None,
+    };
+    let (position, velocity) = match relative {
+        Some(state) => state,
+        None => return advance_leapfrog(bodies, solver, models, constants, dt),
+    };
+
+    let total_mass = bodies[0].mass + bodies[1].mass;
+    let shares = [-bodies[1].mass / total_mass, bodies[0].mass / total_mass];
+    let barycentric_velocity = (bodies[0].momentum() + bodies[1].momentum()) / total_mass;
+    let barycenter = barycenter(bodies) + barycentric_velocity * dt;
+
+    for (body, share) in bodies.iter_mut().zip(IntoIterator::into_iter(shares)) {
+        body.displace(barycenter + position * share - body.position);
+        body.accelerate(barycentric_velocity + velocity * share - body.velocity);
+    }
+
+    solver.refresh_forces(bodies, constants);
+}
+
+// i.e. a lone pair moving under nothing but their mutual gravity
+fn is_keplerian(bodies: &[Body], models: &[Box<dyn ForceModel>], constants: &Constants) -> bool {
+    bodies.len() == 2 && !models.iter().any(|model| model.acts_on(bodies, constants))
+}
+
+// of the given bodies, perturbations included
+fn accelerations_of(bodies: &[Body], solver: &dyn ForceSolver, models: &[Box<dyn ForceModel>], constants: &Constants, indices: &[usize]) -> Vec<EuclideanVector> {
+    let positions: Vec<Coordinate> = bodies.iter().map(|body| body.position).collect();
+    let velocities: Vec<EuclideanVector> = bodies.iter().map(|body| body.velocity).collect();
+    let gravity = solver.accelerations_of(bodies, &positions, indices, constants);
+    let perturbations = perturbations(bodies, &positions, &velocities, models, constants);
+    indices.iter().zip(gravity).map(|(&i, gravity)| gravity + perturbations[i]).collect()
+}
+
+// accelerations that do not come from plain Newtonian gravity, from the models acting on the bodies in the given state
+fn perturbations(bodies: &[Body], positions: &[Coordinate], velocities: &[EuclideanVector], models: &[Box<dyn ForceModel>], constants: &Constants) -> Vec<EuclideanVector> {
+    let mut result = vec![EuclideanVector { dx: 0., dy: 0., dz: 0. }; bodies.len()];
+    let active: Vec<&dyn ForceModel> = models.iter().map(AsRef::as_ref).filter(|model| model.acts_on(bodies, constants)).collect();
+    if active.is_empty() { return result; }
+
+    // the body acted upon is swapped to the front, to tell it apart from the others without copying them
+    let mut state: Vec<Body> = bodies.iter().zip(positions).zip(velocities)
+        .map(|((body, &position), &velocity)| Body { position, velocity, ..body.clone() })
+        .collect();
+    for (i, acceleration) in result.iter_mut().enumerate() {
+        state.swap(0, i);
+        if let Some((body, others)) = state.split_first().filter(|(body, _)| body.mass > 0.) {
+            *acceleration = CompensatedSum::of(active.iter().map(|model| model.force_on(body, others, constants))) / body.mass;
+        }
+        state.swap(0, i);
+    }
+
+    result
+}
+
+fn displaced(positions: &[Coordinate], velocities: &[EuclideanVector], dt: Real) -> Vec<Coordinate> {
+    positions.iter().zip(velocities).map(|(&position, &velocity)| position + velocity * dt).collect()
+}
+
+fn shifted(velocities: &[EuclideanVector], accelerations: &[EuclideanVector], dt: Real) -> Vec<EuclideanVector> {
+    velocities.iter().zip(accelerations).map(|(&velocity, &acceleration)| velocity + acceleration * dt).collect()
+}
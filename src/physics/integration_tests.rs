@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::integration::{substeps_needed, Integrator, NOMINAL_TIME_STEP};
+    use crate::physics::orbit::Elements;
+    use crate::physics::{barycenter, Body, Constants, DirectSummation, standard_models};
+
+    const CENTRAL_MASS: Real = 100.;
+    const ORBIT_RADIUS: Real = 100.;
+
+    fn circular_orbit() -> Vec<Body> {
+        let orbital_speed = (Constants::new().gravitational * CENTRAL_MASS / ORBIT_RADIUS).sqrt();
+        vec![
+            Body::new().with_mass(CENTRAL_MASS).at(Coordinate { x: 0., y: 0., z: 0. }),
+            Body::new().with_mass(0.001).at(Coordinate { x: ORBIT_RADIUS, y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: orbital_speed, dz: 0. }),
+        ]
+    }
+
+    fn radius_drift_after(integrator: Integrator, steps: usize) -> Real {
+        let mut bodies = circular_orbit();
+        for _ in 0..steps { integrator.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP); }
+        ((bodies[1].position - bodies[0].position).magnitude() - ORBIT_RADIUS).abs()
+    }
+
+    #[test]
+    fn integrators_cycle_through_all_variants() {
+        assert_eq!(Integrator::Euler.next(), Integrator::RungeKutta4);
+        assert_eq!(Integrator::RungeKutta4.next(), Integrator::Leapfrog);
+        assert_eq!(Integrator::Leapfrog.next(), Integrator::BlockLeapfrog);
+        assert_eq!(Integrator::BlockLeapfrog.next(), Integrator::Kepler);
+        assert_eq!(Integrator::Kepler.next(), Integrator::PatchedConics);
+        assert_eq!(Integrator::PatchedConics.next(), Integrator::Euler);
+    }
+
+    #[test]
+    fn runge_kutta_keeps_circular_orbit_radius() {
+        assert!(radius_drift_after(Integrator::RungeKutta4, 1000) < 0.1);
+    }
+
+    #[test]
+    fn runge_kutta_drifts_less_than_euler() {
+        assert!(radius_drift_after(Integrator::RungeKutta4, 1000) < radius_drift_after(Integrator::Euler, 1000));
+    }
+
+    #[test]
+    fn runge_kutta_keeps_forces_up_to_date_for_display() {
+        let mut bodies = circular_orbit();
+        Integrator::RungeKutta4.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP);
+        assert_eq!(bodies[0].forces.len(), 1);
+        assert_eq!(bodies[1].forces.len(), 1);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn leapfrog_retraces_its_steps_when_time_is_reversed() {
+        let mut bodies = circular_orbit();
+        for _ in 0..100 { Integrator::Leapfrog.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP); }
+        for _ in 0..100 { Integrator::Leapfrog.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), -NOMINAL_TIME_STEP); }
+        let initial = circular_orbit();
+        assert!(bodies[1].position.approx_eq(initial[1].position, 1e-9));
+        assert!(bodies[1].velocity.approx_eq(initial[1].velocity, 1e-9));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn kepler_keeps_eccentric_orbit_to_rounding_errors() {
+        let mut bodies = circular_orbit();
+        bodies[1].velocity *= 1.3;
+        let period = Elements::of(&bodies[1], &bodies[0], &Constants::new()).period.unwrap();
+        let steps = 10;
+        for _ in 0..steps { Integrator::Kepler.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), period / steps as Real); }
+
+        let initial = circular_orbit();
+        let drift = initial[1].momentum() * 1.3 / (CENTRAL_MASS + initial[1].mass) * period;
+        assert!((bodies[1].position - bodies[0].position).approx_eq(initial[1].position - initial[0].position, 1e-9));
+        assert!((bodies[1].velocity - bodies[0].velocity).approx_eq(initial[1].velocity * 1.3, 1e-9));
+        assert!(barycenter(&bodies).approx_eq(barycenter(&initial) + drift, 1e-9));
+    }
+
+    #[test]
+    fn kepler_falls_back_to_leapfrog_for_more_bodies() {
+        let mut numeric = circular_orbit();
+        numeric.push(Body::new().with_mass(1.).at(Coordinate { x: -500., y: 0., z: 0. }));
+        let mut analytic = numeric.clone();
+        Integrator::Leapfrog.advance(&mut numeric, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP);
+        Integrator::Kepler.advance(&mut analytic, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP);
+        assert_eq!(numeric[1].position - analytic[1].position, 0.);
+    }
+
+    #[test]
+    fn block_time_steps_are_plain_leapfrog_when_no_body_needs_substeps() {
+        let (mut plain, mut block) = (circular_orbit(), circular_orbit());
+        Integrator::Leapfrog.advance(&mut plain, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP);
+        Integrator::BlockLeapfrog.advance(&mut block, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP);
+        assert_eq!(plain[1].position - block[1].position, 0.);
+        assert_eq!(plain[1].velocity, block[1].velocity);
+    }
+
+    #[test]
+    fn block_time_steps_follow_tight_binary_without_global_substeps() {
+        let separation = 2.;
+        let orbital_speed = (Constants::new().gravitational * 10. / (2. * separation)).sqrt();
+        let mut bodies = vec![
+            Body::new().with_mass(10.).at(Coordinate { x: -separation / 2., y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: -orbital_speed, dz: 0. }),
+            Body::new().with_mass(10.).at(Coordinate { x: separation / 2., y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: orbital_speed, dz: 0. }),
+            Body::new().with_mass(0.001).at(Coordinate { x: 1000., y: 0., z: 0. }),
+        ];
+        assert_eq!(Integrator::BlockLeapfrog.substeps_needed(&bodies, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP), 1);
+
+        for _ in 0..20 { Integrator::BlockLeapfrog.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP); }
+        assert!(((bodies[1].position - bodies[0].position).magnitude() - separation).abs() < 0.01);
+    }
+
+    #[test]
+    fn distant_slow_bodies_need_no_substeps() {
+        assert_eq!(substeps_needed(&circular_orbit(), &Constants::new(), NOMINAL_TIME_STEP), 1);
+    }
+
+    #[test]
+    fn close_encounter_is_subdivided() {
+        let bodies = vec![
+            Body::new().with_mass(10.).at(Coordinate { x: 0., y: 0., z: 0. }),
+            Body::new().with_mass(10.).at(Coordinate { x: 2., y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: 3., dz: 0. }),
+        ];
+        assert!(substeps_needed(&bodies, &Constants::new(), NOMINAL_TIME_STEP) > 1);
+    }
+
+    #[test]
+    fn substeps_are_capped_for_coinciding_bodies() {
+        let bodies = vec![Body::new().with_mass(10.), Body::new().with_mass(10.)];
+        assert_eq!(substeps_needed(&bodies, &Constants::new(), NOMINAL_TIME_STEP), 4096);
+    }
+
+    #[test]
+    fn substeps_collapse_ahead_of_a_close_pass() {
+        let approaching = vec![
+            Body::new().with_mass(10.).at(Coordinate { x: 0., y: 0., z: 0. }),
+            Body::new().with_mass(10.).at(Coordinate { x: 4., y: 0., z: 0. }).moving(EuclideanVector { dx: -8., dy: 0.1, dz: 0. }),
+        ];
+        let mut receding = approaching.clone();
+        receding[1].velocity = -receding[1].velocity;
+        assert!(substeps_needed(&approaching, &Constants::new(), NOMINAL_TIME_STEP) > 256);
+        assert!(substeps_needed(&receding, &Constants::new(), NOMINAL_TIME_STEP) <= 256);
+        assert!(substeps_needed(&receding, &Constants::new(), -NOMINAL_TIME_STEP) > 256);
+    }
+}
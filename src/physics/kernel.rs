@@ -0,0 +1,174 @@
+use crate::maths::{Coordinate, EuclideanVector, Real};
+use crate::physics::{Body, Constants, ForceSolver};
+
+const LANES: usize = 4; // of AVX on f64, and of SSE on f32
+
+type Lanes = ([Real; LANES], [Real; LANES], [Real; LANES]); // partial sums of the pulls along x, y and z
+
+// Positions and masses of bodies laid out as structure of arrays, so that the inner loop of the pairwise summation
+// walks contiguous memory in fixed-width lanes without branches. With the simd feature on x86-64 the lanes are
+// explicit vector instructions, and otherwise the same loop written out in scalars, left to the optimizer.
+pub struct Particles {
+    xs: Vec<Real>,
+    ys: Vec<Real>,
+    zs: Vec<Real>,
+    masses: Vec<Real>,
+}
+
+impl Particles {
+    pub fn of(bodies: &[Body], positions: &[Coordinate]) -> Self {
+        Self {
+            xs: positions.iter().map(|position| position.x).collect(),
+            ys: positions.iter().map(|position| position.y).collect(),
+            zs: positions.iter().map(|position| position.z).collect(),
+            masses: bodies.iter().map(|body| body.mass).collect(),
+        }
+    }
+
+    pub fn acceleration_at(&self, position: Coordinate, constants: &Constants) -> EuclideanVector {
+        let softening_squared = constants.softening * constants.softening;
+        let (mut ax, mut ay, mut az) = self.lanes(position, softening_squared);
+
+        let remainder = self.xs.len() - self.xs.len() % LANES;
+        for i in remainder..self.xs.len() {
+            let (x, y, z) = pull(position, softening_squared, self.xs[i], self.ys[i], self.zs[i], self.masses[i]);
+            ax[0] += x;
+            ay[0] += y;
+            az[0] += z;
+        }
+
+        EuclideanVector { dx: ax.iter().sum(), dy: ay.iter().sum(), dz: az.iter().sum() } * constants.gravitational
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64", not(feature = "single-precision")))]
+    fn lanes(&self, position: Coordinate, softening_squared: Real) -> Lanes {
+        if !is_x86_feature_detected!("avx") { return self.scalar_lanes(position, softening_squared); }
+        // safe as the CPU has just been found to support AVX
+        unsafe { simd::lanes(&self.xs, &self.ys, &self.zs, &self.masses, position, softening_squared) }
+    }
+
+    #[cfg(all(feature = "simd", target_arch = "x86_64", feature = "single-precision"))]
+    fn lanes(&self, position: Coordinate, softening_squared: Real) -> Lanes {
+        // safe as every x86-64 CPU supports SSE
+        unsafe { simd::lanes(&self.xs, &self.ys, &self.zs, &self.masses, position, softening_squared) }
+    }
+
+    #[cfg(not(all(feature = "simd", target_arch = "x86_64")))]
+    fn lanes(&self, position: Coordinate, softening_squared: Real) -> Lanes {
+        self.scalar_lanes(position, softening_squared)
+    }
+
+    // of all the whole chunks of lanes, leaving out the remainder
+    #[cfg_attr(all(feature = "simd", target_arch = "x86_64", feature = "single-precision"), allow(dead_code))]
+    fn scalar_lanes(&self, position: Coordinate, softening_squared: Real) -> Lanes {
+        let (mut ax, mut ay, mut az) = ([0.; LANES], [0.; LANES], [0.; LANES]);
+        let chunks = self.xs.chunks_exact(LANES).zip(self.ys.chunks_exact(LANES)).zip(self.zs.chunks_exact(LANES)).zip(self.masses.chunks_exact(LANES));
+        for (((xs, ys), zs), masses) in chunks {
+            for lane in 0..LANES {
+                let (x, y, z) = pull(position, softening_squared, xs[lane], ys[lane], zs[lane], masses[lane]);
+                ax[lane] += x;
+                ay[lane] += y;
+                az[lane] += z;
+            }
+        }
+        (ax, ay, az)
+    }
+}
+
+// of a single body on one at the position, divided by the gravitational constant
+fn pull(position: Coordinate, softening_squared: Real, x: Real, y: Real, z: Real, mass: Real) -> (Real, Real, Real) {
+    let (dx, dy, dz) = (x - position.x, y - position.y, z - position.z);
+    let distance_squared = dx * dx + dy * dy + dz * dz;
+    // masked rather than branched away: the body itself, or one coinciding with it, pulls with nothing,
+    // and the denominator is kept off zero for it so that the mask does not meet an infinity
+    let mask = Real::from(u8::from(distance_squared > 0.));
+    let magnitude = mask * mass / ((distance_squared + softening_squared) * distance_squared.sqrt() + (1. - mask));
+    (dx * magnitude, dy * magnitude, dz * magnitude)
+}
+
+// The scalar lanes above, an instruction for all of them at a time. Masking and all, they take the same steps in the
+// same order, so that the sums come out the same to the last bit.
+#[cfg(all(feature = "simd", target_arch = "x86_64", not(feature = "single-precision")))]
+mod simd {
+    use super::{Lanes, LANES};
+    use crate::maths::{Coordinate, Real};
+    use std::arch::x86_64::*;
+
+    #[target_feature(enable = "avx")]
+    pub unsafe fn lanes(xs: &[Real], ys: &[Real], zs: &[Real], masses: &[Real], position: Coordinate, softening_squared: Real) -> Lanes {
+        let (px, py, pz) = (_mm256_set1_pd(position.x), _mm256_set1_pd(position.y), _mm256_set1_pd(position.z));
+        let (softening_squared, zero, one) = (_mm256_set1_pd(softening_squared), _mm256_setzero_pd(), _mm256_set1_pd(1.));
+        let (mut ax, mut ay, mut az) = (zero, zero, zero);
+
+        for i in (0..xs.len() - xs.len() % LANES).step_by(LANES) {
+            let dx = _mm256_sub_pd(_mm256_loadu_pd(xs.as_ptr().add(i)), px);
+            let dy = _mm256_sub_pd(_mm256_loadu_pd(ys.as_ptr().add(i)), py);
+            let dz = _mm256_sub_pd(_mm256_loadu_pd(zs.as_ptr().add(i)), pz);
+            let distance_squared = _mm256_add_pd(_mm256_add_pd(_mm256_mul_pd(dx, dx), _mm256_mul_pd(dy, dy)), _mm256_mul_pd(dz, dz));
+
+            let mask = _mm256_and_pd(_mm256_cmp_pd::<_CMP_GT_OQ>(distance_squared, zero), one);
+            let denominator = _mm256_add_pd(_mm256_mul_pd(_mm256_add_pd(distance_squared, softening_squared), _mm256_sqrt_pd(distance_squared)), _mm256_sub_pd(one, mask));
+            let magnitude = _mm256_div_pd(_mm256_mul_pd(mask, _mm256_loadu_pd(masses.as_ptr().add(i))), denominator);
+
+            ax = _mm256_add_pd(ax, _mm256_mul_pd(dx, magnitude));
+            ay = _mm256_add_pd(ay, _mm256_mul_pd(dy, magnitude));
+            az = _mm256_add_pd(az, _mm256_mul_pd(dz, magnitude));
+        }
+
+        let mut sums = ([0.; LANES], [0.; LANES], [0.; LANES]);
+        _mm256_storeu_pd(sums.0.as_mut_ptr(), ax);
+        _mm256_storeu_pd(sums.1.as_mut_ptr(), ay);
+        _mm256_storeu_pd(sums.2.as_mut_ptr(), az);
+        sums
+    }
+}
+
+#[cfg(all(feature = "simd", target_arch = "x86_64", feature = "single-precision"))]
+mod simd {
+    use super::{Lanes, LANES};
+    use crate::maths::{Coordinate, Real};
+    use std::arch::x86_64::*;
+
+    pub unsafe fn lanes(xs: &[Real], ys: &[Real], zs: &[Real], masses: &[Real], position: Coordinate, softening_squared: Real) -> Lanes {
+        let (px, py, pz) = (_mm_set1_ps(position.x), _mm_set1_ps(position.y), _mm_set1_ps(position.z));
+        let (softening_squared, zero, one) = (_mm_set1_ps(softening_squared), _mm_setzero_ps(), _mm_set1_ps(1.));
+        let (mut ax, mut ay, mut az) = (zero, zero, zero);
+
+        for i in (0..xs.len() - xs.len() % LANES).step_by(LANES) {
+            let dx = _mm_sub_ps(_mm_loadu_ps(xs.as_ptr().add(i)), px);
+            let dy = _mm_sub_ps(_mm_loadu_ps(ys.as_ptr().add(i)), py);
+            let dz = _mm_sub_ps(_mm_loadu_ps(zs.as_ptr().add(i)), pz);
+            let distance_squared = _mm_add_ps(_mm_add_ps(_mm_mul_ps(dx, dx), _mm_mul_ps(dy, dy)), _mm_mul_ps(dz, dz));
+
+            let mask = _mm_and_ps(_mm_cmpgt_ps(distance_squared, zero), one);
+            let denominator = _mm_add_ps(_mm_mul_ps(_mm_add_ps(distance_squared, softening_squared), _mm_sqrt_ps(distance_squared)), _mm_sub_ps(one, mask));
+            let magnitude = _mm_div_ps(_mm_mul_ps(mask, _mm_loadu_ps(masses.as_ptr().add(i))), denominator);
+
+            ax = _mm_add_ps(ax, _mm_mul_ps(dx, magnitude));
+            ay = _mm_add_ps(ay, _mm_mul_ps(dy, magnitude));
+            az = _mm_add_ps(az, _mm_mul_ps(dz, magnitude));
+        }
+
+        let mut sums = ([0.; LANES], [0.; LANES], [0.; LANES]);
+        _mm_storeu_ps(sums.0.as_mut_ptr(), ax);
+        _mm_storeu_ps(sums.1.as_mut_ptr(), ay);
+        _mm_storeu_ps(sums.2.as_mut_ptr(), az);
+        sums
+    }
+}
+
+// Exact pairwise summation, like DirectSummation, only laid out for throughput with thousands of bodies.
+pub struct Vectorized;
+
+impl ForceSolver for Vectorized {
+    fn accelerations(&self, bodies: &[Body], positions: &[Coordinate], constants: &Constants) -> Vec<EuclideanVector> {
+        let particles = Particles::of(bodies, positions);
+        positions.iter().map(|&position| particles.acceleration_at(position, constants)).collect()
+    }
+}
+
+impl std::fmt::Display for Vectorized {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "direct (vectorized)")
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::kernel::Vectorized;
+    use crate::physics::{Body, Constants, DirectSummation, ForceSolver};
+    use std::time::Instant;
+
+    // deliberately not a multiple of the lane count, for the remainder to be covered as well
+    fn cluster() -> Vec<Body> {
+        (0..51).map(|i| {
+            let i = i as Real;
+            Body::new().with_mass(1. + i % 3.).at(Coordinate { x: (i * 37.) % 101., y: (i * 53.) % 97., z: 0. })
+        }).collect()
+    }
+
+    // the same, spread out in depth as well
+    fn cloud() -> Vec<Body> {
+        cluster().into_iter().enumerate().map(|(i, body)| {
+            let position = Coordinate { z: (i as Real * 71.) % 89., ..body.position };
+            body.at(position)
+        }).collect()
+    }
+
+    fn positions_of(bodies: &[Body]) -> Vec<Coordinate> {
+        bodies.iter().map(|body| body.position).collect()
+    }
+
+    fn largest_difference(lhs: &[EuclideanVector], rhs: &[EuclideanVector]) -> Real {
+        lhs.iter().zip(rhs).map(|(&l, &r)| (l - r).magnitude() / r.magnitude()).fold(0., Real::max)
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn vectorized_kernel_matches_direct_summation() {
+        let bodies = cluster();
+        let positions = positions_of(&bodies);
+        let exact = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+        let vectorized = Vectorized.accelerations(&bodies, &positions, &Constants::new());
+        assert!(largest_difference(&vectorized, &exact) < 1e-12);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn vectorized_kernel_matches_direct_summation_in_three_dimensions() {
+        let bodies = cloud();
+        let positions = positions_of(&bodies);
+        let exact = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+        let vectorized = Vectorized.accelerations(&bodies, &positions, &Constants::new());
+        assert!(largest_difference(&vectorized, &exact) < 1e-12);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn vectorized_kernel_respects_softening() {
+        let bodies = cluster();
+        let positions = positions_of(&bodies);
+        let constants = Constants { softening: 2., ..Constants::new() };
+        let exact = DirectSummation.accelerations(&bodies, &positions, &constants);
+        let vectorized = Vectorized.accelerations(&bodies, &positions, &constants);
+        assert!(largest_difference(&vectorized, &exact) < 1e-12);
+    }
+
+    #[test]
+    fn coinciding_bodies_do_not_pull_each_other() {
+        // enough of them to fill the lanes as well as the remainder
+        let bodies: Vec<Body> = (0..5).map(|_| Body::new().with_mass(1.)).collect();
+        let accelerations = Vectorized.accelerations(&bodies, &positions_of(&bodies), &Constants::new());
+        assert!(accelerations.iter().all(|&acceleration| acceleration == 0.));
+    }
+
+    // Several thousand bodies, timed through both solvers, each at its best of a few runs. Best run on its own:
+    // cargo test --release --features simd -- --ignored --nocapture vectorized_kernel_outpaces
+    #[test]
+    #[ignore = "a benchmark, meaningful with optimizations only"]
+    fn vectorized_kernel_outpaces_direct_summation() {
+        let bodies: Vec<Body> = (0..3000).map(|i| {
+            let i = i as Real;
+            Body::new().with_mass(1. + i % 3.).at(Coordinate { x: (i * 37.) % 1009., y: (i * 53.) % 997., z: (i * 71.) % 991. })
+        }).collect();
+        let positions = positions_of(&bodies);
+        let timed = |solver: &dyn ForceSolver| (0..5).map(|_| {
+            let start = Instant::now();
+            solver.accelerations(&bodies, &positions, &Constants::new());
+            start.elapsed()
+        }).min().unwrap();
+
+        let (direct, vectorized) = (timed(&DirectSummation), timed(&Vectorized));
+        println!("{} bodies: direct {:?}, vectorized {:?}, {:.1}x as fast", bodies.len(), direct, vectorized, direct.as_secs_f64() / vectorized.as_secs_f64());
+        assert!(vectorized < direct);
+    }
+}
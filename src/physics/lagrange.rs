@@ -0,0 +1,49 @@
+use crate::maths::{Coordinate, Real};
+use crate::physics::{orbit, Body};
+
+const NEWTON_ITERATIONS: usize = 50;
+
+// L1 to L5 of the restricted three-body problem, in the order of their conventional numbering
+pub fn lagrange_points(primary: &Body, secondary: &Body) -> [Coordinate; 5] {
+    let separation = secondary.position - primary.position;
+    let distance = separation.magnitude();
+    let along = separation.versor();
+    let across = orbit::normal(separation, secondary.velocity - primary.velocity).cross(along);
+
+    let mass_ratio = secondary.mass / (primary.mass + secondary.mass);
+    let barycenter = primary.position + separation * mass_ratio;
+    let at = |x: Real, y: Real| barycenter + along * (x * distance) + across * (y * distance);
+
+    let hill = (mass_ratio / 3.).cbrt();
+    let triangular_x = 0.5 - mass_ratio;
+    let triangular_y = Real::sqrt(3.) / 2.;
+
+    [
+        at(collinear_point(mass_ratio, 1. - mass_ratio - hill), 0.),
+        at(collinear_point(mass_ratio, 1. - mass_ratio + hill), 0.),
+        at(collinear_point(mass_ratio, -1. - 5. * mass_ratio / 12.), 0.),
+        at(triangular_x, triangular_y),
+        at(triangular_x, -triangular_y),
+    ]
+}
+
+// Finds where gravity of both bodies balances the centrifugal force on the line joining them, in a frame rotating
+// around their barycenter, with distances expressed in units of their separation.
+fn collinear_point(mass_ratio: Real, initial_guess: Real) -> Real {
+    let primary = -mass_ratio;
+    let secondary = 1. - mass_ratio;
+
+    let mut x = initial_guess;
+    for _ in 0..NEWTON_ITERATIONS {
+        let (to_primary, to_secondary) = (x - primary, x - secondary);
+        let balance = x
+            - (1. - mass_ratio) * to_primary / to_primary.abs().powi(3)
+            - mass_ratio * to_secondary / to_secondary.abs().powi(3);
+        let slope = 1. + 2. * (1. - mass_ratio) / to_primary.abs().powi(3) + 2. * mass_ratio / to_secondary.abs().powi(3);
+
+        let step = balance / slope;
+        x -= step;
+        if step.abs() < 1e-15 { break; }
+    }
+    x
+}
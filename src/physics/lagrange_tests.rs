@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, Real};
+    use crate::physics::lagrange::lagrange_points;
+    use crate::physics::Body;
+
+    fn sun_and_planet() -> (Body, Body) {
+        (Body::new().with_mass(1000.), Body::new().with_mass(1.).at(Coordinate { x: 100., y: 0., z: 0. }))
+    }
+
+    #[test]
+    fn triangular_points_are_equidistant_from_both_bodies() {
+        let (sun, planet) = sun_and_planet();
+        let points = lagrange_points(&sun, &planet);
+        for point in &points[3..] {
+            assert!(((*point - sun.position).magnitude() - 100.).abs() < 1e-9);
+            assert!(((*point - planet.position).magnitude() - 100.).abs() < 1e-9);
+        }
+        assert!(points[3].y > 0. && points[4].y < 0.);
+    }
+
+    #[test]
+    fn collinear_points_lie_on_the_line_joining_the_bodies() {
+        let (sun, planet) = sun_and_planet();
+        let points = lagrange_points(&sun, &planet);
+        assert!(points[..3].iter().all(|point| point.y.abs() < 1e-9));
+        assert!(points[0].x > 0. && points[0].x < planet.position.x);
+        assert!(points[1].x > planet.position.x);
+        assert!(points[2].x < 0.);
+    }
+
+    #[test]
+    fn first_two_points_lie_near_the_hill_radius_of_a_small_body() {
+        let (sun, planet) = sun_and_planet();
+        let points = lagrange_points(&sun, &planet);
+        let hill_radius = 100. * Real::cbrt(1. / 3003.);
+        assert!(((planet.position - points[0]).magnitude() / hill_radius - 1.).abs() < 0.05);
+        assert!(((points[1] - planet.position).magnitude() / hill_radius - 1.).abs() < 0.05);
+    }
+
+    #[test]
+    fn points_follow_orientation_of_the_pair() {
+        let (sun, mut planet) = sun_and_planet();
+        planet.position = Coordinate { x: 0., y: 100., z: 0. };
+        let points = lagrange_points(&sun, &planet);
+        assert!(points[0].x.abs() < 1e-9 && points[0].y > 0. && points[0].y < 100.);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::maths::{EuclideanVector, Real};
+use crate::physics::{orbit, Body};
+
+// An impulsive burn planned ahead, given in the frame of the body's motion relative to its dominant attractor:
+// prograde along that motion, radial perpendicular to it and away from the attractor.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Maneuver {
+    pub body: usize,
+    pub at: u64, // update at which the burn is to happen
+    pub prograde: Real,
+    pub radial: Real,
+}
+
+impl Maneuver {
+    pub const fn new(body: usize, at: u64) -> Self {
+        Self { body, at, prograde: 0., radial: 0. }
+    }
+
+    pub fn delta_v(&self, bodies: &[Body]) -> EuclideanVector {
+        let body = &bodies[self.body];
+        let (offset, velocity) = match orbit::dominant_attractor(bodies, self.body) {
+            Some(attractor) => (body.position - bodies[attractor].position, body.velocity - bodies[attractor].velocity),
+            None => (EuclideanVector { dx: 0., dy: 0., dz: 0. }, body.velocity),
+        };
+        if velocity.magnitude() == 0. { return EuclideanVector { dx: 0., dy: 0., dz: 0. }; }
+
+        let prograde = velocity.versor();
+        let radial = prograde.cross(orbit::normal(offset, velocity));
+        prograde * self.prograde + radial * self.radial
+    }
+
+    pub fn magnitude(&self) -> Real {
+        self.prograde.hypot(self.radial)
+    }
+
+    pub fn execute(&self, bodies: &mut [Body]) {
+        let delta_v = self.delta_v(bodies);
+        bodies[self.body].accelerate(delta_v);
+    }
+}
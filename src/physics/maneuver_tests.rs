@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector};
+    use crate::physics::maneuver::Maneuver;
+    use crate::physics::Body;
+
+    fn orbiting_pair() -> Vec<Body> {
+        vec![
+            Body::new().with_mass(100.).moving(EuclideanVector { dx: 5., dy: 0., dz: 0. }),
+            Body::new().with_mass(1.).at(Coordinate { x: 10., y: 0., z: 0. }).moving(EuclideanVector { dx: 5., dy: 2., dz: 0. }),
+        ]
+    }
+
+    #[test]
+    fn prograde_burn_is_along_the_motion_relative_to_the_attractor() {
+        let maneuver = Maneuver { prograde: 0.5, ..Maneuver::new(1, 0) };
+        assert_eq!(maneuver.delta_v(&orbiting_pair()), EuclideanVector { dx: 0., dy: 0.5, dz: 0. });
+    }
+
+    #[test]
+    fn radial_burn_points_away_from_the_attractor() {
+        let mut bodies = orbiting_pair();
+        bodies[1].velocity.dy = -2.;
+        let maneuver = Maneuver { radial: 0.5, ..Maneuver::new(1, 0) };
+        assert_eq!(maneuver.delta_v(&bodies), EuclideanVector { dx: 0.5, dy: 0., dz: 0. });
+    }
+
+    #[test]
+    fn executed_maneuver_changes_only_the_velocity_of_its_body() {
+        let mut bodies = orbiting_pair();
+        let maneuver = Maneuver { prograde: 3., radial: 4., ..Maneuver::new(1, 0) };
+        maneuver.execute(&mut bodies);
+        assert_eq!(bodies[1].velocity, EuclideanVector { dx: 9., dy: 5., dz: 0. });
+        assert_eq!(bodies[0].velocity, EuclideanVector { dx: 5., dy: 0., dz: 0. });
+        assert_eq!(maneuver.magnitude(), 5.);
+    }
+}
@@ -0,0 +1,31 @@
+use crate::maths::EuclideanVector;
+use crate::physics::{Body, Constants, ForceModel};
+
+// extra pull of the equatorial bulge of bodies with a J2 coefficient, equatorial planes being the one of the simulation,
+// along with the pull back of the bulge of the body itself; it makes orbits in that plane precess around the body, and
+// the nodes of ones inclined to it regress
+pub struct Oblateness;
+
+impl Oblateness {
+    fn pull(body: &Body, planet: &Body, constants: &Constants) -> EuclideanVector {
+        let offset = body.position - planet.position;
+        let distance = offset.magnitude();
+        if planet.oblateness == 0. || distance == 0. { return EuclideanVector { dx: 0., dy: 0., dz: 0. }; }
+
+        let latitude_sine_squared = (offset.dz / distance).powi(2);
+        let magnitude = 1.5 * constants.gravitational * planet.oblateness * planet.radius.powi(2) / distance.powi(5);
+        (-offset * (1. - 5. * latitude_sine_squared) - EuclideanVector::UNIT_Z * (2. * offset.dz)) * (magnitude * planet.mass * body.mass)
+    }
+}
+
+impl ForceModel for Oblateness {
+    fn force_on(&self, body: &Body, others: &[Body], constants: &Constants) -> EuclideanVector {
+        others.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, other| {
+            sum + Self::pull(body, other, constants) - Self::pull(other, body, constants)
+        })
+    }
+
+    fn acts_on(&self, bodies: &[Body], _constants: &Constants) -> bool {
+        bodies.iter().any(|body| body.oblateness != 0.)
+    }
+}
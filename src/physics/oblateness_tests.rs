@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::integration::Integrator;
+    use crate::physics::oblateness::Oblateness;
+    use crate::physics::orbit::Elements;
+    use crate::physics::{Body, Constants, DirectSummation, ForceModel, standard_models};
+
+    #[test]
+    fn spherical_bodies_are_not_perturbed() {
+        let bodies = vec![Body::new().with_mass(100.), Body::new().with_mass(1.).at(Coordinate { x: 10., y: 0., z: 0. })];
+        assert!(!Oblateness.acts_on(&bodies, &Constants::new()));
+        assert_eq!(Oblateness.force_on(&bodies[1], &bodies[..1], &Constants::new()), 0.);
+    }
+
+    #[test]
+    fn bulge_pulls_satellite_inwards_and_planet_back() {
+        let bodies = [Body::new().with_mass(100.).with_oblateness(0.1), Body::new().with_mass(1.).at(Coordinate { x: 10., y: 0., z: 0. })];
+        let on_planet = Oblateness.force_on(&bodies[0], &bodies[1..], &Constants::new());
+        let on_satellite = Oblateness.force_on(&bodies[1], &bodies[..1], &Constants::new());
+        assert!(on_satellite.dx < 0.);
+        assert!((on_planet + on_satellite).magnitude() < 1e-15);
+    }
+
+    #[test]
+    fn oblate_planet_makes_orbit_precess() {
+        let orbit_after = |oblateness: Real| {
+            let speed = 1.1 * (Constants::new().gravitational * 100. / 10.).sqrt();
+            let mut bodies = vec![
+                Body::new().with_mass(100.).with_oblateness(oblateness),
+                Body::new().with_mass(0.001).at(Coordinate { x: 10., y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: speed, dz: 0. }),
+            ];
+            for _ in 0..2000 { Integrator::RungeKutta4.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), 0.01); }
+            Elements::of(&bodies[1], &bodies[0], &Constants::new()).argument_of_periapsis
+        };
+        assert!(orbit_after(0.).abs() < 1e-3);
+        assert!(orbit_after(0.5).abs() > 1e-2);
+    }
+}
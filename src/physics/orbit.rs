@@ -0,0 +1,231 @@
+use crate::maths::{consts::PI, Coordinate, EuclideanVector, Real};
+use crate::physics::{Body, Constants};
+
+const KEPLER_ITERATIONS: usize = 50;
+const KEPLER_TOLERANCE: Real = 1e-12;
+
+// Keplerian elements of a two-body orbit, derived from the relative state vector; the reference plane is the one of the
+// simulation, and the reference direction is the x axis.
+pub struct Elements {
+    pub semi_major_axis: Real, // negative for hyperbolic trajectories
+    pub eccentricity: Real,
+    pub inclination: Real, // in radians, beyond a right angle for orbits going clockwise
+    pub longitude_of_ascending_node: Real, // zero for orbits in the reference plane
+    pub argument_of_periapsis: Real, // from the ascending node towards periapsis, in the direction of motion
+    pub periapsis: Real,
+    pub apoapsis: Option<Real>, // none for unbound trajectories
+    pub period: Option<Real>, // none for unbound trajectories
+}
+
+impl Elements {
+    pub fn of(body: &Body, primary: &Body, constants: &Constants) -> Self {
+        let gravitational_parameter = constants.gravitational * (body.mass + primary.mass);
+        Self::from_state(body.position - primary.position, body.velocity - primary.velocity, gravitational_parameter)
+    }
+
+    fn from_state(position: EuclideanVector, velocity: EuclideanVector, gravitational_parameter: Real) -> Self {
+        let distance = position.magnitude();
+        let speed_squared = velocity.dot(velocity);
+        let radial_speed = position.dot(velocity);
+
+        let specific_energy = speed_squared / 2. - gravitational_parameter / distance;
+        let semi_major_axis = -gravitational_parameter / (2. * specific_energy);
+        let eccentricity_vector = (position * (speed_squared - gravitational_parameter / distance) - velocity * radial_speed) / gravitational_parameter;
+        let eccentricity = eccentricity_vector.magnitude();
+        let bound = eccentricity < 1.;
+
+        Self {
+            semi_major_axis,
+            eccentricity,
+            inclination: 0.,
+            longitude_of_ascending_node: 0.,
+            argument_of_periapsis: 0.,
+            periapsis: semi_major_axis * (1. - eccentricity),
+            apoapsis: if bound { Some(semi_major_axis * (1. + eccentricity)) } else { None },
+            period: if bound { Some(2. * PI * (semi_major_axis.powi(3) / gravitational_parameter).sqrt()) } else { None },
+        }.facing(eccentricity_vector, normal(position, velocity))
+    }
+
+    // in the reference plane, travelled counter-clockwise
+    pub fn elliptic(semi_major_axis: Real, eccentricity: Real, argument_of_periapsis: Real, gravitational_parameter: Real) -> Self {
+        Self {
+            semi_major_axis,
+            eccentricity,
+            inclination: 0.,
+            longitude_of_ascending_node: 0.,
+            argument_of_periapsis,
+            periapsis: semi_major_axis * (1. - eccentricity),
+            apoapsis: Some(semi_major_axis * (1. + eccentricity)),
+            period: Some(2. * PI * (semi_major_axis.powi(3) / gravitational_parameter).sqrt()),
+        }
+    }
+
+    pub fn inclined(self, inclination: Real, longitude_of_ascending_node: Real) -> Self {
+        Self { inclination, longitude_of_ascending_node, ..self }
+    }
+
+    // turned so that periapsis lies in the given direction, in the plane perpendicular to the given normal of unit
+    // length, which points the way the orbit is travelled counter-clockwise
+    pub fn facing(self, periapsis_direction: EuclideanVector, normal: EuclideanVector) -> Self {
+        let node = EuclideanVector::UNIT_Z.cross(normal);
+        let longitude_of_ascending_node = if node.magnitude() > 0. { node.dy.atan2(node.dx) } else { 0. };
+        let node = EuclideanVector::from_polar(1., longitude_of_ascending_node);
+        Self {
+            inclination: normal.dx.hypot(normal.dy).atan2(normal.dz),
+            longitude_of_ascending_node,
+            argument_of_periapsis: normal.dot(node.cross(periapsis_direction)).atan2(node.dot(periapsis_direction)),
+            ..self
+        }
+    }
+
+    // from the vis-viva equation, relative to the primary
+    pub fn speed_at(&self, distance: Real, gravitational_parameter: Real) -> Real {
+        (gravitational_parameter * (2. / distance - 1. / self.semi_major_axis)).sqrt()
+    }
+
+    // position and velocity relative to the primary, for a bound orbit
+    pub fn state_at(&self, mean_anomaly: Real, gravitational_parameter: Real) -> (EuclideanVector, EuclideanVector) {
+        let (a, e) = (self.semi_major_axis, self.eccentricity);
+        let anomaly = eccentric_anomaly(mean_anomaly, e);
+        let minor_axis_ratio = (1. - e * e).sqrt();
+
+        let position = EuclideanVector { dx: a * (anomaly.cos() - e), dy: a * minor_axis_ratio * anomaly.sin(), dz: 0. };
+        let speed_factor = (gravitational_parameter * a).sqrt() / position.magnitude();
+        let velocity = EuclideanVector { dx: -speed_factor * anomaly.sin(), dy: speed_factor * minor_axis_ratio * anomaly.cos(), dz: 0. };
+
+        (self.oriented(position), self.oriented(velocity))
+    }
+
+    // the same for an unbound hyperbolic trajectory, given the hyperbolic mean anomaly
+    fn hyperbolic_state_at(&self, mean_anomaly: Real, gravitational_parameter: Real) -> (EuclideanVector, EuclideanVector) {
+        let (a, e) = (-self.semi_major_axis, self.eccentricity);
+        let anomaly = hyperbolic_anomaly(mean_anomaly, e);
+        let minor_axis_ratio = (e * e - 1.).sqrt();
+
+        let position = EuclideanVector { dx: a * (e - anomaly.cosh()), dy: a * minor_axis_ratio * anomaly.sinh(), dz: 0. };
+        let speed_factor = (gravitational_parameter * a).sqrt() / position.magnitude();
+        let velocity = EuclideanVector { dx: -speed_factor * anomaly.sinh(), dy: speed_factor * minor_axis_ratio * anomaly.cosh(), dz: 0. };
+
+        (self.oriented(position), self.oriented(velocity))
+    }
+
+    // from the frame with periapsis along the x axis and the orbit travelled counter-clockwise around the z axis
+    fn oriented(&self, vector: EuclideanVector) -> EuclideanVector {
+        vector.rotated(self.argument_of_periapsis).rotated_about(EuclideanVector::UNIT_X, self.inclination).rotated(self.longitude_of_ascending_node)
+    }
+
+    fn periapsis_direction(&self) -> EuclideanVector {
+        self.oriented(EuclideanVector::UNIT_X)
+    }
+
+    pub fn normal(&self) -> EuclideanVector {
+        self.oriented(EuclideanVector::UNIT_Z)
+    }
+
+    // of unit length, from the primary towards the point of the orbit at the given true anomaly
+    pub fn direction_at(&self, true_anomaly: Real) -> EuclideanVector {
+        self.oriented(EuclideanVector::from_polar(1., true_anomaly))
+    }
+
+    pub fn periapsis_point(&self, primary: Coordinate) -> Coordinate {
+        primary + self.periapsis_direction() * self.periapsis
+    }
+
+    pub fn apoapsis_point(&self, primary: Coordinate) -> Option<Coordinate> {
+        self.apoapsis.map(|apoapsis| primary + -self.periapsis_direction() * apoapsis)
+    }
+}
+
+// solves Kepler's equation M = E - e·sin(E) for elliptic orbits
+pub fn eccentric_anomaly(mean_anomaly: Real, eccentricity: Real) -> Real {
+    let mut anomaly = if eccentricity > 0.8 { PI } else { mean_anomaly };
+    for _ in 0..KEPLER_ITERATIONS {
+        let step = (anomaly - eccentricity * anomaly.sin() - mean_anomaly) / (1. - eccentricity * anomaly.cos());
+        anomaly -= step;
+        if step.abs() < KEPLER_TOLERANCE { break; }
+    }
+    anomaly
+}
+
+// relative position and velocity of the body after the given time, following its Keplerian orbit exactly;
+// none for the borderline parabolic trajectories
+pub fn propagated(body: &Body, primary: &Body, constants: &Constants, time: Real) -> Option<(EuclideanVector, EuclideanVector)> {
+    let gravitational_parameter = constants.gravitational * (body.mass + primary.mass);
+    let (position, velocity) = (body.position - primary.position, body.velocity - primary.velocity);
+
+    let elements = Elements::from_state(position, velocity, gravitational_parameter);
+    let (a, e) = (elements.semi_major_axis, elements.eccentricity);
+    let periapsis_direction = elements.periapsis_direction();
+    let true_anomaly = elements.normal().dot(periapsis_direction.cross(position)).atan2(periapsis_direction.dot(position));
+    let mean_motion = (gravitational_parameter / a.abs().powi(3)).sqrt();
+
+    let (position, velocity) = if e < 1. {
+        let anomaly = ((1. - e * e).sqrt() * true_anomaly.sin()).atan2(e + true_anomaly.cos());
+        elements.state_at(anomaly - e * anomaly.sin() + mean_motion * time, gravitational_parameter)
+    } else if e > 1. {
+        let anomaly = ((e * e - 1.).sqrt() * true_anomaly.sin() / (1. + e * true_anomaly.cos())).asinh();
+        elements.hyperbolic_state_at(e * anomaly.sinh() - anomaly + mean_motion * time, gravitational_parameter)
+    } else {
+        return None;
+    };
+    Some((position, velocity))
+}
+
+// of unit length, perpendicular to the plane of the relative motion given, pointing the way it goes counter-clockwise;
+// the z axis for motion along a line
+pub fn normal(position: EuclideanVector, velocity: EuclideanVector) -> EuclideanVector {
+    let angular_momentum = position.cross(velocity);
+    if angular_momentum.magnitude() > 0. { angular_momentum.versor() } else { EuclideanVector::UNIT_Z }
+}
+
+// solves the hyperbolic Kepler's equation M = e·sinh(H) - H
+fn hyperbolic_anomaly(mean_anomaly: Real, eccentricity: Real) -> Real {
+    let mut anomaly = (mean_anomaly / eccentricity).asinh();
+    for _ in 0..KEPLER_ITERATIONS {
+        let step = (eccentricity * anomaly.sinh() - anomaly - mean_anomaly) / (eccentricity * anomaly.cosh() - 1.);
+        anomaly -= step;
+        if step.abs() < KEPLER_TOLERANCE { break; }
+    }
+    anomaly
+}
+
+// kinetic plus potential energy of the relative motion per unit of mass, negative for bound orbits
+pub fn specific_energy(body: &Body, primary: &Body, constants: &Constants) -> Real {
+    let gravitational_parameter = constants.gravitational * (body.mass + primary.mass);
+    let speed = (body.velocity - primary.velocity).magnitude();
+    speed * speed / 2. - gravitational_parameter / (body.position - primary.position).magnitude()
+}
+
+// ratio of the speed relative to the primary to the local escape velocity, at least 1 for unbound trajectories
+pub fn escape_velocity_ratio(body: &Body, primary: &Body, constants: &Constants) -> Real {
+    let distance = (body.position - primary.position).magnitude();
+    let escape_velocity = (2. * constants.gravitational * (body.mass + primary.mass) / distance).sqrt();
+    (body.velocity - primary.velocity).magnitude() / escape_velocity
+}
+
+// radius of the region where the body's own gravity dominates over the tidal pull of the primary, none if not orbiting it
+pub fn hill_radius(body: &Body, primary: &Body, constants: &Constants) -> Option<Real> {
+    let elements = Elements::of(body, primary, constants);
+    elements.apoapsis?;
+    Some(elements.semi_major_axis * (1. - elements.eccentricity) * (body.mass / (3. * primary.mass)).cbrt())
+}
+
+pub fn most_massive(bodies: &[Body]) -> Option<usize> {
+    (0..bodies.len()).fold(None, |heaviest: Option<usize>, index| match heaviest {
+        Some(heaviest) if bodies[heaviest].mass >= bodies[index].mass => Some(heaviest),
+        _ => Some(index),
+    })
+}
+
+// the body pulling the given one the strongest
+pub fn dominant_attractor(bodies: &[Body], index: usize) -> Option<usize> {
+    let body = &bodies[index];
+    bodies.iter().enumerate()
+        .filter(|&(other, _)| other != index)
+        .map(|(other, attractor)| (other, body.pull_from(attractor, &Constants::new()).magnitude()))
+        .fold(None, |strongest: Option<(usize, Real)>, (other, pull)| match strongest {
+            Some((_, strongest_pull)) if strongest_pull >= pull => strongest,
+            _ => Some((other, pull)),
+        })
+        .map(|(other, _)| other)
+}
@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::orbit::{dominant_attractor, eccentric_anomaly, escape_velocity_ratio, hill_radius, most_massive, propagated, specific_energy, Elements};
+    use crate::physics::{Body, Constants};
+    use crate::maths::consts::PI;
+
+    const PRIMARY_MASS: Real = 100.;
+    const DISTANCE: Real = 100.;
+
+    fn primary() -> Body {
+        Body::new().with_mass(PRIMARY_MASS)
+    }
+
+    fn satellite_with_speed_ratio(ratio: Real) -> Body {
+        let circular_speed = (Constants::new().gravitational * PRIMARY_MASS / DISTANCE).sqrt();
+        Body::new().with_mass(0.).at(Coordinate { x: DISTANCE, y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: circular_speed * ratio, dz: 0. })
+    }
+
+    #[test]
+    fn circular_orbit_has_no_eccentricity() {
+        let elements = Elements::of(&satellite_with_speed_ratio(1.), &primary(), &Constants::new());
+        assert!(elements.eccentricity < 1e-12);
+        assert!((elements.semi_major_axis - DISTANCE).abs() < 1e-9);
+        assert!((elements.period.unwrap() - 2. * PI * (DISTANCE.powi(3) / (Constants::new().gravitational * PRIMARY_MASS)).sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn faster_satellite_is_at_periapsis_of_an_ellipse() {
+        let elements = Elements::of(&satellite_with_speed_ratio(1.2), &primary(), &Constants::new());
+        assert!(elements.eccentricity > 0. && elements.eccentricity < 1.);
+        assert!((elements.periapsis - DISTANCE).abs() < 1e-9);
+        assert!(elements.apoapsis.unwrap() > DISTANCE);
+        assert!(elements.argument_of_periapsis.abs() < 1e-9);
+    }
+
+    #[test]
+    fn slower_satellite_is_at_apoapsis_of_an_ellipse() {
+        let elements = Elements::of(&satellite_with_speed_ratio(0.8), &primary(), &Constants::new());
+        assert!((elements.apoapsis.unwrap() - DISTANCE).abs() < 1e-9);
+        assert!(elements.periapsis < DISTANCE);
+        assert!((elements.argument_of_periapsis.abs() - PI).abs() < 1e-9);
+    }
+
+    #[test]
+    fn apsides_lie_on_opposite_sides_of_the_primary() {
+        let satellite = satellite_with_speed_ratio(1.2);
+        let elements = Elements::of(&satellite, &primary(), &Constants::new());
+        let periapsis = elements.periapsis_point(primary().position);
+        let apoapsis = elements.apoapsis_point(primary().position).unwrap();
+        assert!(periapsis.approx_eq(satellite.position, 1e-9));
+        assert!((apoapsis.x + elements.apoapsis.unwrap()).abs() < 1e-9);
+        assert!(apoapsis.y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn satellite_above_escape_velocity_is_unbound() {
+        let elements = Elements::of(&satellite_with_speed_ratio(1.5), &primary(), &Constants::new());
+        assert!(elements.eccentricity > 1.);
+        assert!(elements.semi_major_axis < 0.);
+        assert!(elements.apoapsis.is_none());
+        assert!(elements.period.is_none());
+        assert!(elements.apoapsis_point(primary().position).is_none());
+    }
+
+    #[test]
+    fn kepler_equation_is_solved() {
+        for &(mean_anomaly, eccentricity) in &[(0.5, 0.1), (2., 0.5), (3., 0.95), (0., 0.)] {
+            let anomaly = eccentric_anomaly(mean_anomaly, eccentricity);
+            assert!((anomaly - eccentricity * anomaly.sin() - mean_anomaly).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn state_on_orbit_reproduces_its_elements() {
+        let primary = primary();
+        let gravitational_parameter = Constants::new().gravitational * PRIMARY_MASS;
+        let elements = Elements::elliptic(DISTANCE, 0.3, 1., gravitational_parameter);
+        let (position, velocity) = elements.state_at(2., gravitational_parameter);
+        let body = Body::new().with_mass(0.).at(primary.position + position).moving(velocity);
+
+        let derived = Elements::of(&body, &primary, &Constants::new());
+        assert!((derived.semi_major_axis - DISTANCE).abs() < 1e-9);
+        assert!((derived.eccentricity - 0.3).abs() < 1e-12);
+        assert!((derived.argument_of_periapsis - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn state_on_inclined_orbit_reproduces_its_elements() {
+        let primary = primary();
+        let gravitational_parameter = Constants::new().gravitational * PRIMARY_MASS;
+        let elements = Elements::elliptic(DISTANCE, 0.3, 1., gravitational_parameter).inclined(0.4, 2.);
+        let (position, velocity) = elements.state_at(2., gravitational_parameter);
+        let body = Body::new().with_mass(0.).at(primary.position + position).moving(velocity);
+        assert!(position.dz.abs() > 1.);
+
+        let derived = Elements::of(&body, &primary, &Constants::new());
+        assert!((derived.eccentricity - 0.3).abs() < 1e-12);
+        assert!((derived.inclination - 0.4).abs() < 1e-12);
+        assert!((derived.longitude_of_ascending_node - 2.).abs() < 1e-12);
+        assert!((derived.argument_of_periapsis - 1.).abs() < 1e-12);
+        assert!(derived.normal().approx_eq(position.cross(velocity).versor(), 1e-12));
+    }
+
+    #[test]
+    fn orbits_in_the_plane_are_inclined_by_nothing_or_turned_over() {
+        let prograde = Elements::of(&satellite_with_speed_ratio(1.2), &primary(), &Constants::new());
+        let retrograde = Elements::of(&satellite_with_speed_ratio(-1.2), &primary(), &Constants::new());
+        assert_eq!((prograde.inclination, prograde.longitude_of_ascending_node), (0., 0.));
+        assert_eq!((retrograde.inclination, retrograde.longitude_of_ascending_node), (PI, 0.));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn propagated_orbit_returns_after_a_period_either_way_round() {
+        for &direction in &[1., -1.] {
+            let satellite = satellite_with_speed_ratio(1.2 * direction);
+            let period = Elements::of(&satellite, &primary(), &Constants::new()).period.unwrap();
+
+            let (halfway, _) = propagated(&satellite, &primary(), &Constants::new(), period / 2.).unwrap();
+            let (position, velocity) = propagated(&satellite, &primary(), &Constants::new(), period).unwrap();
+            assert!(halfway.dx < -DISTANCE);
+            assert!(position.approx_eq(satellite.position - primary().position, 1e-9));
+            assert!(velocity.approx_eq(satellite.velocity, 1e-9));
+        }
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn propagated_inclined_orbit_returns_after_a_period() {
+        let satellite = satellite_with_speed_ratio(1.2);
+        let velocity = satellite.velocity.rotated_about(EuclideanVector::UNIT_X, 0.7);
+        let satellite = satellite.moving(velocity);
+        let period = Elements::of(&satellite, &primary(), &Constants::new()).period.unwrap();
+
+        let (a_quarter_in, _) = propagated(&satellite, &primary(), &Constants::new(), period / 4.).unwrap();
+        let (position, velocity) = propagated(&satellite, &primary(), &Constants::new(), period).unwrap();
+        assert!(a_quarter_in.dz > 0.);
+        assert!(position.approx_eq(satellite.position - primary().position, 1e-9));
+        assert!(velocity.approx_eq(satellite.velocity, 1e-9));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn hyperbolic_trajectory_is_propagated_there_and_back() {
+        let satellite = satellite_with_speed_ratio(1.5);
+        let (position, velocity) = propagated(&satellite, &primary(), &Constants::new(), 500.).unwrap();
+        let elements = Elements::of(&Body::new().with_mass(0.).at(primary().position + position).moving(velocity), &primary(), &Constants::new());
+        assert!(position.magnitude() > 5. * DISTANCE);
+        assert!((elements.eccentricity - 1.25).abs() < 1e-9);
+
+        let departed = Body::new().with_mass(0.).at(primary().position + position).moving(velocity);
+        let (position, velocity) = propagated(&departed, &primary(), &Constants::new(), -500.).unwrap();
+        assert!(position.approx_eq(satellite.position - primary().position, 1e-9));
+        assert!(velocity.approx_eq(satellite.velocity, 1e-9));
+    }
+
+    #[test]
+    fn specific_energy_is_negative_only_for_bound_orbits() {
+        let circular = specific_energy(&satellite_with_speed_ratio(1.), &primary(), &Constants::new());
+        assert!((circular + Constants::new().gravitational * PRIMARY_MASS / (2. * DISTANCE)).abs() < 1e-12);
+        assert!(specific_energy(&satellite_with_speed_ratio(1.5), &primary(), &Constants::new()) > 0.);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn vis_viva_speed_matches_the_actual_one_all_along_the_orbit() {
+        let satellite = satellite_with_speed_ratio(1.2);
+        let elements = Elements::of(&satellite, &primary(), &Constants::new());
+        let gravitational_parameter = Constants::new().gravitational * PRIMARY_MASS;
+        for &time in &[0., 100., 1000.] {
+            let (position, velocity) = propagated(&satellite, &primary(), &Constants::new(), time).unwrap();
+            assert!((elements.speed_at(position.magnitude(), gravitational_parameter) - velocity.magnitude()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn escape_velocity_is_sqrt_two_times_circular_speed() {
+        assert!((escape_velocity_ratio(&satellite_with_speed_ratio(1.), &primary(), &Constants::new()) - 1. / Real::sqrt(2.)).abs() < 1e-12);
+        assert!((escape_velocity_ratio(&satellite_with_speed_ratio(Real::sqrt(2.)), &primary(), &Constants::new()) - 1.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn hill_radius_of_circular_orbit() {
+        let satellite = satellite_with_speed_ratio(((PRIMARY_MASS + 3.) / PRIMARY_MASS).sqrt()).with_mass(3.);
+        let expected = DISTANCE * (3. / (3. * PRIMARY_MASS)).cbrt();
+        assert!((hill_radius(&satellite, &primary(), &Constants::new()).unwrap() - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn unbound_body_has_no_hill_sphere() {
+        assert!(hill_radius(&satellite_with_speed_ratio(1.5).with_mass(3.), &primary(), &Constants::new()).is_none());
+    }
+
+    #[test]
+    fn most_massive_body_is_found() {
+        let bodies = vec![Body::new().with_mass(1.), Body::new().with_mass(10.), Body::new().with_mass(5.)];
+        assert_eq!(most_massive(&bodies), Some(1));
+        assert_eq!(most_massive(&[]), None);
+    }
+
+    #[test]
+    fn dominant_attractor_is_the_one_pulling_the_strongest() {
+        let bodies = vec![
+            Body::new().with_mass(1000.).at(Coordinate { x: 100., y: 0., z: 0. }),
+            Body::new().with_mass(1.),
+            Body::new().with_mass(10.).at(Coordinate { x: 5., y: 0., z: 0. }),
+        ];
+        assert_eq!(dominant_attractor(&bodies, 1), Some(2));
+        assert_eq!(dominant_attractor(&bodies, 2), Some(0));
+        assert_eq!(dominant_attractor(&bodies[1..2], 0), None);
+    }
+}
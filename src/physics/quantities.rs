@@ -0,0 +1,107 @@
+use crate::maths::Real;
+
+// Physical quantities, in whatever units the scenario uses, told apart by the compiler. They add up, scale by
+// plain numbers and divide into plain ratios only with quantities of their own kind, and combine into one another
+// only where the units do.
+macro_rules! quantity {
+    ($name:ident) => {
+        #[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+        pub struct $name(pub Real);
+
+        impl std::ops::Add for $name {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl std::ops::Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl std::ops::Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl std::ops::Mul<Real> for $name {
+            type Output = Self;
+
+            fn mul(self, factor: Real) -> Self {
+                Self(self.0 * factor)
+            }
+        }
+
+        impl std::ops::MulAssign<Real> for $name {
+            fn mul_assign(&mut self, factor: Real) {
+                self.0 *= factor;
+            }
+        }
+
+        impl std::ops::Div<Real> for $name {
+            type Output = Self;
+
+            fn div(self, divisor: Real) -> Self {
+                Self(self.0 / divisor)
+            }
+        }
+
+        impl std::ops::Div for $name {
+            type Output = Real;
+
+            fn div(self, other: Self) -> Real {
+                self.0 / other.0
+            }
+        }
+
+        // with the formatting options applying to the number
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+                std::fmt::Display::fmt(&self.0, f)
+            }
+        }
+    };
+}
+
+macro_rules! product {
+    ($lhs:ident * $rhs:ident = $result:ident) => {
+        impl std::ops::Mul<$rhs> for $lhs {
+            type Output = $result;
+
+            fn mul(self, other: $rhs) -> $result {
+                $result(self.0 * other.0)
+            }
+        }
+
+        impl std::ops::Div<$rhs> for $result {
+            type Output = $lhs;
+
+            fn div(self, other: $rhs) -> $lhs {
+                $lhs(self.0 / other.0)
+            }
+        }
+
+        impl std::ops::Div<$lhs> for $result {
+            type Output = $rhs;
+
+            fn div(self, other: $lhs) -> $rhs {
+                $rhs(self.0 / other.0)
+            }
+        }
+    };
+}
+
+quantity!(Mass);
+quantity!(Length);
+quantity!(Time);
+quantity!(Velocity); // along some direction, or the magnitude of one
+
+product!(Velocity * Time = Length);
@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    use crate::physics::quantities::{Length, Mass, Time, Velocity};
+
+    #[test]
+    fn quantities_of_a_kind_add_up_and_divide_into_ratios() {
+        assert_eq!(Mass(2.) + Mass(3.), Mass(5.));
+        assert_eq!(Length(6.) - Length(2.) * 2., Length(2.));
+        assert_eq!(Time(6.) / Time(2.), 3.);
+    }
+
+    #[test]
+    fn quantities_combine_where_their_units_do() {
+        assert_eq!(Velocity(3.) * Time(2.), Length(6.));
+        assert_eq!(Length(6.) / Time(2.), Velocity(3.));
+        assert_eq!(Length(6.) / Velocity(3.), Time(2.));
+    }
+
+    #[test]
+    fn quantities_are_formatted_as_their_numbers() {
+        assert_eq!(format!("{:+.2}", -Velocity(1.5)), "-1.50");
+    }
+}
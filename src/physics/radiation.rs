@@ -0,0 +1,28 @@
+use crate::maths::{consts::PI, EuclideanVector};
+use crate::physics::{Body, Constants, ForceModel};
+
+// push of the light of radiating bodies (stars) on the cross-section of bodies exposed to it (sails, dust), falling
+// off with the square of the distance just like gravity does, so that their ratio is the same all around the star
+pub struct RadiationPressure;
+
+impl RadiationPressure {
+    fn push(body: &Body, star: &Body) -> EuclideanVector {
+        let joining_vector = body.position - star.position;
+        let distance = joining_vector.magnitude();
+        if star.radiation == 0. || distance == 0. { return EuclideanVector { dx: 0., dy: 0., dz: 0. }; }
+
+        let pressure = star.radiation / (4. * PI * distance * distance);
+        joining_vector.versor() * (pressure * body.area_to_mass * body.mass)
+    }
+}
+
+impl ForceModel for RadiationPressure {
+    fn force_on(&self, body: &Body, others: &[Body], _constants: &Constants) -> EuclideanVector {
+        if body.area_to_mass == 0. { return EuclideanVector { dx: 0., dy: 0., dz: 0. }; }
+        others.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, star| sum + Self::push(body, star))
+    }
+
+    fn acts_on(&self, bodies: &[Body], _constants: &Constants) -> bool {
+        bodies.iter().any(|body| body.radiation > 0.) && bodies.iter().any(|body| body.area_to_mass > 0.)
+    }
+}
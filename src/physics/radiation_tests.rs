@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, Real};
+    use crate::physics::radiation::RadiationPressure;
+    use crate::physics::{Body, Constants, ForceModel};
+
+    fn star() -> Body {
+        Body::new().with_mass(100.).with_radiation(40.)
+    }
+
+    fn sail_at(distance: Real) -> Body {
+        Body::new().with_mass(2.).with_sail(0.5).at(Coordinate { x: distance, y: 0., z: 0. })
+    }
+
+    #[test]
+    fn light_pushes_sail_away_from_the_star() {
+        let push = RadiationPressure.force_on(&sail_at(10.), &[star()], &Constants::new());
+        assert!(push.dx > 0.);
+        assert_eq!(push.dy, 0.);
+    }
+
+    #[test]
+    fn push_to_pull_ratio_is_the_same_at_any_distance() {
+        let ratio_at = |distance: Real| {
+            let (sail, star) = (sail_at(distance), star());
+            RadiationPressure.force_on(&sail, std::slice::from_ref(&star), &Constants::new()).magnitude() / sail.pull_from(&star, &Constants::new()).magnitude()
+        };
+        assert!((ratio_at(10.) - ratio_at(1000.)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn nothing_is_pushed_without_a_star_or_a_sail() {
+        let dark = Body::new().with_mass(100.);
+        assert!(!RadiationPressure.acts_on(&[dark, sail_at(10.)], &Constants::new()));
+        assert!(!RadiationPressure.acts_on(&[star(), Body::new().with_mass(2.)], &Constants::new()));
+        assert!(RadiationPressure.acts_on(&[star(), sail_at(10.)], &Constants::new()));
+    }
+}
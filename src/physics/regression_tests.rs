@@ -0,0 +1,63 @@
+// Long headless runs of canonical scenarios, guarding the conservation properties of the simulation.
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::conservation::Totals;
+    use crate::physics::integration::{substeps_needed, Integrator};
+    use crate::physics::{Body, Constants, DirectSummation, standard_models};
+
+    const ENERGY_TOLERANCE: Real = 1e-6;
+    const ANGULAR_MOMENTUM_TOLERANCE: Real = 1e-6;
+    const MOMENTUM_TOLERANCE: Real = 1e-9;
+
+    fn two_body_circular_orbit() -> Vec<Body> {
+        let (central_mass, radius) = (100., 100.);
+        let orbital_speed = (Constants::new().gravitational * central_mass / radius).sqrt();
+        vec![
+            Body::new().with_mass(central_mass),
+            Body::new().with_mass(0.001).at(Coordinate { x: radius, y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: orbital_speed, dz: 0. }),
+        ]
+    }
+
+    // the Chenciner-Montgomery choreography, with masses chosen so that G·m = 1
+    #[cfg_attr(feature = "single-precision", allow(clippy::excessive_precision))]
+    fn figure_eight() -> Vec<Body> {
+        let mass = 1. / Constants::new().gravitational;
+        let position = Coordinate { x: 0.970_004_36, y: -0.243_087_53, z: 0. };
+        let velocity = EuclideanVector { dx: -0.932_407_37, dy: -0.864_731_46, dz: 0. };
+        vec![
+            Body::new().with_mass(mass).at(position).moving(-velocity / 2.),
+            Body::new().with_mass(mass).at(Coordinate { x: -position.x, y: -position.y, z: 0. }).moving(-velocity / 2.),
+            Body::new().with_mass(mass).moving(velocity),
+        ]
+    }
+
+    fn drift_after(mut bodies: Vec<Body>, steps: usize, time_step: Real) -> Totals {
+        let initial = Totals::of(&bodies, &Constants::new());
+        for _ in 0..steps {
+            let substeps = substeps_needed(&bodies, &Constants::new(), time_step);
+            for _ in 0..substeps {
+                Integrator::RungeKutta4.advance(&mut bodies, &DirectSummation, &standard_models(), &Constants::new(), time_step / substeps as Real);
+            }
+        }
+        Totals::of(&bodies, &Constants::new()).drift_from(&initial)
+    }
+
+    fn assert_conserved(drift: &Totals) {
+        assert!(drift.energy.abs() < ENERGY_TOLERANCE, "energy drift: {}", drift.energy);
+        assert!(drift.angular_momentum.abs() < ANGULAR_MOMENTUM_TOLERANCE, "angular momentum drift: {}", drift.angular_momentum);
+        assert!(drift.momentum.magnitude() < MOMENTUM_TOLERANCE, "momentum drift: {}", drift.momentum);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn two_body_circular_orbit_is_conserved() {
+        assert_conserved(&drift_after(two_body_circular_orbit(), 5000, 1.));
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn figure_eight_is_conserved() {
+        assert_conserved(&drift_after(figure_eight(), 5000, 0.002));
+    }
+}
@@ -0,0 +1,31 @@
+use crate::maths::EuclideanVector;
+use crate::physics::{gravitational_pull, Body, Constants, ForceModel};
+
+// Extra attraction following from the Schwarzschild effective potential, scaling the Newtonian pull by 3L²/(r²c²)
+// where L is the specific angular momentum; this is what makes the periapsis of an orbit precess.
+// Only when the speed of light is given.
+pub struct Relativity;
+
+impl ForceModel for Relativity {
+    fn force_on(&self, body: &Body, others: &[Body], constants: &Constants) -> EuclideanVector {
+        let speed_of_light = match constants.speed_of_light {
+            Some(speed_of_light) => speed_of_light,
+            None => return EuclideanVector { dx: 0., dy: 0., dz: 0. },
+        };
+
+        others.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, other| {
+            let relative_position = body.position - other.position;
+            let relative_velocity = body.velocity - other.velocity;
+            let angular_momentum = relative_position.cross(relative_velocity);
+            let distance_squared = relative_position.dot(relative_position);
+            if distance_squared == 0. { return sum; }
+
+            let factor = 3. * angular_momentum.dot(angular_momentum) / (distance_squared * speed_of_light * speed_of_light);
+            sum + gravitational_pull(body.position, other.position, body.mass * other.mass, constants) * factor
+        })
+    }
+
+    fn acts_on(&self, _bodies: &[Body], constants: &Constants) -> bool {
+        constants.speed_of_light.is_some()
+    }
+}
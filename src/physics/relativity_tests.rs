@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::integration::{Integrator, NOMINAL_TIME_STEP};
+    use crate::physics::relativity::Relativity;
+    use crate::physics::{Body, Constants, DirectSummation, ForceModel, standard_models};
+
+    fn correction_on_planet(bodies: &[Body], speed_of_light: Real) -> EuclideanVector {
+        Relativity.force_on(&bodies[1], &bodies[..1], &Constants { speed_of_light: Some(speed_of_light), ..Constants::new() }) / bodies[1].mass
+    }
+
+    fn star_and_planet(planet_velocity: EuclideanVector) -> Vec<Body> {
+        vec![
+            Body::new().with_mass(100.),
+            Body::new().with_mass(0.001).at(Coordinate { x: 100., y: 0., z: 0. }).moving(planet_velocity),
+        ]
+    }
+
+    #[test]
+    fn radially_moving_body_needs_no_correction() {
+        let bodies = star_and_planet(EuclideanVector { dx: -1., dy: 0., dz: 0. });
+        assert_eq!(correction_on_planet(&bodies, 30.), 0.);
+    }
+
+    #[test]
+    fn there_is_no_correction_without_speed_of_light() {
+        let bodies = star_and_planet(EuclideanVector { dx: 0., dy: 3., dz: 0. });
+        assert!(!Relativity.acts_on(&bodies, &Constants::new()));
+        assert_eq!(Relativity.force_on(&bodies[1], &bodies[..1], &Constants::new()), 0.);
+    }
+
+    #[test]
+    fn orbiting_body_is_additionally_attracted() {
+        let bodies = star_and_planet(EuclideanVector { dx: 0., dy: 3., dz: 0. });
+        assert!(correction_on_planet(&bodies, 30.).dx < 0.);
+    }
+
+    #[test]
+    fn correction_vanishes_with_growing_speed_of_light() {
+        let bodies = star_and_planet(EuclideanVector { dx: 0., dy: 3., dz: 0. });
+        let slow = correction_on_planet(&bodies, 30.).magnitude();
+        let fast = correction_on_planet(&bodies, 300.).magnitude();
+        assert!((slow / fast - 100.).abs() < 1e-9);
+    }
+
+    #[test]
+    fn correction_changes_the_orbit() {
+        let mut newtonian = star_and_planet(EuclideanVector { dx: 0., dy: 2.5, dz: 0. });
+        let mut relativistic = star_and_planet(EuclideanVector { dx: 0., dy: 2.5, dz: 0. });
+        for _ in 0..500 {
+            Integrator::RungeKutta4.advance(&mut newtonian, &DirectSummation, &standard_models(), &Constants::new(), NOMINAL_TIME_STEP);
+            Integrator::RungeKutta4.advance(&mut relativistic, &DirectSummation, &standard_models(), &Constants { speed_of_light: Some(30.), ..Constants::new() }, NOMINAL_TIME_STEP);
+        }
+        assert!((newtonian[1].position - relativistic[1].position).magnitude() > 1.);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::maths::{consts::PI, Coordinate, EuclideanVector, Random, Real};
+use crate::physics::{gravitational_pull, Body, Constants};
+
+// Too light to pull anything, so that it is advanced by the pull of the bodies alone: thousands of these cost
+// as much as that many times the bodies, rather than the square of their total count.
+#[derive(Copy, Clone)]
+pub struct Particle {
+    pub position: Coordinate,
+    pub velocity: EuclideanVector,
+    pull: Option<EuclideanVector>, // at the end of the last step, to start the next one with
+}
+
+impl Particle {
+    pub const fn new(position: Coordinate, velocity: EuclideanVector) -> Self {
+        Self { position, velocity, pull: None }
+    }
+}
+
+// an annulus of particles on circular orbits, like planetary rings or accretion discs
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ring {
+    pub inner_radius: Real,
+    pub outer_radius: Real,
+    pub count: usize,
+    pub dispersion: Real, // of the velocities, as a fraction of the circular one
+}
+
+impl Ring {
+    // spread evenly over the area of the annulus, each moving in a random direction off its circular orbit
+    pub fn around(&self, body: &Body, constants: &Constants, random: &mut Random) -> Vec<Particle> {
+        let (inner, outer) = (self.inner_radius * self.inner_radius, self.outer_radius * self.outer_radius);
+        (0..self.count).map(|_| {
+            let distance = random.between(inner, outer).sqrt();
+            let (angle, heading) = (random.between(0., 2. * PI), random.between(0., 2. * PI));
+            let speed = (constants.gravitational * body.mass / distance).sqrt();
+
+            let circular = EuclideanVector::from_polar(speed, angle + PI / 2.);
+            let deviation = EuclideanVector::from_polar(speed * self.dispersion * random.between(0., 1.), heading);
+            Particle::new(body.position + EuclideanVector::from_polar(distance, angle), body.velocity + circular + deviation)
+        }).collect()
+    }
+}
+
+pub fn pull_on(position: Coordinate, bodies: &[Body], constants: &Constants) -> EuclideanVector {
+    bodies.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, body| sum + gravitational_pull(position, body.position, body.mass, constants))
+}
+
+// Kick-drift-kick, once the bodies are already advanced over the same step. Particles falling onto a body are gone.
+pub fn advance(particles: &mut Vec<Particle>, bodies: &[Body], constants: &Constants, time_step: Real) {
+    for particle in particles.iter_mut() {
+        let pull = particle.pull.unwrap_or_else(|| pull_on(particle.position, bodies, constants));
+        particle.velocity += pull * (time_step / 2.);
+        particle.position += particle.velocity * time_step;
+
+        let pull = pull_on(particle.position, bodies, constants);
+        particle.velocity += pull * (time_step / 2.);
+        particle.pull = Some(pull);
+    }
+
+    particles.retain(|particle| !bodies.iter().any(|body| (particle.position - body.position).magnitude() < body.radius));
+}
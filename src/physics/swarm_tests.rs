@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Random, Real};
+    use crate::physics::swarm::{advance, Particle, Ring};
+    use crate::physics::{Body, Constants};
+
+    fn planet() -> Vec<Body> {
+        vec![Body::new().with_mass(100.)]
+    }
+
+    #[test]
+    fn particle_keeps_its_circular_orbit() {
+        let speed = (Constants::new().gravitational * 100. / 50.).sqrt();
+        let mut particles = vec![Particle::new(Coordinate { x: 50., y: 0., z: 0. }, EuclideanVector { dx: 0., dy: speed, dz: 0. })];
+        for _ in 0..1000 { advance(&mut particles, &planet(), &Constants::new(), 0.1); }
+        assert!(((particles[0].position - Coordinate { x: 0., y: 0., z: 0. }).magnitude() - 50.).abs() < 0.01);
+    }
+
+    #[test]
+    fn particle_falling_onto_a_body_is_gone() {
+        let mut particles = vec![
+            Particle::new(Coordinate { x: 10., y: 0., z: 0. }, EuclideanVector { dx: -5., dy: 0., dz: 0. }),
+            Particle::new(Coordinate { x: 500., y: 0., z: 0. }, EuclideanVector { dx: 0., dy: 0., dz: 0. }),
+        ];
+        for _ in 0..10 { advance(&mut particles, &planet(), &Constants::new(), 0.1); }
+        assert_eq!(particles.len(), 1);
+        assert!(particles[0].position.x > 400.);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn ring_particles_lie_within_the_annulus_on_circular_orbits() {
+        let planet = Body::new().with_mass(100.).at(Coordinate { x: 30., y: 40., z: 0. }).moving(EuclideanVector { dx: 1., dy: 0., dz: 0. });
+        let ring = Ring { inner_radius: 60., outer_radius: 120., count: 500, dispersion: 0. };
+        let particles = ring.around(&planet, &Constants::new(), &mut Random::seeded(7));
+
+        assert_eq!(particles.len(), 500);
+        for particle in &particles {
+            let (offset, velocity) = (particle.position - planet.position, particle.velocity - planet.velocity);
+            assert!(offset.magnitude() >= 60. - 1e-9 && offset.magnitude() <= 120. + 1e-9);
+            assert!((velocity.magnitude() - (Constants::new().gravitational * 100. / offset.magnitude()).sqrt()).abs() < 1e-9);
+            assert!((offset.dx * velocity.dx + offset.dy * velocity.dy).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn dispersion_stirs_the_velocities_up_to_its_fraction() {
+        let planet = Body::new().with_mass(100.);
+        let ring = Ring { inner_radius: 60., outer_radius: 120., count: 500, dispersion: 0.1 };
+        let deviations: Vec<Real> = ring.around(&planet, &Constants::new(), &mut Random::seeded(7)).iter().map(|particle| {
+            let offset = particle.position - planet.position;
+            let circular = (Constants::new().gravitational * 100. / offset.magnitude()).sqrt();
+            (particle.velocity - EuclideanVector { dx: -offset.dy, dy: offset.dx, dz: 0. }.versor() * circular).magnitude() / circular
+        }).collect();
+        assert!(deviations.iter().all(|&deviation| deviation <= 0.1 + 1e-9));
+        assert!(deviations.iter().any(|&deviation| deviation > 0.05));
+    }
+}
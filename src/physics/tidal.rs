@@ -0,0 +1,130 @@
+use crate::maths::{consts::PI, EuclideanVector, Real};
+use crate::physics::{orbit, Body, BodyId};
+use std::collections::HashSet;
+
+const PARTICLES: usize = 8;
+const MIN_MASS_RATIO: Real = 10.; // only much heavier bodies are able to tear others apart
+const MIN_PARTICLE_MASS: Real = 0.05;
+const LOVE_NUMBER_RATIO: Real = 2.25; // 3 k₂ / α, for a Love number of 0.3 and the inertia factor of a uniform sphere
+const QUALITY: Real = 1e-4; // exaggerated a million times, so that moons lock within minutes rather than aeons
+
+// distance below which the tidal pull of the primary exceeds the self-gravity of a rigid satellite
+pub fn roche_limit(primary: &Body, satellite: &Body) -> Real {
+    if satellite.mass <= 0. { return 0.; }
+    satellite.radius * (2. * primary.mass / satellite.mass).cbrt()
+}
+
+pub struct TidalDisruption {
+    pub enabled: bool,
+    pub particles: usize,
+    // Of the particles torn off so far, never torn apart again: being as dense as their body and as far from the
+    // primary, they are just as deep within their own Roche limit, and would go on splitting until too light to.
+    pub debris: HashSet<BodyId>,
+}
+
+// the debris is left out, as identities of bodies are not kept
+serializable!(TidalDisruption { enabled, particles } or TidalDisruption::new());
+
+impl TidalDisruption {
+    pub fn new() -> Self {
+        Self { enabled: false, particles: PARTICLES, debris: HashSet::new() }
+    }
+
+    // returns indices of the disrupted bodies, in order of removal; their particles are appended at the end
+    pub fn disrupt(&mut self, bodies: &mut Vec<Body>) -> Vec<usize> {
+        let mut removed = Vec::new();
+        if !self.enabled { return removed; }
+
+        let (mut i, mut original) = (0, bodies.len());
+        while i < original {
+            if self.is_torn_apart(bodies, i) {
+                let satellite = bodies.remove(i);
+                let mut particles = disintegrated(&satellite, self.particles);
+                self.debris.extend(particles.iter().map(|particle| particle.id));
+                bodies.append(&mut particles);
+                removed.push(i);
+                original -= 1;
+            } else {
+                i += 1;
+            }
+        }
+
+        removed
+    }
+
+    fn is_torn_apart(&self, bodies: &[Body], index: usize) -> bool {
+        let satellite = &bodies[index];
+        if self.debris.contains(&satellite.id) { return false; }
+        if self.particles < 2 || satellite.mass / (self.particles as Real) < MIN_PARTICLE_MASS { return false; }
+
+        bodies.iter().any(|primary| {
+            primary.mass >= MIN_MASS_RATIO * satellite.mass
+                && (primary.position - satellite.position).magnitude() < roche_limit(primary, satellite)
+        })
+    }
+}
+
+// Tides raised on a body by its dominant attractor lag behind, and their pull drags its spin towards the rate at
+// which it goes around the attractor, exponentially with the constant time lag model. The angular momentum taken
+// from the spin is not handed over to the orbit.
+pub struct TidalLocking {
+    pub enabled: bool,
+    pub quality: Real, // the Q factor of the bodies, the lower the faster they dissipate
+}
+
+serializable!(TidalLocking { enabled, quality } or TidalLocking::new());
+
+impl TidalLocking {
+    pub const fn new() -> Self {
+        Self { enabled: false, quality: QUALITY }
+    }
+
+    pub fn despin(&self, bodies: &mut [Body], time_step: Real) {
+        if !self.enabled { return; }
+
+        let primaries: Vec<Option<usize>> = (0..bodies.len()).map(|index| orbit::dominant_attractor(bodies, index)).collect();
+        let spins: Vec<Real> = bodies.iter().zip(&primaries).map(|(body, primary)| match primary {
+            Some(primary) => self.despun(body, &bodies[*primary], time_step),
+            None => body.spin,
+        }).collect();
+        for (body, spin) in bodies.iter_mut().zip(spins) { body.spin = spin; }
+    }
+
+    fn despun(&self, body: &Body, primary: &Body, time_step: Real) -> Real {
+        if body.mass <= 0. || body.radius <= 0. { return body.spin; }
+
+        let distance = (body.position - primary.position).magnitude();
+        let orbital_rate = orbital_rate(body, primary);
+        let rate = LOVE_NUMBER_RATIO / self.quality * (primary.mass / body.mass) * (body.radius / distance).powi(3) * orbital_rate.abs();
+        orbital_rate + (body.spin - orbital_rate) * (-rate * time_step).exp()
+    }
+}
+
+// angular velocity of the body around the primary, about the z axis, positive counter-clockwise
+pub fn orbital_rate(body: &Body, primary: &Body) -> Real {
+    let (offset, relative_velocity) = (body.position - primary.position, body.velocity - primary.velocity);
+    offset.cross(relative_velocity).dz / offset.dot(offset)
+}
+
+// 1 for tidally locked bodies, none for ones not spinning at all
+pub fn spin_to_orbit_period(body: &Body, primary: &Body) -> Option<Real> {
+    if body.spin == 0. { return None; }
+    Some(orbital_rate(body, primary) / body.spin)
+}
+
+// a ring of equal particles sharing the mass, center and momentum of the body
+pub fn disintegrated(body: &Body, count: usize) -> Vec<Body> {
+    let mass = body.mass / count as Real;
+    let radius = body.radius / (count as Real).cbrt();
+    let spread = 1.1 * radius / (PI / count as Real).sin();
+
+    (0..count).map(|k| {
+        let angle = 2. * PI * k as Real / count as Real;
+        Body::new()
+            .named(&format!("{} debris {}", body.name, k + 1))
+            .at(body.position + EuclideanVector::from_polar(spread, angle))
+            .moving(body.velocity)
+            .with_mass(mass)
+            .with_radius(radius)
+    }).collect()
+}
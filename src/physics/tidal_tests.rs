@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::tidal::{disintegrated, roche_limit, spin_to_orbit_period, TidalDisruption, TidalLocking};
+    use crate::physics::Body;
+
+    fn planet() -> Body {
+        Body::new().named("planet").with_mass(1000.)
+    }
+
+    fn moon_at(distance: Real) -> Body {
+        Body::new().named("moon").with_mass(2.).at(Coordinate { x: distance, y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: 3., dz: 0. })
+    }
+
+    fn enabled() -> TidalDisruption {
+        TidalDisruption { enabled: true, ..TidalDisruption::new() }
+    }
+
+    #[test]
+    fn spinning_moon_is_despun_into_tidal_locking() {
+        let mut bodies = vec![planet(), moon_at(100.).with_spin(1.)];
+        let locking = TidalLocking { enabled: true, ..TidalLocking::new() };
+        let initial = spin_to_orbit_period(&bodies[1], &bodies[0]).unwrap();
+        locking.despin(&mut bodies, 1.);
+        let despun = spin_to_orbit_period(&bodies[1], &bodies[0]).unwrap();
+        for _ in 0..10_000 { locking.despin(&mut bodies, 1.); }
+        assert!(initial < despun && despun < 1.);
+        assert!((spin_to_orbit_period(&bodies[1], &bodies[0]).unwrap() - 1.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn tidal_locking_is_off_by_default() {
+        let mut bodies = vec![planet(), moon_at(100.).with_spin(1.)];
+        TidalLocking::new().despin(&mut bodies, 1.);
+        assert_eq!(bodies[1].spin, 1.);
+    }
+
+    #[test]
+    fn roche_limit_grows_with_the_primary_mass() {
+        let moon = moon_at(0.);
+        assert!((roche_limit(&planet(), &moon) - moon.radius * Real::cbrt(1000.)).abs() < 1e-9);
+        assert!(roche_limit(&planet().with_mass(2000.), &moon) > roche_limit(&planet(), &moon));
+    }
+
+    #[test]
+    fn disintegration_conserves_mass_and_momentum() {
+        let moon = moon_at(10.);
+        let particles = disintegrated(&moon, 8);
+        assert_eq!(particles.len(), 8);
+        assert!((particles.iter().map(|particle| particle.mass).sum::<Real>() - moon.mass).abs() < 1e-12);
+        let momentum = particles.iter().fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, particle| sum + particle.momentum());
+        assert!(momentum.approx_eq(moon.momentum(), 1e-12));
+    }
+
+    #[test]
+    fn disintegrated_particles_do_not_overlap() {
+        let particles = disintegrated(&moon_at(10.), 8);
+        assert!(!particles[0].overlaps(&particles[1]));
+    }
+
+    #[test]
+    fn moon_within_roche_limit_is_torn_apart() {
+        let limit = roche_limit(&planet(), &moon_at(0.));
+        let mut bodies = vec![moon_at(limit * 0.9), planet()];
+        assert_eq!(enabled().disrupt(&mut bodies), vec![0]);
+        assert_eq!(bodies.len(), 9);
+        assert_eq!(bodies[0].name, "planet");
+    }
+
+    #[test]
+    fn debris_of_a_heavy_moon_is_not_torn_apart_again_within_the_same_update() {
+        let (planet, moon) = (planet().with_mass(1e5), moon_at(0.).with_mass(1000.));
+        let limit = roche_limit(&planet, &moon);
+        let mut bodies = vec![planet, moon.at(Coordinate { x: limit * 0.9, y: 0., z: 0. })];
+        assert_eq!(enabled().disrupt(&mut bodies), vec![1]);
+        assert_eq!(bodies.len(), 1 + enabled().particles);
+    }
+
+    #[test]
+    fn debris_of_a_heavy_moon_is_never_torn_apart_again() {
+        let (planet, moon) = (planet().with_mass(1e5), moon_at(0.).with_mass(1000.));
+        let limit = roche_limit(&planet, &moon);
+        let mut bodies = vec![planet, moon.at(Coordinate { x: limit * 0.9, y: 0., z: 0. })];
+        let mut disruption = enabled();
+        assert_eq!(disruption.disrupt(&mut bodies), vec![1]);
+        for _ in 0..10 { assert!(disruption.disrupt(&mut bodies).is_empty()); }
+        assert_eq!(bodies.len(), 1 + disruption.particles);
+    }
+
+    #[test]
+    fn moon_outside_roche_limit_survives() {
+        let limit = roche_limit(&planet(), &moon_at(0.));
+        let mut bodies = vec![planet(), moon_at(limit * 1.1)];
+        assert!(enabled().disrupt(&mut bodies).is_empty());
+        assert_eq!(bodies.len(), 2);
+    }
+
+    #[test]
+    fn comparable_bodies_are_not_disrupted() {
+        let mut bodies = vec![planet().with_mass(5.), moon_at(0.5)];
+        assert!(enabled().disrupt(&mut bodies).is_empty());
+    }
+
+    #[test]
+    fn disruption_is_off_by_default() {
+        let mut bodies = vec![planet(), moon_at(0.5)];
+        assert!(TidalDisruption::new().disrupt(&mut bodies).is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+use crate::maths::{consts::PI, Coordinate, EuclideanVector, Real};
+use crate::physics::orbit::{self, Elements};
+use crate::physics::quantities::{Length, Time, Velocity};
+use crate::physics::{Body, Constants};
+
+const PATH_POINTS: usize = 128;
+
+// Two prograde burns taking a body from its current distance to a circular orbit of the target radius, the first one
+// right away and the second one half a transfer ellipse later; negative burns are retrograde, to lower the orbit.
+pub struct Hohmann {
+    pub departure_burn: Velocity,
+    pub arrival_burn: Velocity,
+    pub duration: Time,
+    transfer: Elements,
+    gravitational_parameter: Real,
+    arrival: EuclideanVector, // relative to the primary, opposite the departure
+}
+
+impl Hohmann {
+    pub fn plan(body: &Body, primary: &Body, constants: &Constants, target_radius: Length) -> Self {
+        let gravitational_parameter = constants.gravitational * (body.mass + primary.mass);
+        let offset = body.position - primary.position;
+        let (departure, arrival) = (offset.magnitude(), target_radius.0);
+        let semi_major_axis = (departure + arrival) / 2.;
+
+        let vis_viva = |distance: Real| (gravitational_parameter * (2. / distance - 1. / semi_major_axis)).sqrt();
+        let circular = |distance: Real| (gravitational_parameter / distance).sqrt();
+        let speed = (body.velocity - primary.velocity).magnitude();
+
+        // the periapsis of the transfer is where the body is when raising the orbit, and opposite when lowering it,
+        // in the plane of its current motion
+        let periapsis_direction = if arrival >= departure { offset.versor() } else { -offset.versor() };
+        let eccentricity = (arrival - departure).abs() / (arrival + departure);
+
+        Self {
+            departure_burn: Velocity(vis_viva(departure) - speed),
+            arrival_burn: Velocity(circular(arrival) - vis_viva(arrival)),
+            duration: Time(PI * (semi_major_axis.powi(3) / gravitational_parameter).sqrt()),
+            transfer: Elements::elliptic(semi_major_axis, eccentricity, 0., gravitational_parameter)
+                .facing(periapsis_direction, orbit::normal(offset, body.velocity - primary.velocity)),
+            gravitational_parameter,
+            arrival: -offset.versor() * arrival,
+        }
+    }
+
+    pub fn total_burn(&self) -> Velocity {
+        Velocity(self.departure_burn.0.abs() + self.arrival_burn.0.abs())
+    }
+
+    // the whole transfer ellipse, around the primary at the given position
+    pub fn path(&self, primary: Coordinate) -> Vec<Coordinate> {
+        (0..=PATH_POINTS)
+            .map(|i| self.transfer.state_at(2. * PI * i as Real / PATH_POINTS as Real, self.gravitational_parameter).0)
+            .map(|offset| primary + offset)
+            .collect()
+    }
+
+    pub fn arrival_point(&self, primary: Coordinate) -> Coordinate {
+        primary + self.arrival
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::orbit::propagated;
+    use crate::physics::quantities::{Length, Velocity};
+    use crate::physics::transfer::Hohmann;
+    use crate::physics::{Body, Constants};
+
+    const PRIMARY_MASS: Real = 100.;
+
+    fn circular_speed(distance: Real) -> Real {
+        (Constants::new().gravitational * PRIMARY_MASS / distance).sqrt()
+    }
+
+    fn satellite_at(distance: Real) -> Body {
+        Body::new().with_mass(0.).at(Coordinate { x: distance, y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: circular_speed(distance), dz: 0. })
+    }
+
+    fn arrival_after_transfer(from: Real, to: Real) -> (Coordinate, Real, Hohmann) {
+        let primary = Body::new().with_mass(PRIMARY_MASS);
+        let mut satellite = satellite_at(from);
+        let plan = Hohmann::plan(&satellite, &primary, &Constants::new(), Length(to));
+
+        satellite.velocity *= (circular_speed(from) + plan.departure_burn.0) / circular_speed(from);
+        let (position, velocity) = propagated(&satellite, &primary, &Constants::new(), plan.duration.0).unwrap();
+        (primary.position + position, velocity.magnitude() + plan.arrival_burn.0, plan)
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn raising_burns_bring_the_body_onto_the_target_orbit() {
+        let (position, speed, plan) = arrival_after_transfer(100., 400.);
+        assert!(plan.departure_burn > Velocity(0.) && plan.arrival_burn > Velocity(0.));
+        assert!(position.approx_eq(plan.arrival_point(Coordinate { x: 0., y: 0., z: 0. }), 1e-6));
+        assert!((position.x + 400.).abs() < 1e-6);
+        assert!((speed - circular_speed(400.)).abs() < 1e-9);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn lowering_burns_are_retrograde() {
+        let (position, speed, plan) = arrival_after_transfer(400., 100.);
+        assert!(plan.departure_burn < Velocity(0.) && plan.arrival_burn < Velocity(0.));
+        assert!((position.x + 100.).abs() < 1e-6);
+        assert!((speed - circular_speed(100.)).abs() < 1e-9);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn transfer_path_spans_both_orbits() {
+        let plan = Hohmann::plan(&satellite_at(100.), &Body::new().with_mass(PRIMARY_MASS), &Constants::new(), Length(400.));
+        let distances: Vec<Real> = plan.path(Coordinate { x: 0., y: 0., z: 0. }).iter().map(|point| point.x.hypot(point.y)).collect();
+        assert!((distances.iter().copied().fold(Real::INFINITY, Real::min) - 100.).abs() < 1e-9);
+        assert!((distances.iter().copied().fold(0., Real::max) - 400.).abs() < 1e-9);
+        assert!(plan.total_burn() > Velocity(0.));
+    }
+}
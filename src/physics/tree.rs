@@ -0,0 +1,143 @@
+use crate::maths::{Aabb, Coordinate, EuclideanVector, Real};
+use crate::physics::{gravitational_pull, Body, Constants, ForceSolver};
+
+const MAX_DEPTH: u32 = 48; // below that, coinciding bodies are simply aggregated in a single leaf
+const COINCIDENCE: Real = 1e-12; // of the size of a node, closer than which bodies are aggregated right away
+
+struct Node {
+    center: Coordinate,
+    half_size: Real,
+    count: usize,
+    mass: Real,
+    weighted_position: EuclideanVector,
+    children: Option<usize>, // index of the first of eight consecutive child nodes
+}
+
+impl Node {
+    const fn new(center: Coordinate, half_size: Real) -> Self {
+        Self {
+            center,
+            half_size,
+            count: 0,
+            mass: 0.,
+            weighted_position: EuclideanVector { dx: 0., dy: 0., dz: 0. },
+            children: None,
+        }
+    }
+
+    fn center_of_mass(&self) -> Coordinate {
+        if self.mass == 0. { return self.center; }
+        let average = self.weighted_position / self.mass;
+        Coordinate { x: average.dx, y: average.dy, z: average.dz }
+    }
+
+    fn octant_of(&self, position: Coordinate) -> usize {
+        let east = if position.x >= self.center.x { 1 } else { 0 };
+        let south = if position.y >= self.center.y { 2 } else { 0 };
+        let far = if position.z >= self.center.z { 4 } else { 0 };
+        east + south + far
+    }
+
+    fn accumulate(&mut self, position: Coordinate, mass: Real) {
+        self.count += 1;
+        self.mass += mass;
+        self.weighted_position += EuclideanVector::towards(position) * mass;
+    }
+}
+
+pub struct Octree {
+    nodes: Vec<Node>,
+}
+
+impl Octree {
+    pub fn build(bodies: &[Body], positions: &[Coordinate]) -> Self {
+        let mut tree = Self { nodes: vec![Self::bounding_node(positions)] };
+        for (body, &position) in bodies.iter().zip(positions) {
+            tree.insert(0, position, body.mass, 0);
+        }
+        tree
+    }
+
+    fn bounding_node(positions: &[Coordinate]) -> Node {
+        match Aabb::of(positions.iter().copied()) {
+            Some(bounds) => Node::new(bounds.center(), bounds.size().dx.max(bounds.size().dy).max(bounds.size().dz) / 2. + 1.),
+            None => Node::new(Coordinate { x: 0., y: 0., z: 0. }, 1.),
+        }
+    }
+
+    fn insert(&mut self, index: usize, position: Coordinate, mass: Real, depth: u32) {
+        let node = &self.nodes[index];
+        let previous = (node.count == 1 && node.children.is_none()).then(|| (node.center_of_mass(), node.mass));
+
+        self.nodes[index].accumulate(position, mass);
+        if self.nodes[index].count == 1 || depth >= MAX_DEPTH { return; }
+
+        if let Some((previous_position, previous_mass)) = previous {
+            if previous_position.approx_eq(position, self.nodes[index].half_size * COINCIDENCE) { return; }
+            self.subdivide(index);
+            self.insert_into_child(index, previous_position, previous_mass, depth);
+        }
+        self.insert_into_child(index, position, mass, depth);
+    }
+
+    fn subdivide(&mut self, index: usize) {
+        let (center, quarter) = (self.nodes[index].center, self.nodes[index].half_size / 2.);
+        self.nodes[index].children = Some(self.nodes.len());
+
+        for octant in 0..8 {
+            let side = |bit: usize| if octant & bit == 0 { -1. } else { 1. };
+            self.nodes.push(Node::new(Coordinate { x: center.x + side(1) * quarter, y: center.y + side(2) * quarter, z: center.z + side(4) * quarter }, quarter));
+        }
+    }
+
+    fn insert_into_child(&mut self, index: usize, position: Coordinate, mass: Real, depth: u32) {
+        if let Some(first_child) = self.nodes[index].children {
+            let child = first_child + self.nodes[index].octant_of(position);
+            self.insert(child, position, mass, depth + 1);
+        }
+    }
+
+    pub fn acceleration_at(&self, position: Coordinate, opening_angle: Real, constants: &Constants) -> EuclideanVector {
+        self.acceleration_from(0, position, opening_angle, constants)
+    }
+
+    fn acceleration_from(&self, index: usize, position: Coordinate, opening_angle: Real, constants: &Constants) -> EuclideanVector {
+        let node = &self.nodes[index];
+        if node.count == 0 { return EuclideanVector { dx: 0., dy: 0., dz: 0. }; }
+
+        let center_of_mass = node.center_of_mass();
+        let distance = (center_of_mass - position).magnitude();
+
+        match node.children {
+            Some(first_child) if 2. * node.half_size >= opening_angle * distance => {
+                (first_child..first_child + 8).fold(EuclideanVector { dx: 0., dy: 0., dz: 0. }, |sum, child| {
+                    sum + self.acceleration_from(child, position, opening_angle, constants)
+                })
+            }
+            _ => gravitational_pull(position, center_of_mass, node.mass, constants),
+        }
+    }
+}
+
+pub struct BarnesHut {
+    pub opening_angle: Real,
+}
+
+impl BarnesHut {
+    pub const fn with_opening_angle(opening_angle: Real) -> Self {
+        Self { opening_angle }
+    }
+}
+
+impl ForceSolver for BarnesHut {
+    fn accelerations(&self, bodies: &[Body], positions: &[Coordinate], constants: &Constants) -> Vec<EuclideanVector> {
+        let tree = Octree::build(bodies, positions);
+        positions.iter().map(|&position| tree.acceleration_at(position, self.opening_angle, constants)).collect()
+    }
+}
+
+impl std::fmt::Display for BarnesHut {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        write!(f, "Barnes-Hut (θ = {:.1})", self.opening_angle)
+    }
+}
@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::tree::BarnesHut;
+    use crate::physics::{Body, Constants, DirectSummation, ForceSolver};
+
+    fn cluster() -> Vec<Body> {
+        (0..50).map(|i| {
+            let i = i as Real;
+            Body::new().with_mass(1. + i % 3.).at(Coordinate { x: (i * 37.) % 101., y: (i * 53.) % 97., z: 0. })
+        }).collect()
+    }
+
+    // the same, spread out in depth as well
+    fn cloud() -> Vec<Body> {
+        cluster().into_iter().enumerate().map(|(i, body)| {
+            let position = Coordinate { z: (i as Real * 71.) % 89., ..body.position };
+            body.at(position)
+        }).collect()
+    }
+
+    fn positions_of(bodies: &[Body]) -> Vec<Coordinate> {
+        bodies.iter().map(|body| body.position).collect()
+    }
+
+    fn largest_difference(lhs: &[EuclideanVector], rhs: &[EuclideanVector]) -> Real {
+        lhs.iter().zip(rhs).map(|(&l, &r)| (l - r).magnitude() / r.magnitude()).fold(0., Real::max)
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn fully_opened_tree_matches_direct_summation() {
+        let bodies = cluster();
+        let positions = positions_of(&bodies);
+        let exact = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+        let approximated = BarnesHut::with_opening_angle(0.).accelerations(&bodies, &positions, &Constants::new());
+        assert!(largest_difference(&approximated, &exact) < 1e-9);
+    }
+
+    #[test]
+    fn tree_approximates_direct_summation() {
+        let bodies = cluster();
+        let positions = positions_of(&bodies);
+        let exact = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+        let approximated = BarnesHut::with_opening_angle(0.5).accelerations(&bodies, &positions, &Constants::new());
+        assert!(largest_difference(&approximated, &exact) < 0.05);
+    }
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn tree_approximates_direct_summation_in_three_dimensions() {
+        let bodies = cloud();
+        let positions = positions_of(&bodies);
+        let exact = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+        assert!(largest_difference(&BarnesHut::with_opening_angle(0.).accelerations(&bodies, &positions, &Constants::new()), &exact) < 1e-9);
+        assert!(largest_difference(&BarnesHut::with_opening_angle(0.5).accelerations(&bodies, &positions, &Constants::new()), &exact) < 0.05);
+    }
+
+    #[test]
+    fn lonely_body_is_not_pulled_by_itself() {
+        let bodies = vec![Body::new().with_mass(5.).at(Coordinate { x: 3., y: 4., z: 0. })];
+        let accelerations = BarnesHut::with_opening_angle(0.5).accelerations(&bodies, &positions_of(&bodies), &Constants::new());
+        assert_eq!(accelerations[0], 0.);
+    }
+
+    #[test]
+    fn coinciding_bodies_do_not_break_the_tree() {
+        let bodies = vec![Body::new().with_mass(1.), Body::new().with_mass(1.), Body::new().with_mass(1.).at(Coordinate { x: 10., y: 0., z: 0. })];
+        let accelerations = BarnesHut::with_opening_angle(0.5).accelerations(&bodies, &positions_of(&bodies), &Constants::new());
+        assert!(accelerations[0].dx > 0.);
+        assert!(accelerations[2].dx < 0.);
+    }
+}
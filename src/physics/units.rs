@@ -0,0 +1,17 @@
+// values are given in full, even if single precision cannot hold them
+#![allow(clippy::excessive_precision)]
+
+use crate::maths::Real;
+
+// SI values, for scenarios modelling real systems directly in kilograms, meters and seconds.
+pub const GRAVITATIONAL_CONSTANT: Real = 6.674_30e-11;
+pub const ASTRONOMICAL_UNIT: Real = 1.495_978_707e11;
+pub const DAY: Real = 86_400.;
+pub const SOLAR_MASS: Real = 1.988_47e30;
+pub const EARTH_MASS: Real = 5.972_2e24;
+pub const MOON_MASS: Real = 7.342e22;
+pub const EARTH_DENSITY: Real = 5_514.;
+pub const SOLAR_DENSITY: Real = 1_408.;
+pub const MOON_DENSITY: Real = 3_344.;
+pub const EARTH_SPIN: Real = 7.292_115e-5; // radians per second, relative to the stars
+pub const SOLAR_RADIATION: Real = 3.828e26 / 299_792_458.; // luminosity over the speed of light
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use crate::maths::{Coordinate, EuclideanVector};
+    use crate::physics::orbit::Elements;
+    use crate::physics::quantities::{Length, Mass};
+    use crate::physics::units::{ASTRONOMICAL_UNIT, DAY, EARTH_MASS, SOLAR_DENSITY, SOLAR_MASS};
+    use crate::physics::{Body, Constants};
+
+    #[test]
+    fn earth_orbits_the_sun_in_a_year() {
+        let sun = Body::new().with_mass(SOLAR_MASS);
+        let earth = Body::new().with_mass(EARTH_MASS).at(Coordinate { x: ASTRONOMICAL_UNIT, y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: 29_780., dz: 0. });
+        let period = Elements::of(&earth, &sun, &Constants::si()).period.unwrap();
+        assert!((period / DAY - 365.25).abs() < 2.);
+    }
+
+    #[test]
+    fn earth_sized_body_has_earth_radius() {
+        assert!((Constants::si().radius_of(Mass(EARTH_MASS)) / Length(6.371e6) - 1.).abs() < 0.01);
+    }
+
+    #[test]
+    fn sun_has_solar_radius() {
+        assert!((Body::new().with_mass(SOLAR_MASS).with_density(SOLAR_DENSITY).radius / 6.957e8 - 1.).abs() < 0.01);
+    }
+}
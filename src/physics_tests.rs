@@ -1,27 +1,33 @@
 #[cfg(test)]
 mod tests {
-    use crate::maths::{Coordinate, EuclideanVector};
-    use crate::physics::Body;
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::physics::quantities::{Length, Mass};
+    use crate::physics::{barycenter, checksum, fnv, gravitational_pull, radius_at, Body, BodyId, Constants, DirectSummation, ForceSolver};
+    use std::collections::HashSet;
+
+    fn softened(softening: Real) -> Constants {
+        Constants { softening, ..Constants::new() }
+    }
 
     #[test]
     fn when_body_with_no_forces_is_updated_its_velocity_does_not_change() {
-        let initial_velocity = EuclideanVector { dx: 4.4, dy: 7.7 };
+        let initial_velocity = EuclideanVector { dx: 4.4, dy: 7.7, dz: 0. };
         let mut body = Body::new().with_mass(1.).moving(initial_velocity);
-        body.update();
+        body.update(1.);
         assert_eq!(body.velocity, initial_velocity);
     }
 
     #[test]
     fn a_body_may_be_gravitationally_pulled_by_other_body() {
         let mut body = Body::new()
-            .at(Coordinate { x: 0.0, y: 0.0 })
+            .at(Coordinate { x: 0.0, y: 0.0, z: 0. })
             .with_mass(1.);
         let other_body = Body::new()
-            .at(Coordinate { x: 10.0, y: 10.0 })
+            .at(Coordinate { x: 10.0, y: 10.0, z: 0. })
             .with_mass(1.);
 
-        body.add_pull_from(&other_body);
-        body.update();
+        body.forces.push(body.pull_from(&other_body, &Constants::new()));
+        body.update(1.);
 
         assert!(body.velocity.dx > 0.);
         assert!(body.velocity.dy > 0.);
@@ -29,19 +35,151 @@ mod tests {
 
     #[test]
     fn a_moving_body_velocity_is_also_affected_by_gravitational_pull() {
-        let initial_velocity = EuclideanVector { dx: 1.0, dy: 1.0 };
+        let initial_velocity = EuclideanVector { dx: 1.0, dy: 1.0, dz: 0. };
         let mut body = Body::new()
-            .at(Coordinate { x: 0.0, y: 0.0 })
+            .at(Coordinate { x: 0.0, y: 0.0, z: 0. })
             .with_mass(1.)
             .moving(initial_velocity);
         let other_body = Body::new()
-            .at(Coordinate { x: -10.0, y: 10.0 })
+            .at(Coordinate { x: -10.0, y: 10.0, z: 0. })
             .with_mass(1.);
 
-        body.add_pull_from(&other_body);
-        body.update();
+        body.forces.push(body.pull_from(&other_body, &Constants::new()));
+        body.update(1.);
 
         assert!(body.velocity.dx < initial_velocity.dx);
         assert!(body.velocity.dy > initial_velocity.dy);
     }
+
+    #[test]
+    fn spinning_body_turns_within_a_single_turn() {
+        let mut body = Body::new().with_spin(-1.);
+        body.turn(1.);
+        assert!((body.rotation - (2. * crate::maths::consts::PI - 1.)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn body_is_displayed_with_its_name_mass_position_and_velocity() {
+        let body = Body::new().named("Probe").with_mass(2.).at(Coordinate { x: 1., y: -2., z: 3. }).moving(EuclideanVector { dx: 0.5, dy: 0., dz: -1. });
+        assert_eq!(body.to_string(), "Probe (mass: 2) at (1.0000, -2.0000, 3.0000) moving (0.5000, 0.0000, -1.0000)");
+        assert!(format!("{:?}", body).contains("name: \"Probe\""));
+    }
+
+    #[test]
+    fn fields_missing_from_a_saved_body_are_the_default_ones() {
+        let body: Body = toml::from_str("name = \"Probe\"\nmass = 2.0\n[position]\nx = 1.0\ny = -2.0\n").unwrap();
+        assert_eq!(body.name, "Probe");
+        assert_eq!(body.position.x, 1.);
+        assert_eq!(body.velocity, 0.);
+        assert!(body.engine.is_none());
+    }
+
+    #[test]
+    fn body_equals_only_itself_in_the_same_state() {
+        let body = Body::new().with_mass(2.).at(Coordinate { x: 1., y: -2., z: 0. });
+        let mut moved = body.clone();
+        moved.displace(EuclideanVector { dx: 1., dy: 0., dz: 0. });
+        assert_eq!(body.clone(), body);
+        assert_ne!(moved, body);
+        assert_ne!(Body::new().with_mass(2.).at(Coordinate { x: 1., y: -2., z: 0. }), body);
+    }
+
+    #[test]
+    fn bodies_are_told_apart_by_their_ids_across_states() {
+        let body = Body::new().with_mass(2.);
+        let mut moved = body.clone();
+        moved.displace(EuclideanVector { dx: 1., dy: 0., dz: 0. });
+        let ids: HashSet<BodyId> = vec![body.id, moved.id, Body::new().id].into_iter().collect();
+        assert_eq!(ids.len(), 2);
+    }
+
+    #[test]
+    fn many_tiny_displacements_add_up_despite_rounding() {
+        let mut body = Body::new().at(Coordinate { x: 1e8, y: 0., z: 0. });
+        for _ in 0..1_000_000 { body.displace(EuclideanVector { dx: 1e-9, dy: 0., dz: 0. }); }
+        assert!((body.position.x - (1e8 + 1e-3)).abs() < 1e-7);
+    }
+
+    #[test]
+    fn coinciding_bodies_do_not_pull_each_other() {
+        let body = Body::new().with_mass(1.);
+        assert_eq!(body.pull_from(&Body::new().with_mass(1.), &Constants::new()), 0.);
+    }
+
+    #[test]
+    fn softening_limits_the_pull_of_nearly_coinciding_bodies() {
+        let at = Coordinate { x: 0., y: 0., z: 0. };
+        let close_by = Coordinate { x: 1e-6, y: 0., z: 0. };
+        assert!(gravitational_pull(at, close_by, 1., &softened(1.)).magnitude() < gravitational_pull(at, close_by, 1., &softened(0.)).magnitude());
+        assert!(gravitational_pull(at, close_by, 1., &softened(1.)).magnitude().is_finite());
+    }
+
+    #[test]
+    fn softening_is_negligible_for_distant_bodies() {
+        let at = Coordinate { x: 0., y: 0., z: 0. };
+        let far_away = Coordinate { x: 1000., y: 0., z: 0. };
+        let ratio = gravitational_pull(at, far_away, 1., &softened(1.)).magnitude() / gravitational_pull(at, far_away, 1., &softened(0.)).magnitude();
+        assert!((ratio - 1.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn barycenter_is_the_mass_weighted_center() {
+        let bodies = vec![
+            Body::new().with_mass(3.).at(Coordinate { x: 0., y: 0., z: 0. }),
+            Body::new().with_mass(1.).at(Coordinate { x: 4., y: 8., z: 0. }),
+        ];
+        let center = barycenter(&bodies);
+        assert_eq!((center.x, center.y), (1., 2.));
+    }
+
+    #[test]
+    fn barycenter_of_nothing_is_the_origin() {
+        let center = barycenter(&[]);
+        assert_eq!((center.x, center.y), (0., 0.));
+    }
+
+    #[test]
+    fn fnv_matches_reference_values() {
+        assert_eq!(fnv(Vec::new()), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv("a".bytes()), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv("foobar".bytes()), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn checksum_tells_apart_slightest_differences() {
+        let bodies = vec![Body::new().with_mass(3.), Body::new().with_mass(1.).at(Coordinate { x: 4., y: 8., z: 0. })];
+        let mut nudged = bodies.clone();
+        nudged[1].position.x = Real::from_bits(nudged[1].position.x.to_bits() + 1);
+        assert_eq!(checksum(&bodies), checksum(&bodies.clone()));
+        assert_ne!(checksum(&bodies), checksum(&nudged));
+    }
+
+    #[test]
+    fn denser_bodies_are_smaller() {
+        let dense = Constants { density: 30., ..Constants::new() };
+        assert!(dense.radius_of(Mass(10.)) < Constants::new().radius_of(Mass(10.)));
+        assert_eq!(Body::new().with_mass(10.).radius, Constants::new().radius_of(Mass(10.)).0);
+    }
+
+    #[test]
+    fn bodies_may_have_a_density_of_their_own() {
+        assert_eq!(Body::new().with_density(30.).with_mass(10.).radius, radius_at(Mass(10.), 30.).0);
+        assert_eq!(Body::new().with_mass(10.).with_density(30.).radius, radius_at(Mass(10.), 30.).0);
+    }
+
+    #[test]
+    fn explicit_radius_determines_the_density() {
+        let body = Body::new().with_mass(10.).with_radius(2.);
+        assert!((radius_at(Mass(body.mass), body.density.unwrap()) - Length(2.)).0.abs() < 1e-12);
+    }
+
+    #[test]
+    fn accelerations_of_many_bodies_are_gathered_in_their_order() {
+        let bodies: Vec<Body> = (0..300).map(|i| Body::new().with_mass(1.).at(Coordinate { x: (i % 17) as Real * 10., y: (i / 17) as Real * 10., z: 0. })).collect();
+        let positions: Vec<Coordinate> = bodies.iter().map(|body| body.position).collect();
+        let all = DirectSummation.accelerations(&bodies, &positions, &Constants::new());
+        for i in (0..bodies.len()).step_by(37) {
+            assert_eq!(all[i], DirectSummation.accelerations_of(&bodies, &positions, &[i], &Constants::new())[0]);
+        }
+    }
 }
@@ -0,0 +1,348 @@
+use crate::maths::{consts::PI, Coordinate, EuclideanVector, Random, Real};
+use crate::physics::binary;
+use crate::physics::collision::CollisionMode;
+use crate::physics::integration::{Integrator, NOMINAL_TIME_STEP};
+use crate::physics::orbit::Elements;
+use crate::physics::quantities::Mass;
+use crate::physics::swarm::Ring;
+use crate::physics::{units, Body, Constants};
+use crate::{Situation, SOLVERS};
+
+pub const DEFAULT: &str = "imagirus";
+pub const NAMES: [&str; 14] = ["imagirus", "solar-system", "earth-moon", "earth-orbit", "ephemeris", "solar-sail", "asteroid-belt", "saturn", "galaxies", "binary", "triple", "figure-eight", "lagrange-triangle", "butterfly"];
+
+const SOLAR_SYSTEM_SUN_MASS: Real = 100.;
+const SOLAR_SYSTEM_ASTRONOMICAL_UNIT: Real = 100.;
+const SI_PIXELS_PER_UNIT: Real = 200. / units::ASTRONOMICAL_UNIT;
+const SI_TIME_STEP: Real = units::DAY / 10.;
+const EARTH_ORBIT_PIXELS_PER_UNIT: Real = 300. / 4.2e7; // up to geostationary orbit
+const EARTH_ORBIT_TIME_STEP: Real = 10.;
+const ASTEROIDS: usize = 2000;
+const ASTEROID_BELT: (Real, Real) = (2.0, 3.5); // in AU, spanning the Kirkwood gaps opened by Jupiter
+const ASTEROID_SEED: u64 = 0x4b69_726b_776f_6f64;
+const SATURN_RINGS: Ring = Ring { inner_radius: 60., outer_radius: 120., count: 3000, dispersion: 0.005 };
+const SATURN_RINGS_SEED: u64 = 0x0043_6173_7369_6e69;
+const GALAXY_STARS: usize = 1500; // in the disc of each galaxy
+const GALAXY_DISC: (Real, Real) = (20., 100.); // inner and outer radius
+const GALAXY_MASSES: (Real, Real) = (10., 2.); // of the core and of the whole disc
+const GALAXY_SOFTENING: Real = 2.;
+const GALAXY_SEED: u64 = 0x0041_6e74_656e_6e61;
+const CHOREOGRAPHY_CONSTANTS: Constants = Constants { gravitational: 1., ..Constants::new() }; // as the orbits were found in
+const CHOREOGRAPHY_RADIUS: Real = 0.01; // the orbits are those of point masses
+const CHOREOGRAPHY_PIXELS_PER_UNIT: Real = 250.;
+const CHOREOGRAPHY_TIME_STEP: Real = 0.01;
+const GENERATED_PLANETS: (u64, u64) = (2, 6); // fewest and most
+const GENERATED_MOONS: u64 = 2; // at most, per planet
+const GENERATED_SPACING: Real = 8.; // least distance between neighbouring planets, in their mutual Hill radii
+const GENERATED_VIEW: Real = 350.; // pixels up to the outermost planet
+const SAIL_LIGHTNESS: Real = 0.05; // ratio of the push of sunlight to the pull of the Sun, a.k.a. β
+const DUST_LIGHTNESS: [Real; 5] = [0.1, 0.3, 0.5, 0.7, 0.9]; // bound to the Sun below 0.5, blown away above it
+
+// (name, semi-major axis in AU, eccentricity, mass in solar masses, longitude of perihelion, inclination to the
+// ecliptic and longitude of the ascending node, all three in degrees)
+const PLANETS: [(&str, Real, Real, Real, Real, Real, Real); 8] = [
+    ("Mercury", 0.387, 0.2056, 1.660e-7, 77.5, 7.00, 48.3),
+    ("Venus", 0.723, 0.0068, 2.448e-6, 131.5, 3.39, 76.7),
+    ("Earth", 1.000, 0.0167, 3.003e-6, 102.9, 0., 0.),
+    ("Mars", 1.524, 0.0934, 3.227e-7, 336.0, 1.85, 49.6),
+    ("Jupiter", 5.203, 0.0484, 9.545e-4, 14.3, 1.30, 100.5),
+    ("Saturn", 9.537, 0.0542, 2.858e-4, 93.1, 2.49, 113.7),
+    ("Uranus", 19.19, 0.0472, 4.366e-5, 173.0, 0.77, 74.0),
+    ("Neptune", 30.07, 0.0086, 5.151e-5, 48.1, 1.77, 131.8),
+];
+
+// the given function adjusts the constants each scenario is meant for
+pub fn build(name: &str, constants: impl Fn(Constants) -> Constants) -> Option<Situation> {
+    match name {
+        "imagirus" => Some(imagirus(constants(Constants::new()))),
+        "solar-system" => Some(solar_system(constants(Constants::new()))),
+        "earth-moon" => Some(earth_and_moon(constants(Constants::si()))),
+        "earth-orbit" => Some(earth_orbit(constants(Constants::si()))),
+        "ephemeris" => Some(ephemeris(constants(Constants::si()))),
+        "solar-sail" => Some(solar_sail(constants(Constants::si()))),
+        "asteroid-belt" => Some(asteroid_belt(constants(Constants::new()))),
+        "saturn" => Some(saturn(constants(Constants::new()))),
+        "binary" => Some(binary_star(constants(Constants::new()))),
+        "triple" => Some(triple_star(constants(Constants::new()))),
+        "figure-eight" => Some(figure_eight(constants(CHOREOGRAPHY_CONSTANTS))),
+        "lagrange-triangle" => Some(lagrange_triangle(constants(CHOREOGRAPHY_CONSTANTS))),
+        "butterfly" => Some(butterfly(constants(CHOREOGRAPHY_CONSTANTS))),
+        "galaxies" => Some(galaxies(constants(Constants { softening: GALAXY_SOFTENING, ..Constants::new() }))),
+        _ => None,
+    }
+}
+
+fn imagirus(constants: Constants) -> Situation {
+    Situation::new().with_constants(constants).with(
+        Body::new().with_mass(70.).at(Coordinate{x: 0., y: 0., z: 0.}).moving(EuclideanVector{dx: 0., dy: 0., dz: 0.}).with_atmosphere(20., 0.05).with_oblateness(0.2).with_spin(0.05).named("Imagirus*")
+    ).with(
+        Body::new().with_mass(1.).at(Coordinate{x: 150., y: 0., z: 0.}).moving(EuclideanVector{dx: 0., dy: 2., dz: 0.}).with_spin(0.2).named("Imagirus I")
+    ).with(
+        Body::new().with_mass(1.).at(Coordinate{x: -400., y: 0., z: 0.}).moving(EuclideanVector{dx: 0., dy: 1., dz: 0.}).named("Imagirus II")
+    ).with(
+        Body::new().with_mass(0.1).at(Coordinate{x: 0., y: -300., z: 0.}).moving(EuclideanVector{dx: 0.9, dy: 0., dz: 0.}).with_engine(0.01, 2.).named("Feather")
+    )
+}
+
+// the Sun and the planets at their perihelia, with masses and distances scaled down to toy units, and the ecliptic
+// for the plane of the simulation
+fn solar_system(constants: Constants) -> Situation {
+    let mut situation = Situation::new().with_constants(constants).with(
+        Body::new().with_mass(SOLAR_SYSTEM_SUN_MASS).named("Sun")
+    );
+
+    let (sun, gravitational_parameter) = (situation.bodies[0].position, constants.gravitational * SOLAR_SYSTEM_SUN_MASS);
+    for &(name, semi_major_axis, eccentricity, mass, longitude, inclination, node) in &PLANETS {
+        let elements = Elements::elliptic(SOLAR_SYSTEM_ASTRONOMICAL_UNIT * semi_major_axis, eccentricity, (longitude - node).to_radians(), gravitational_parameter)
+            .inclined(inclination.to_radians(), node.to_radians());
+        let (position, velocity) = elements.state_at(0., gravitational_parameter);
+        situation.add(
+            Body::new()
+                .with_mass(SOLAR_SYSTEM_SUN_MASS * mass)
+                .at(sun + position)
+                .moving(velocity)
+                .named(name)
+        );
+    }
+
+    situation
+}
+
+// the Earth alone, meant for satellites loaded from two-line element sets
+fn earth_orbit(constants: Constants) -> Situation {
+    Situation::new().with_constants(constants).with_scale(EARTH_ORBIT_PIXELS_PER_UNIT, EARTH_ORBIT_TIME_STEP).with(
+        Body::new().with_mass(units::EARTH_MASS).with_spin(units::EARTH_SPIN).named("Earth")
+    )
+}
+
+// no bodies of its own, meant for ones imported from JPL Horizons
+fn ephemeris(constants: Constants) -> Situation {
+    Situation::new().with_constants(constants).with_scale(SI_PIXELS_PER_UNIT, SI_TIME_STEP)
+}
+
+fn earth_and_moon(constants: Constants) -> Situation {
+    let earth_orbit = Coordinate { x: units::ASTRONOMICAL_UNIT, y: 0., z: 0. };
+    // densely marked, for the Moon winding around the orbit of the Earth to show
+    Situation::new().with_constants(constants).with_scale(SI_PIXELS_PER_UNIT, SI_TIME_STEP).with_trails(4000, 1).with(
+        Body::new().with_mass(units::SOLAR_MASS).with_density(units::SOLAR_DENSITY).named("Sun")
+    ).with(
+        Body::new().with_mass(units::EARTH_MASS).at(earth_orbit).moving(EuclideanVector{dx: 0., dy: 29_780., dz: 0.}).with_spin(units::EARTH_SPIN).with_color(0.3, 0.5, 1.).named("Earth")
+    ).with(
+        Body::new().with_mass(units::MOON_MASS).with_density(units::MOON_DENSITY).at(earth_orbit + EuclideanVector{dx: 3.844e8, dy: 0., dz: 0.}).moving(EuclideanVector{dx: 0., dy: 29_780. + 1_022., dz: 0.}).with_color(0.8, 0.8, 0.7).named("Moon")
+    )
+}
+
+// the Sun and Jupiter, with asteroids on circular orbits in between, as massless particles
+fn asteroid_belt(constants: Constants) -> Situation {
+    let (_, jupiter_distance, _, jupiter_mass, ..) = PLANETS[4];
+    let jupiter_distance = SOLAR_SYSTEM_ASTRONOMICAL_UNIT * jupiter_distance;
+    let jupiter_speed = (constants.gravitational * SOLAR_SYSTEM_SUN_MASS / jupiter_distance).sqrt();
+
+    let mut situation = Situation::new().with_constants(constants).with(
+        Body::new().with_mass(SOLAR_SYSTEM_SUN_MASS).named("Sun")
+    ).with(
+        Body::new().with_mass(SOLAR_SYSTEM_SUN_MASS * jupiter_mass).at(Coordinate { x: jupiter_distance, y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: jupiter_speed, dz: 0. }).named("Jupiter")
+    );
+
+    let belt = Ring {
+        inner_radius: SOLAR_SYSTEM_ASTRONOMICAL_UNIT * ASTEROID_BELT.0,
+        outer_radius: SOLAR_SYSTEM_ASTRONOMICAL_UNIT * ASTEROID_BELT.1,
+        count: ASTEROIDS,
+        dispersion: 0.,
+    };
+    for particle in belt.around(&situation.bodies[0], &constants, &mut Random::seeded(ASTEROID_SEED)) {
+        situation.add_particle(particle);
+    }
+
+    situation
+}
+
+// rings of particles, with a moon just outside of them to stir them
+fn saturn(constants: Constants) -> Situation {
+    let mimas_distance = 185.;
+    let mimas_speed = (constants.gravitational * 100. / mimas_distance).sqrt();
+    let mut situation = Situation::new().with_constants(constants).with(
+        Body::new().with_mass(100.).named("Saturn")
+    ).with(
+        Body::new().with_mass(0.05).at(Coordinate { x: mimas_distance, y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: mimas_speed, dz: 0. }).named("Mimas")
+    );
+
+    for particle in SATURN_RINGS.around(&situation.bodies[0], &constants, &mut Random::seeded(SATURN_RINGS_SEED)) {
+        situation.add_particle(particle);
+    }
+
+    situation
+}
+
+// an eccentric pair of stars, with a planet going around both of them far enough out to stay on a stable orbit
+fn binary_star(constants: Constants) -> Situation {
+    let stars = binary::binary(Body::new().with_mass(60.).named("A"), Body::new().with_mass(40.).named("B"), 80., 0.4, &constants);
+    let bodies = binary::paired(stars, Body::new().with_mass(0.5).named("AB b"), 400., 0., &constants);
+    let mut situation = Situation::new().with_constants(constants).with_scale(0.8, NOMINAL_TIME_STEP);
+    for body in bodies { situation.add(body); }
+    situation
+}
+
+// a close pair of stars, and a third one going around it on a wide eccentric orbit
+fn triple_star(constants: Constants) -> Situation {
+    let bodies = binary::hierarchical_triple(
+        (Body::new().with_mass(30.).named("Aa"), Body::new().with_mass(20.).named("Ab")), (20., 0.1),
+        Body::new().with_mass(40.).named("B"), (300., 0.3),
+        &constants,
+    );
+    let mut situation = Situation::new().with_constants(constants).with_scale(0.8, NOMINAL_TIME_STEP);
+    for body in bodies { situation.add(body); }
+    situation
+}
+
+// three equal masses chasing each other along a single figure of eight, as found by Chenciner and Montgomery
+#[allow(clippy::excessive_precision)]
+fn figure_eight(constants: Constants) -> Situation {
+    let (position, velocity) = (EuclideanVector { dx: 0.970_004_36, dy: -0.243_087_53, dz: 0. }, EuclideanVector { dx: -0.932_407_37, dy: -0.864_731_46, dz: 0. });
+    let origin = Coordinate { x: 0., y: 0., z: 0. };
+    three_bodies(constants, [(origin + position, -velocity / 2.), (origin + -position, -velocity / 2.), (origin, velocity)])
+}
+
+// three equal masses going around a circle at the corners of an equilateral triangle, as found by Lagrange;
+// it is unstable for equal masses, so the slightest error breaks it up sooner or later
+fn lagrange_triangle(constants: Constants) -> Situation {
+    let side = (3. as Real).sqrt();
+    let speed = (constants.gravitational * 3. / side.powi(3)).sqrt();
+    let corners = [0., 2. * PI / 3., 4. * PI / 3.].map(|angle| on_circle(Coordinate { x: 0., y: 0., z: 0. }, angle, 1., speed));
+    three_bodies(constants, corners)
+}
+
+// the butterfly I periodic orbit of Šuvakov and Dmitrašinović, starting from a collinear configuration; it is
+// unstable, and its close approaches make it the hardest of the three to follow
+#[allow(clippy::excessive_precision)]
+fn butterfly(constants: Constants) -> Situation {
+    let velocity = EuclideanVector { dx: 0.306_892_758_965_492, dy: 0.125_506_782_829_762, dz: 0. };
+    three_bodies(constants, [
+        (Coordinate { x: -1., y: 0., z: 0. }, velocity),
+        (Coordinate { x: 1., y: 0., z: 0. }, velocity),
+        (Coordinate { x: 0., y: 0., z: 0. }, velocity * -2.),
+    ])
+}
+
+// point masses of unity followed with the leapfrog integrator, passing through each other on close approaches
+fn three_bodies(constants: Constants, states: [(Coordinate, EuclideanVector); 3]) -> Situation {
+    let mut situation = Situation::new().with_constants(constants).with_scale(CHOREOGRAPHY_PIXELS_PER_UNIT, CHOREOGRAPHY_TIME_STEP);
+    situation.integrator = Integrator::Leapfrog;
+    situation.collisions.mode = CollisionMode::Ignore;
+    for ((position, velocity), name) in IntoIterator::into_iter(states).zip(&["A", "B", "C"]) {
+        situation.add(Body::new().with_mass(1.).with_radius(CHOREOGRAPHY_RADIUS).at(position).moving(velocity).named(name));
+    }
+    situation
+}
+
+// two discs of softened stars around heavier cores, spinning opposite ways and falling towards each other on a
+// roughly parabolic orbit; meant for the tree solver and the performance mode
+fn galaxies(constants: Constants) -> Situation {
+    let mut situation = Situation::new().with_constants(constants).with_scale(0.8, 0.5);
+    situation.solver = SOLVERS.iter().position(|&solver| solver == "barnes-hut").unwrap_or(0);
+    situation.integrator = Integrator::Leapfrog;
+    situation.collisions.mode = CollisionMode::Ignore;
+    situation.performance = true;
+
+    let (separation, offset) = (400., 120.);
+    let total_mass = 2. * (GALAXY_MASSES.0 + GALAXY_MASSES.1);
+    let approach_speed = (2. * constants.gravitational * total_mass / separation).sqrt() / 2.;
+    let mut random = Random::seeded(GALAXY_SEED);
+    for &(side, spin) in &[(-1., 1.), (1., -1.)] {
+        let center = Coordinate { x: side * separation / 2., y: side * offset / 2., z: 0. };
+        let velocity = EuclideanVector { dx: -side * approach_speed, dy: 0., dz: 0. };
+        for star in galaxy(center, velocity, spin, &constants, &mut random) { situation.add(star); }
+    }
+
+    situation
+}
+
+// a core with stars on circular orbits around it, each pulled by the core and by the part of the disc inside its orbit
+fn galaxy(center: Coordinate, velocity: EuclideanVector, spin: Real, constants: &Constants, random: &mut Random) -> Vec<Body> {
+    let (core_mass, disc_mass) = GALAXY_MASSES;
+    let star_mass = disc_mass / GALAXY_STARS as Real;
+    let mut stars = vec![Body::new().with_mass(core_mass).at(center).moving(velocity)];
+    for _ in 0..GALAXY_STARS {
+        let radius = random.between(GALAXY_DISC.0, GALAXY_DISC.1);
+        let enclosed_mass = core_mass + disc_mass * (radius - GALAXY_DISC.0) / (GALAXY_DISC.1 - GALAXY_DISC.0);
+        let speed = (constants.gravitational * enclosed_mass * radius / (radius * radius + constants.softening * constants.softening)).sqrt();
+        let (position, orbital_velocity) = on_circle(center, random.between(0., 2. * PI), radius, speed);
+        stars.push(Body::new().with_mass(star_mass).at(position).moving(velocity + orbital_velocity * spin));
+    }
+    stars
+}
+
+// a sail spiralling out of the orbit of the Earth, and dust grains of various sizes released from the same orbit
+fn solar_sail(constants: Constants) -> Situation {
+    let gravitational_parameter = constants.gravitational * units::SOLAR_MASS;
+    let circular_speed = (gravitational_parameter / units::ASTRONOMICAL_UNIT).sqrt();
+    let area_to_mass = |lightness: Real| lightness * 4. * PI * gravitational_parameter / units::SOLAR_RADIATION;
+    let on_earth_orbit = |angle: Real| {
+        (Coordinate::from_polar(units::ASTRONOMICAL_UNIT, angle), EuclideanVector::from_polar(circular_speed, angle + PI / 2.))
+    };
+
+    let (earth, earth_velocity) = on_earth_orbit(0.);
+    let (sail, sail_velocity) = on_earth_orbit(0.1);
+    let mut situation = Situation::new().with_constants(constants).with_scale(SI_PIXELS_PER_UNIT, SI_TIME_STEP).with(
+        Body::new().with_mass(units::SOLAR_MASS).with_density(units::SOLAR_DENSITY).with_radiation(units::SOLAR_RADIATION).named("Sun")
+    ).with(
+        Body::new().with_mass(units::EARTH_MASS).at(earth).moving(earth_velocity).named("Earth")
+    ).with(
+        Body::new().with_mass(1000.).at(sail).moving(sail_velocity).with_sail(area_to_mass(SAIL_LIGHTNESS)).named("Sail")
+    );
+
+    for (i, &lightness) in DUST_LIGHTNESS.iter().enumerate() {
+        let (position, velocity) = on_earth_orbit(PI + 0.1 * i as Real);
+        situation.add(Body::new().with_mass(1.).at(position).moving(velocity).with_sail(area_to_mass(lightness)).named(&format!("Dust (β = {})", lightness)));
+    }
+
+    situation
+}
+
+// a star with planets on nearly circular orbits, spaced widely enough to stay stable, and moons well within their
+// Hill spheres, all following from the seed alone
+pub fn generated(seed: u64, constants: Constants) -> Situation {
+    let mut random = Random::seeded(seed);
+    let star_mass = random.between(50., 200.);
+    let mut bodies = vec![Body::new().with_mass(star_mass).named(&format!("Star {}", seed))];
+
+    let planets = GENERATED_PLANETS.0 + random.next_u64() % (GENERATED_PLANETS.1 - GENERATED_PLANETS.0 + 1);
+    let (mut distance, mut previous_mass) = (random.between(60., 100.), 0.);
+    for letter in ('b'..).take(planets as usize) {
+        let mass = star_mass * log_uniform(&mut random, 1e-6, 3e-4);
+        if previous_mass > 0. {
+            let half_spacing = GENERATED_SPACING * ((mass + previous_mass) / (3. * star_mass)).cbrt() / 2.;
+            distance *= random.between(1., 1.15) * (1. + half_spacing) / (1. - half_spacing);
+        }
+        previous_mass = mass;
+
+        let eccentricity = random.between(0., 0.05);
+        let (position, velocity) = on_circle(Coordinate { x: 0., y: 0., z: 0. }, random.between(0., 2. * PI), distance, (constants.gravitational * (star_mass + mass) * (1. + eccentricity) / distance).sqrt());
+        bodies.push(Body::new().with_mass(mass).at(position).moving(velocity).named(&letter.to_string()));
+
+        let hill_radius = distance * (1. - eccentricity) * (mass / (3. * star_mass)).cbrt();
+        let mut moon_distance = Real::max(hill_radius * random.between(0.1, 0.2), 3. * constants.radius_of(Mass(mass)).0);
+        for numeral in ["I", "II", "III"].iter().take((random.next_u64() % (GENERATED_MOONS + 1)) as usize) {
+            if moon_distance > hill_radius / 2. { break; }
+            let moon_mass = mass * log_uniform(&mut random, 1e-4, 1e-2);
+            let (moon_position, orbital_velocity) = on_circle(position, random.between(0., 2. * PI), moon_distance, (constants.gravitational * (mass + moon_mass) / moon_distance).sqrt());
+            let moon = Body::new().with_mass(moon_mass).at(moon_position).moving(velocity + orbital_velocity);
+            bodies.push(moon.named(&format!("{} {}", letter, numeral)));
+            moon_distance *= 1.8;
+        }
+    }
+
+    let mut situation = Situation::new().with_constants(constants).with_scale(GENERATED_VIEW / distance, NOMINAL_TIME_STEP);
+    for body in bodies { situation.add(body); }
+    situation
+}
+
+// position and relative velocity on a counter-clockwise circular orbit around the given center
+fn on_circle(center: Coordinate, angle: Real, radius: Real, speed: Real) -> (Coordinate, EuclideanVector) {
+    (center + EuclideanVector::from_polar(radius, angle), EuclideanVector::from_polar(speed, angle + PI / 2.))
+}
+
+// spread evenly across orders of magnitude
+fn log_uniform(random: &mut Random, low: Real, high: Real) -> Real {
+    random.between(low.ln(), high.ln()).exp()
+}
@@ -0,0 +1,576 @@
+#[cfg(test)]
+mod tests {
+    use crate::physics::checksum;
+    use crate::physics::collision::CollisionMode;
+    use crate::physics::orbit::{dominant_attractor, Elements};
+    use crate::physics::{Body, Constants};
+    use crate::scenarios::{build, generated, NAMES};
+    use crate::maths::{Coordinate, EuclideanVector, Real};
+    use crate::{body_color, grid_spacing, place_labels, ramp_color, scale_bar, starfield, Following, Point, Properties, Quality, Rate, Situation, GRAPH_LENGTH, MINIMAP_SIZE, RAMPS, SOLVERS, STARFIELD_SHADES, STARFIELD_TILE};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn all_scenarios_can_be_built() {
+        for name in &NAMES {
+            assert!(build(name, |constants| constants).is_some());
+        }
+    }
+
+    #[test]
+    fn unknown_scenario_is_not_built() {
+        assert!(build("nemesis", |constants| constants).is_none());
+    }
+
+    #[test]
+    fn planets_of_the_solar_system_orbit_the_sun() {
+        let situation = build("solar-system", |constants| constants).unwrap();
+        assert_eq!(situation.bodies.len(), 9);
+        for planet in 1..situation.bodies.len() {
+            assert_eq!(dominant_attractor(&situation.bodies, planet), Some(0));
+            let elements = Elements::of(&situation.bodies[planet], &situation.bodies[0], &situation.constants);
+            assert!(elements.eccentricity < 0.25);
+        }
+    }
+
+    #[test]
+    fn asteroids_are_particles_pulled_by_the_sun_and_jupiter_alone() {
+        let mut situation = build("asteroid-belt", |constants| constants).unwrap();
+        assert_eq!(situation.bodies.len(), 2);
+        assert_eq!(situation.particles.len(), 2000);
+
+        let sun = situation.bodies[0].position;
+        situation.update();
+        assert_eq!(situation.particles.len(), 2000);
+        assert!(situation.particles.iter().all(|particle| (particle.position - sun).magnitude() > 190.));
+    }
+
+    // fragmenting collisions included, as they are the only ones drawing random numbers
+    fn trajectory_checksums_of(name: &str, updates: usize) -> Vec<u64> {
+        let mut situation = build(name, |constants| constants).unwrap();
+        situation.collisions.mode = CollisionMode::Fragment;
+        (0..updates).map(|_| { situation.update(); checksum(&situation.bodies) }).collect()
+    }
+
+    #[test]
+    fn replayed_scenario_follows_identical_trajectory() {
+        for name in &["imagirus", "solar-system"] {
+            assert_eq!(trajectory_checksums_of(name, 500), trajectory_checksums_of(name, 500));
+        }
+    }
+
+    #[test]
+    fn saved_situation_loads_back_the_same() {
+        let mut situation = build("imagirus", |constants| constants).unwrap();
+        for _ in 0..10 { situation.update(); }
+        let loaded: Situation = toml::from_str(&toml::to_string(&situation).unwrap()).unwrap();
+        assert_eq!(loaded.updates, situation.updates);
+        assert_eq!(loaded.constants, situation.constants);
+        assert_eq!(checksum(&loaded.bodies), checksum(&situation.bodies));
+        assert!(loaded.bodies.iter().zip(&situation.bodies).all(|(lhs, rhs)| lhs.name == rhs.name && lhs.spin == rhs.spin));
+    }
+
+    #[test]
+    fn saved_situation_loads_back_simulated_the_same_way() {
+        let mut situation = build("figure-eight", |constants| constants).unwrap().with_trails(100, 3);
+        situation.switch_solver();
+        situation.set_opening_angle(0.3);
+        situation.adjust_restitution(-0.5);
+        situation.toggle_tidal_locking();
+        for _ in 0..10 { situation.update(); }
+
+        let loaded = Situation::loaded(&toml::to_string(&situation).unwrap()).unwrap();
+        assert_eq!((loaded.integrator, loaded.collisions.mode, loaded.collisions.restitution), (situation.integrator, CollisionMode::Ignore, 0.5));
+        assert_eq!((loaded.solver, loaded.opening_angle, loaded.solvers[2].to_string()), (situation.solver, 0.3, situation.solvers[2].to_string()));
+        assert_eq!((loaded.trail_length, loaded.trail_spacing), (100, 3));
+        assert!(loaded.tidal_locking.enabled && !loaded.tidal_disruption.enabled);
+    }
+
+    // only as closely as the rounding errors compensated for, which are left out
+    #[test]
+    #[cfg_attr(feature = "single-precision", ignore = "within the tolerance of double precision only")]
+    fn saved_situation_loads_back_following_the_same_trajectory() {
+        let mut situation = build("figure-eight", |constants| constants).unwrap();
+        for _ in 0..10 { situation.update(); }
+        let mut loaded = Situation::loaded(&toml::to_string(&situation).unwrap()).unwrap();
+        for _ in 0..100 {
+            situation.update();
+            loaded.update();
+        }
+        assert!(loaded.bodies.iter().zip(&situation.bodies).all(|(lhs, rhs)| lhs.position.approx_eq(rhs.position, 1e-12)));
+    }
+
+    #[test]
+    fn situation_with_an_unknown_solver_is_not_loaded() {
+        assert!(Situation::loaded("solver = 99").is_err());
+        assert!(Situation::loaded("integrator = \"Verlet\"").is_err());
+    }
+
+    #[test]
+    fn zooming_keeps_the_point_under_the_cursor_in_place() {
+        let mut situation = build("imagirus", |constants| constants).unwrap();
+        let cursor = Point { x: 120., y: -45. };
+        let under_cursor = situation.transform().screen_to_world(Coordinate { x: 120., y: -45., z: 0. });
+        situation.zoom_at(cursor, true);
+        assert!(situation.transform().screen_to_world(Coordinate { x: 120., y: -45., z: 0. }).approx_eq(under_cursor, 1e-9));
+    }
+
+    #[test]
+    fn framing_fits_all_the_bodies_in_the_window() {
+        let mut situation = build("solar-system", |constants| constants).unwrap();
+        let half_size = Point { x: 400., y: 300. };
+        situation.frame_all(half_size);
+        situation.glide = None; // skipping right to where the camera glides to
+        let transform = situation.transform();
+        assert!(situation.bodies.iter().map(|body| transform.world_to_screen(body.position))
+            .all(|point| point.x.abs() <= 400. && point.y.abs() <= 300.));
+        situation.zoom_in();
+        let transform = situation.transform();
+        assert!(situation.bodies.iter().map(|body| transform.world_to_screen(body.position))
+            .any(|point| point.x.abs() > 400. * 0.9 || point.y.abs() > 300. * 0.9));
+    }
+
+    #[test]
+    fn constants_of_a_scenario_can_be_overridden() {
+        let situation = build("earth-moon", |mut constants| { constants.density = 1000.; constants }).unwrap();
+        assert_eq!(situation.constants.density, 1000.);
+        assert!(situation.constants.gravitational < 1e-10);
+    }
+
+    #[test]
+    fn figure_eight_returns_to_where_it_started_after_a_period() {
+        let mut situation = build("figure-eight", |constants| constants).unwrap();
+        let initial = situation.bodies.clone();
+        for _ in 0..(6.3259 / situation.time_step).round() as usize { situation.update(); }
+        assert!(situation.bodies.iter().zip(&initial).all(|(body, initial)| (body.position - initial.position).magnitude() < 0.01));
+    }
+
+    #[test]
+    fn galaxies_are_simulated_in_performance_mode() {
+        let mut situation = build("galaxies", |constants| constants).unwrap();
+        assert_eq!(situation.bodies.len(), 3002);
+        situation.update();
+        assert_eq!(situation.bodies.len(), 3002);
+        assert_eq!(situation.substeps, 1);
+        assert!(situation.trails.is_empty());
+        assert!(situation.totals.is_none());
+    }
+
+    #[test]
+    fn generated_system_follows_from_its_seed() {
+        let checksums = |seed| checksum(&generated(seed, Constants::new()).bodies);
+        assert_eq!(checksums(7), checksums(7));
+        assert_ne!(checksums(7), checksums(8));
+    }
+
+    #[test]
+    fn generated_planets_orbit_the_star_and_moons_their_planets() {
+        for seed in 0..20 {
+            let situation = generated(seed, Constants::new());
+            let (star, bodies) = situation.bodies.split_first().unwrap();
+            assert!(bodies.iter().filter(|body| body.name.len() == 1).count() >= 2);
+            for body in bodies {
+                let primary = bodies.iter().find(|planet| body.name.len() > 1 && body.name.starts_with(&planet.name)).unwrap_or(star);
+                let elements = Elements::of(body, primary, &situation.constants);
+                assert!(elements.eccentricity < 0.1, "{} of seed {}", body.name, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn trails_are_told_apart_by_color() {
+        let bodies = build("earth-moon", |constants| constants).unwrap().bodies;
+        assert_eq!(body_color(&bodies[1]), bodies[1].color.unwrap());
+        let mut uncolored = bodies.clone();
+        uncolored.iter_mut().for_each(|body| body.color = None);
+        assert_ne!(body_color(&uncolored[1]), body_color(&uncolored[2]));
+        assert_eq!(body_color(&uncolored[1]), body_color(&uncolored[1].clone()));
+    }
+
+    #[test]
+    fn trails_are_shortened_and_turned_off_at_runtime() {
+        let mut situation = build("earth-moon", |constants| constants).unwrap();
+        for _ in 0..600 { situation.update(); }
+        assert_eq!(situation.trails.len(), 3);
+        assert!(situation.trails.values().all(|trail| trail.points.len() == 600));
+
+        situation.adjust_trail_length(false);
+        assert_eq!(situation.trail_length, 2000);
+        situation.adjust_trail_spacing(false);
+        for _ in 0..2 { situation.adjust_trail_length(false); }
+        assert_eq!(situation.trail_length, 500);
+        assert!(situation.trails.values().all(|trail| trail.points.len() == 499));
+
+        for _ in 0..4 { situation.adjust_trail_length(false); }
+        assert_eq!(situation.trail_length, 0);
+        assert!(situation.trails.is_empty());
+    }
+
+    #[test]
+    fn labels_of_bodies_close_together_are_placed_apart_or_left_out() {
+        let at = |x: f64, y: f64| Point { x, y };
+        let labels = [(at(0., 0.), 40.), (at(1., 1.), 40.), (at(2., 0.), 40.), (at(0., 2.), 40.), (at(1., 0.), 40.), (at(300., 0.), 40.)];
+        let placed = place_labels(&labels);
+
+        assert_eq!(placed.iter().filter(|corner| corner.is_some()).count(), 5);
+        assert!(placed[3].is_none());
+        let corners: Vec<Point> = placed.into_iter().flatten().collect();
+        for (i, lhs) in corners.iter().enumerate() {
+            for rhs in &corners[i + 1..] {
+                assert!((lhs.x - rhs.x).abs() >= 40. || (lhs.y - rhs.y).abs() >= 10.);
+            }
+        }
+    }
+
+    #[test]
+    fn grid_spacing_is_the_power_of_ten_keeping_lines_apart() {
+        assert_eq!(grid_spacing(40.), 1.);
+        assert_eq!(grid_spacing(5.), 10.);
+        assert_eq!(grid_spacing(1.), 100.);
+        assert_eq!(grid_spacing(0.5), 100.);
+    }
+
+    #[test]
+    fn minimap_frames_all_bodies_however_far_the_camera_is() {
+        let mut situation = Situation::new()
+            .with(Body::new().with_mass(100.).at(Coordinate { x: -1000., y: 50., z: 0. }))
+            .with(Body::new().with_mass(1.).at(Coordinate { x: 3000., y: -20., z: 0. }));
+        situation.zoom_in();
+        situation.scroll(5000., 5000.);
+        let transform = situation.minimap_transform().unwrap();
+        for body in &situation.bodies {
+            let at = transform.world_to_screen(body.position);
+            assert!(at.x >= 0. && at.x <= MINIMAP_SIZE as Real && at.y >= 0. && at.y <= MINIMAP_SIZE as Real);
+        }
+        assert!(Situation::new().minimap_transform().is_none());
+    }
+
+    #[test]
+    fn scale_bar_is_the_longest_round_length_that_fits() {
+        assert_eq!(scale_bar(1.), (100., 100.));
+        assert_eq!(scale_bar(0.1), (1000., 100.));
+        assert_eq!(scale_bar(2.), (50., 100.));
+        assert_eq!(scale_bar(0.005), (20000., 100.));
+    }
+
+    #[test]
+    fn bodies_are_colored_along_the_ramp_by_their_property() {
+        let mut situation = Situation::new()
+            .with(Body::new().with_mass(1.).moving(EuclideanVector { dx: 3., dy: 4., dz: 0. }))
+            .with(Body::new().with_mass(10.))
+            .with(Body::new().with_mass(100.));
+        assert!(situation.body_colors().is_empty());
+
+        situation.switch_coloring();
+        assert_eq!(situation.coloring_range(), Some((1., 100.)));
+        let colors = situation.body_colors();
+        let ramp = RAMPS[situation.ramp].1;
+        assert_eq!(colors[&situation.bodies[0].id], ramp_color(ramp, 0.));
+        assert_eq!(colors[&situation.bodies[1].id], ramp_color(ramp, 0.5));
+        assert_eq!(colors[&situation.bodies[2].id], *ramp.last().unwrap());
+
+        situation.switch_coloring();
+        assert_eq!(situation.coloring_range(), Some((0., 5.)));
+    }
+
+    #[test]
+    fn quality_cycles_through_settings_known_by_name() {
+        let mut situation = Situation::new();
+        for _ in 0..3 {
+            situation.switch_quality();
+            assert_eq!(Quality::named(&situation.quality.to_string()), Some(situation.quality));
+        }
+        assert_eq!(situation.quality, Quality::Medium);
+        assert_eq!(Quality::named("ultra"), None);
+    }
+
+    #[test]
+    fn higher_quality_draws_no_thinner() {
+        let qualities = [Quality::Low, Quality::Medium, Quality::High];
+        for pair in qualities.windows(2) {
+            assert!(pair[0].trail_width() < pair[1].trail_width());
+            assert!(pair[0].vector_width() < pair[1].vector_width());
+            assert!(pair[0].dot_size() <= pair[1].dot_size());
+        }
+    }
+
+    #[test]
+    fn rates_count_what_happened_over_the_last_second() {
+        let start = Instant::now();
+        let mut rate = Rate::new(start);
+        for tick in 0..30 { rate.tick(start + Duration::from_millis(tick * 50)); }
+        assert_eq!(rate.per_second(start + Duration::from_millis(500)), None);
+        assert_eq!(rate.per_second(start + Duration::from_millis(1450)), Some(20));
+        assert_eq!(rate.per_second(start + Duration::from_secs(3)), Some(0));
+    }
+
+    #[test]
+    fn graphs_reach_back_a_bounded_number_of_updates_and_rewind_along() {
+        let mut situation = Situation::new()
+            .with(Body::new().with_mass(100.).at(Coordinate { x: 0., y: 0., z: 0. }))
+            .with(Body::new().with_mass(1.).at(Coordinate { x: 100., y: 0., z: 0. }).moving(EuclideanVector { dx: 0., dy: 1., dz: 0. }));
+        for _ in 0..GRAPH_LENGTH + 10 { situation.update(); }
+        assert_eq!(situation.graphed.len() as u64, GRAPH_LENGTH);
+        assert_eq!(situation.graphed.back().map(|&(passed, _, bodies)| (passed, bodies)), Some((situation.updates, 2)));
+
+        situation.rewind();
+        assert_eq!(situation.graphed.back().map(|&(passed, ..)| passed), Some(situation.updates));
+    }
+
+    #[test]
+    fn heatmap_is_recomputed_only_once_bodies_move_far_enough() {
+        let mut situation = Situation::new()
+            .with(Body::new().with_mass(100.).at(Coordinate { x: 0., y: 0., z: 0. }))
+            .with(Body::new().with_mass(1.).at(Coordinate { x: 100., y: 0., z: 0. }));
+        let half_size = Point { x: 160., y: 90. };
+        situation.refresh_heatmap(half_size);
+        assert!(situation.heatmap.is_none());
+
+        situation.toggle_heatmap();
+        situation.refresh_heatmap(half_size);
+        let potentials = |situation: &Situation| situation.heatmap.as_ref().unwrap().potentials.clone();
+        let computed = potentials(&situation);
+        assert_eq!(computed.len(), 20 * 12);
+
+        situation.bodies[0].position.x += 0.001;
+        situation.refresh_heatmap(half_size);
+        assert_eq!(potentials(&situation), computed);
+
+        situation.bodies[0].position.x += 50.;
+        situation.refresh_heatmap(half_size);
+        assert_ne!(potentials(&situation), computed);
+    }
+
+    #[test]
+    fn starfield_is_the_same_every_time_and_fits_its_tile() {
+        let stars = starfield();
+        let positions = |stars: &[(Point, usize)]| stars.iter().map(|(at, shade)| (at.x, at.y, *shade)).collect::<Vec<_>>();
+        assert_eq!(positions(&stars), positions(&starfield()));
+        assert!(stars.iter().all(|(at, shade)| at.x < STARFIELD_TILE && at.y < STARFIELD_TILE && *shade < STARFIELD_SHADES));
+        assert!(stars.iter().any(|(_, shade)| *shade > 0));
+    }
+
+    #[test]
+    fn pressing_on_a_body_tracks_it() {
+        let mut situation = Situation::new()
+            .with(Body::new().with_mass(100.).at(Coordinate { x: 0., y: 0., z: 0. }))
+            .with(Body::new().with_mass(1.).at(Coordinate { x: 100., y: 0., z: 0. }));
+        assert_eq!(situation.body_at(Point { x: 102., y: 1. }), Some(1));
+        assert_eq!(situation.body_at(Point { x: 50., y: 50. }), None);
+
+        situation.pressed_at(Point { x: 50., y: 50. });
+        assert_eq!(situation.tracked_body, None);
+        situation.pressed_at(Point { x: 102., y: 1. });
+        assert_eq!(situation.tracked_body, Some(1));
+        assert_eq!(situation.view().tracked, Some(situation.bodies[1].id));
+    }
+
+    #[test]
+    fn tracking_cycles_backwards_and_stops_where_the_camera_is() {
+        let mut situation = build("earth-moon", |constants| constants).unwrap();
+        situation.track_previous();
+        assert_eq!(situation.tracked_body, Some(2));
+        situation.track_previous();
+        assert_eq!(situation.tracked_body, Some(1));
+
+        situation.glide = None;
+        let center = situation.center_translation();
+        situation.stop_tracking();
+        assert!(!situation.tracking());
+        assert_eq!(situation.center_translation(), center);
+    }
+
+    #[test]
+    fn camera_follows_the_midpoint_of_two_bodies_as_they_are_remapped() {
+        let mut situation = build("earth-moon", |constants| constants).unwrap();
+        situation.follow(Some(Following::Midpoint(1, 2)));
+        situation.glide = None;
+        let midpoint = situation.bodies[1].position.lerp(situation.bodies[2].position, 0.5);
+        assert!((situation.center_translation() + EuclideanVector::towards(midpoint)).magnitude() < 1e-6);
+
+        situation.body_removed(0);
+        assert_eq!(situation.following, Some(Following::Midpoint(0, 1)));
+        situation.body_removed(1);
+        assert_eq!(situation.following, None);
+    }
+
+    #[test]
+    fn trails_are_hidden_body_by_body() {
+        let mut situation = build("earth-moon", |constants| constants).unwrap();
+        situation.update();
+        situation.toggle_trail_of(2);
+        for _ in 0..10 { situation.update(); }
+        assert_eq!(situation.trails.len(), 2);
+        assert!(!situation.trails.contains_key(&situation.bodies[2].id));
+
+        situation.toggle_trail_of(2);
+        situation.update();
+        assert_eq!(situation.trails.len(), 3);
+    }
+
+    #[test]
+    fn bodies_are_deleted_and_duplicated_keeping_the_tracked_one() {
+        let mut situation = build("earth-moon", |constants| constants).unwrap();
+        situation.track(Some(2));
+        situation.duplicate_body(1);
+        assert_eq!(situation.bodies.len(), 4);
+        assert_eq!(situation.bodies[3].name, "Earth (copy)");
+        assert_ne!(situation.bodies[3].id, situation.bodies[1].id);
+        assert!(situation.bodies[3].position != situation.bodies[1].position);
+
+        situation.delete_body(0);
+        assert_eq!(situation.tracked_body, Some(1));
+        assert_eq!(situation.bodies[1].name, "Moon");
+    }
+
+    #[test]
+    fn bodies_are_placed_with_the_velocity_dragged_out_from_them() {
+        let mut situation = Situation::new().with(Body::new().with_mass(1000.));
+        situation.start_placing(Point { x: 100., y: 0. });
+        situation.dragging_to(Point { x: 100., y: 50. });
+        situation.adjust_placed_mass(10.);
+        situation.finish_placing();
+
+        assert_eq!(situation.bodies.len(), 2);
+        let placed = &situation.bodies[1];
+        assert!(placed.position.approx_eq(Coordinate { x: 100., y: 0., z: 0. }, 1e-9));
+        assert!((placed.mass - 10.).abs() < 1e-9);
+        assert!(placed.velocity.dx.abs() < 1e-9 && placed.velocity.dy > 0.);
+        assert_eq!(situation.translation, EuclideanVector { dx: 0., dy: 0., dz: 0. });
+    }
+
+    #[test]
+    fn deleting_the_piloted_body_leaves_nothing_tracked_or_piloted() {
+        let mut situation = build("imagirus", |constants| constants).unwrap();
+        situation.toggle_piloting();
+        assert_eq!(situation.ship, Some(3));
+        situation.delete_body(3);
+        assert_eq!((situation.tracked_body, situation.ship), (None, None));
+        situation.update();
+    }
+
+    #[test]
+    fn edited_body_takes_on_the_properties_and_the_radius_of_its_new_mass() {
+        let mut situation = build("imagirus", |constants| constants).unwrap();
+        let radius = situation.bodies[1].radius;
+        let properties = Properties {
+            name: "Imagirus Ia".to_string(),
+            mass: 8.,
+            position: Coordinate { x: 160., y: 10., z: 0. },
+            velocity: EuclideanVector { dx: 0.5, dy: 1.5, dz: 0. },
+        };
+        situation.edit_body(situation.bodies[1].id, properties.clone());
+        assert_eq!(Properties::of(&situation.bodies[1]), properties);
+        assert!((situation.bodies[1].radius - 2. * radius).abs() < 1e-9);
+    }
+
+    #[test]
+    fn edit_is_applied_to_the_same_body_after_others_are_gone() {
+        let mut situation = build("imagirus", |constants| constants).unwrap();
+        let (edited, properties) = (situation.bodies[2].id, Properties::of(&situation.bodies[2]));
+        let renamed = Properties { name: "renamed".to_string(), ..properties };
+        situation.delete_body(1);
+        situation.edit_body(edited, renamed.clone());
+        assert_eq!(Properties::of(&situation.bodies[1]), renamed);
+
+        situation.delete_body(1);
+        let bodies = situation.bodies.clone();
+        situation.edit_body(edited, renamed);
+        assert_eq!(situation.bodies, bodies);
+    }
+
+    #[test]
+    fn simulation_stays_paused_while_a_body_is_edited() {
+        let mut situation = build("imagirus", |constants| constants).unwrap();
+        assert!(situation.start_editing());
+        assert!(!situation.start_editing());
+        situation.toggle_pause();
+        assert!(situation.paused);
+
+        situation.finish_editing();
+        situation.finish_editing();
+        assert!(!situation.paused);
+    }
+
+    #[test]
+    fn paused_simulation_is_stepped_one_update_at_a_time() {
+        let mut situation = build("imagirus", |constants| constants).unwrap();
+        for _ in 0..10 { situation.update(); }
+        situation.toggle_pause();
+        situation.update();
+        assert_eq!(situation.updates, 10);
+
+        situation.replay();
+        assert_eq!(situation.updates, 11);
+        let stepped = checksum(&situation.bodies);
+        situation.rewind();
+        situation.replay();
+        assert_eq!((situation.updates, checksum(&situation.bodies)), (11, stepped));
+        assert!(situation.paused);
+    }
+
+    #[test]
+    fn faster_time_runs_whole_updates_and_slower_time_shorter_ones() {
+        let mut normal = build("imagirus", |constants| constants).unwrap();
+        for _ in 0..10 { normal.update(); }
+        let mut fast = build("imagirus", |constants| constants).unwrap();
+        for _ in 0..3 { fast.adjust_time_scale(true); }
+        assert_eq!(fast.time_scale, 10.);
+        fast.update();
+        assert_eq!((fast.updates, checksum(&fast.bodies)), (normal.updates, checksum(&normal.bodies)));
+
+        let mut slow = build("imagirus", |constants| constants).unwrap();
+        slow.adjust_time_scale(false);
+        assert_eq!(slow.time_scale, 0.5);
+        let start = slow.bodies[1].position;
+        slow.update();
+        let expected = build("imagirus", |constants| constants).unwrap().bodies[1].velocity * slow.time_step * 0.5;
+        assert!((slow.bodies[1].position - start).approx_eq(expected, 1e-3));
+    }
+
+    #[test]
+    fn faster_time_is_recorded_once_per_update_shown() {
+        let mut situation = build("imagirus", |constants| constants).unwrap().with_trails(2000, 3);
+        for _ in 0..12 { situation.adjust_time_scale(true); }
+        assert_eq!(situation.time_scale, 1000.);
+        for _ in 0..2 { situation.update(); }
+        assert_eq!(situation.updates, 2000);
+        assert_eq!(situation.graphed.len(), 2);
+        assert!(situation.trails.values().all(|trail| trail.points.len() == 2));
+
+        situation.rewind();
+        assert_eq!(situation.updates, 1000);
+    }
+
+    #[test]
+    fn maneuver_prediction_is_made_again_only_once_something_changed() {
+        let mut situation = build("earth-moon", |constants| constants).unwrap();
+        situation.tracked_body = Some(2);
+        situation.toggle_maneuver();
+        situation.paused = true;
+        situation.update();
+        assert!(situation.maneuver_prediction.as_ref().is_some_and(|(_, path)| !path.is_empty()));
+
+        situation.maneuver_prediction.as_mut().unwrap().1.clear();
+        situation.update();
+        assert!(situation.maneuver_prediction.as_ref().is_some_and(|(_, path)| path.is_empty()));
+        situation.adjust_maneuver(1., 0.);
+        situation.update();
+        assert!(situation.maneuver_prediction.as_ref().is_some_and(|(_, path)| !path.is_empty()));
+
+        situation.toggle_performance();
+        situation.update();
+        assert!(situation.maneuver_prediction.is_none());
+    }
+
+    #[test]
+    fn opening_angle_is_adjusted_within_bounds_and_shown_by_barnes_hut() {
+        let mut situation = Situation::new();
+        situation.solver = SOLVERS.iter().position(|&name| name == "barnes-hut").unwrap();
+        situation.adjust_opening_angle(0.2);
+        assert_eq!(situation.solvers[situation.solver].to_string(), "Barnes-Hut (θ = 0.7)");
+        situation.set_opening_angle(-1.);
+        assert_eq!(situation.opening_angle, 0.);
+        situation.set_opening_angle(10.);
+        assert_eq!(situation.solvers[situation.solver].to_string(), "Barnes-Hut (θ = 1.5)");
+    }
+}
@@ -0,0 +1,62 @@
+// Implements serde's Serialize and Deserialize for a struct, as a map of the listed fields; the rest are left out,
+// and so are taken from the given default when deserializing, along with the listed fields that are missing.
+// Scalar fields should be listed ahead of the nested ones, as some formats (like TOML) need them in that order.
+// Enums without fields are written as the names of their variants instead, all of which need to be listed.
+macro_rules! serializable {
+    (enum $type:ident { $($variant:ident),* $(,)? }) => {
+        impl serde::Serialize for $type {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    $($type::$variant => serializer.serialize_str(stringify!($variant)),)*
+                }
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let name = String::deserialize(deserializer)?;
+                match name.as_str() {
+                    $(stringify!($variant) => Ok($type::$variant),)*
+                    _ => Err(serde::de::Error::unknown_variant(&name, &[$(stringify!($variant)),*])),
+                }
+            }
+        }
+    };
+    ($type:ident { $($field:ident),* $(,)? } or $default:expr) => {
+        impl serde::Serialize for $type {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                use serde::ser::SerializeStruct;
+                let mut state = serializer.serialize_struct(stringify!($type), [$(stringify!($field)),*].len())?;
+                $(state.serialize_field(stringify!($field), &self.$field)?;)*
+                state.end()
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Visitor;
+
+                impl<'de> serde::de::Visitor<'de> for Visitor {
+                    type Value = $type;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "a map of the fields of {}", stringify!($type))
+                    }
+
+                    fn visit_map<M: serde::de::MapAccess<'de>>(self, mut map: M) -> Result<$type, M::Error> {
+                        let mut value: $type = $default;
+                        while let Some(key) = map.next_key::<String>()? {
+                            match key.as_str() {
+                                $(stringify!($field) => value.$field = map.next_value()?,)*
+                                _ => { map.next_value::<serde::de::IgnoredAny>()?; }
+                            }
+                        }
+                        Ok(value)
+                    }
+                }
+
+                deserializer.deserialize_struct(stringify!($type), &[$(stringify!($field)),*], Visitor)
+            }
+        }
+    };
+}
@@ -0,0 +1,72 @@
+use crate::maths::{consts::PI, Real};
+use crate::physics::orbit::Elements;
+use crate::physics::Body;
+
+const SECONDS_PER_DAY: Real = 86_400.;
+const SATELLITE_MASS: Real = 1000.; // kilograms, irrelevant next to the Earth anyway
+
+// Mean orbital elements of an Earth satellite, as given by a NORAD two-line element set, with the equatorial plane
+// taken for the one of the simulation.
+pub struct TwoLineElements {
+    pub name: String,
+    pub inclination: Real, // all angles in radians
+    pub right_ascension_of_ascending_node: Real,
+    pub eccentricity: Real,
+    pub argument_of_perigee: Real,
+    pub mean_anomaly: Real,
+    pub mean_motion: Real, // revolutions per day
+}
+
+#[derive(Debug)]
+pub enum TleError {
+    MissingLine(usize),
+    MalformedLine(String),
+}
+
+impl std::fmt::Display for TleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        match self {
+            Self::MissingLine(number) => write!(f, "missing line {} of an element set", number),
+            Self::MalformedLine(line) => write!(f, "malformed line: {}", line),
+        }
+    }
+}
+
+impl TwoLineElements {
+    // with the vernal equinox along the x axis
+    pub fn body_around(&self, earth: &Body, gravitational_parameter: Real) -> Body {
+        let mean_motion = self.mean_motion * 2. * PI / SECONDS_PER_DAY;
+        let semi_major_axis = (gravitational_parameter / (mean_motion * mean_motion)).cbrt();
+        let elements = Elements::elliptic(semi_major_axis, self.eccentricity, self.argument_of_perigee, gravitational_parameter)
+            .inclined(self.inclination, self.right_ascension_of_ascending_node);
+        let (position, velocity) = elements.state_at(self.mean_anomaly, gravitational_parameter);
+        Body::new().named(&self.name).with_mass(SATELLITE_MASS).at(earth.position + position).moving(earth.velocity + velocity)
+    }
+}
+
+// element sets in the three-line format, i.e. each preceded by the name of the satellite
+pub fn parse(text: &str) -> Result<Vec<TwoLineElements>, TleError> {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).filter(|line| !line.trim().is_empty()).collect();
+    lines.chunks(3).map(|chunk| match *chunk {
+        [name, first, second] => parse_set(name, first, second),
+        _ => Err(TleError::MissingLine(chunk.len() + 1)),
+    }).collect()
+}
+
+fn parse_set(name: &str, first: &str, second: &str) -> Result<TwoLineElements, TleError> {
+    if !first.starts_with("1 ") { return Err(TleError::MalformedLine(first.to_string())); }
+    if !second.starts_with("2 ") || second.len() < 63 { return Err(TleError::MalformedLine(second.to_string())); }
+
+    let field = |from: usize, to: usize| second[from..to].trim().parse::<Real>().map_err(|_| TleError::MalformedLine(second.to_string()));
+    let eccentricity = second[26..33].trim().parse::<Real>().map_err(|_| TleError::MalformedLine(second.to_string()))? / 1e7; // with an implied leading decimal point
+
+    Ok(TwoLineElements {
+        name: name.trim_start_matches("0 ").trim().to_string(),
+        inclination: field(8, 16)?.to_radians(),
+        right_ascension_of_ascending_node: field(17, 25)?.to_radians(),
+        eccentricity,
+        argument_of_perigee: field(34, 42)?.to_radians(),
+        mean_anomaly: field(43, 51)?.to_radians(),
+        mean_motion: field(52, 63)?,
+    })
+}
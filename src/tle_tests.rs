@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use crate::physics::units::{EARTH_MASS, GRAVITATIONAL_CONSTANT};
+    use crate::physics::orbit::Elements;
+    use crate::physics::{Body, Constants};
+    use crate::tle::{parse, TleError};
+
+    const ISS: &str = "\
+ISS (ZARYA)
+1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927
+2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537
+";
+
+    #[test]
+    #[cfg_attr(feature = "single-precision", allow(clippy::excessive_precision))]
+    fn element_set_is_parsed() {
+        let sets = parse(ISS).unwrap();
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].name, "ISS (ZARYA)");
+        assert!((sets[0].inclination.to_degrees() - 51.6416).abs() < 1e-9);
+        assert!((sets[0].right_ascension_of_ascending_node.to_degrees() - 247.4627).abs() < 1e-9);
+        assert!((sets[0].eccentricity - 0.0006703).abs() < 1e-12);
+        assert!((sets[0].mean_motion - 15.72125391).abs() < 1e-9);
+    }
+
+    #[test]
+    fn satellite_is_placed_in_low_earth_orbit() {
+        let earth = Body::new().with_mass(EARTH_MASS);
+        let satellite = parse(ISS).unwrap()[0].body_around(&earth, GRAVITATIONAL_CONSTANT * EARTH_MASS);
+        let altitude = (satellite.position - earth.position).magnitude() - 6.371e6;
+        assert!(altitude > 300e3 && altitude < 400e3);
+        assert!((satellite.velocity.magnitude() - 7.7e3).abs() < 100.);
+    }
+
+    #[test]
+    fn satellite_keeps_the_inclination_of_its_orbit() {
+        let earth = Body::new().with_mass(EARTH_MASS);
+        let satellite = parse(ISS).unwrap()[0].body_around(&earth, GRAVITATIONAL_CONSTANT * EARTH_MASS);
+        let elements = Elements::of(&satellite, &earth, &Constants::new());
+        assert!((elements.inclination.to_degrees() - 51.6416).abs() < 1e-6);
+        assert!((elements.longitude_of_ascending_node.to_degrees() - 247.4627 + 360.).abs() < 1e-6);
+    }
+
+    #[test]
+    fn incomplete_set_is_reported() {
+        assert!(matches!(parse("ISS (ZARYA)\n1 25544U"), Err(TleError::MissingLine(3))));
+    }
+
+    #[test]
+    fn malformed_line_is_reported() {
+        assert!(matches!(parse("ISS\n1 25544U\n2 garbage"), Err(TleError::MalformedLine(_))));
+    }
+}